@@ -17,8 +17,8 @@
 use std::fs;
 
 use simple_logger::SimpleLogger;
-use sqlparser::dialect::{Dialect, DialectDisplay};
-use sqlparser::parser::Parser;
+use sqlgen::dialect::{Dialect, DialectDisplay};
+use sqlgen::parser::Parser;
 
 fn main() {
     SimpleLogger::new().init().unwrap();
@@ -10,17 +10,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use criterion::{criterion_group, criterion_main, Criterion};
-use sqlparser::dialect::GenericDialect;
-use sqlparser::parser::Parser;
+use std::collections::HashSet;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use sqlgen::dialect::{Dialect, DialectDisplay};
+use sqlgen::parser::Parser;
 
 fn basic_queries(c: &mut Criterion) {
     let mut group = c.benchmark_group("sqlparser-rs parsing benchmark");
-    let dialect = GenericDialect {};
 
     let string = "SELECT * FROM table WHERE 1 = 1";
     group.bench_function("sqlparser::select", |b| {
-        b.iter(|| Parser::parse_sql(&dialect, string));
+        b.iter(|| Parser::parse_sql_query(string));
     });
 
     let with_query = "
@@ -35,9 +36,51 @@ fn basic_queries(c: &mut Criterion) {
         LEFT JOIN derived USING (user_id)
     ";
     group.bench_function("sqlparser::with_select", |b| {
-        b.iter(|| Parser::parse_sql(&dialect, with_query));
+        b.iter(|| Parser::parse_sql_query(with_query));
+    });
+}
+
+fn function_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sqlgen function lookup benchmark");
+    let dialect = Dialect::postgres();
+    let names = ["abs", "COUNT", "Date_Trunc", "regexp_replace", "not_a_function"];
+
+    // Function names stored as written, looked up exactly and then by a scan
+    // ignoring case
+    let functions: HashSet<String> = dialect.functions.iter().map(String::from).collect();
+    group.bench_function("sqlgen::function_scan", |b| {
+        b.iter(|| {
+            names
+                .iter()
+                .filter(|name| {
+                    functions.contains(**name)
+                        || functions
+                            .iter()
+                            .any(|function| function.eq_ignore_ascii_case(name))
+                })
+                .count()
+        });
+    });
+
+    // Function names stored lowercased, looked up with a single hash lookup
+    group.bench_function("sqlgen::supports_function", |b| {
+        b.iter(|| {
+            names
+                .iter()
+                .filter(|name| dialect.supports_function(black_box(name)))
+                .count()
+        });
+    });
+
+    let query = Parser::parse_sql_query(
+        "SELECT ABS(a), Upper(b), date_trunc('day', c), COUNT(*), Sum(d), coalesce(e, 0) \
+         FROM t GROUP BY 1, 2, 3",
+    )
+    .unwrap();
+    group.bench_function("sqlgen::render_functions", |b| {
+        b.iter(|| query.sql(&dialect));
     });
 }
 
-criterion_group!(benches, basic_queries);
+criterion_group!(benches, basic_queries, function_lookup);
 criterion_main!(benches);
@@ -91,6 +91,10 @@ pub enum DataType {
     String,
     /// Bytea
     Bytea,
+    /// JSON
+    Json,
+    /// Binary JSON, as in PostgreSQL
+    Jsonb,
     /// Custom type such as enums
     Custom(ObjectName),
     /// Arrays
@@ -102,7 +106,7 @@ pub enum DataType {
 }
 
 impl DialectDisplay for DataType {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         match self {
             DataType::Char(size) => {
                 format_type_with_optional_length(f, dialect, "CHAR", size, false)
@@ -113,17 +117,18 @@ impl DialectDisplay for DataType {
             DataType::Nvarchar(size) => {
                 format_type_with_optional_length(f, dialect, "NVARCHAR", size, false)
             }
-            DataType::Uuid => Ok(write!(f, "UUID")?),
-            DataType::Clob(size) => Ok(write!(f, "CLOB({})", size)?),
-            DataType::Binary(size) => Ok(write!(f, "BINARY({})", size)?),
-            DataType::Varbinary(size) => Ok(write!(f, "VARBINARY({})", size)?),
-            DataType::Blob(size) => Ok(write!(f, "BLOB({})", size)?),
+            DataType::Uuid => format_type(f, dialect, "UUID"),
+            DataType::Clob(size) => format_type_with_params(f, dialect, "CLOB", &[*size], false),
+            DataType::Binary(size) => {
+                format_type_with_params(f, dialect, "BINARY", &[*size], false)
+            }
+            DataType::Varbinary(size) => {
+                format_type_with_params(f, dialect, "VARBINARY", &[*size], false)
+            }
+            DataType::Blob(size) => format_type_with_params(f, dialect, "BLOB", &[*size], false),
             DataType::Decimal(precision, scale) => {
-                if let Some(scale) = scale {
-                    Ok(write!(f, "NUMERIC({},{})", precision.unwrap(), scale)?)
-                } else {
-                    format_type_with_optional_length(f, dialect, "NUMERIC", precision, false)
-                }
+                let params: Vec<u64> = precision.iter().chain(scale.iter()).copied().collect();
+                format_type_with_params(f, dialect, "NUMERIC", &params, false)
             }
             DataType::Float(size) => {
                 format_type_with_optional_length(f, dialect, "FLOAT", size, false)
@@ -158,18 +163,20 @@ impl DialectDisplay for DataType {
             DataType::UnsignedBigInt(zerofill) => {
                 format_type_with_optional_length(f, dialect, "BIGINT", zerofill, true)
             }
-            DataType::Real => Ok(write!(f, "REAL")?),
-            DataType::Double => Ok(write!(f, "DOUBLE")?),
-            DataType::Boolean => Ok(write!(f, "BOOLEAN")?),
-            DataType::Date => Ok(write!(f, "DATE")?),
-            DataType::Time => Ok(write!(f, "TIME")?),
-            DataType::Datetime => Ok(write!(f, "DATETIME")?),
-            DataType::Timestamp => Ok(write!(f, "TIMESTAMP")?),
-            DataType::Interval => Ok(write!(f, "INTERVAL")?),
-            DataType::Regclass => Ok(write!(f, "REGCLASS")?),
-            DataType::Text => Ok(write!(f, "TEXT")?),
-            DataType::String => Ok(write!(f, "STRING")?),
-            DataType::Bytea => Ok(write!(f, "BYTEA")?),
+            DataType::Real => format_type(f, dialect, "REAL"),
+            DataType::Double => format_type(f, dialect, "DOUBLE"),
+            DataType::Boolean => format_type(f, dialect, "BOOLEAN"),
+            DataType::Date => format_type(f, dialect, "DATE"),
+            DataType::Time => format_type(f, dialect, "TIME"),
+            DataType::Datetime => format_type(f, dialect, "DATETIME"),
+            DataType::Timestamp => format_type(f, dialect, "TIMESTAMP"),
+            DataType::Interval => format_type(f, dialect, "INTERVAL"),
+            DataType::Regclass => format_type(f, dialect, "REGCLASS"),
+            DataType::Text => format_type(f, dialect, "TEXT"),
+            DataType::String => format_type(f, dialect, "STRING"),
+            DataType::Bytea => format_type(f, dialect, "BYTEA"),
+            DataType::Json => format_type(f, dialect, "JSON"),
+            DataType::Jsonb => format_type(f, dialect, "JSONB"),
             DataType::Array(ty) => Ok(write!(f, "{}[]", ty.sql(dialect)?)?),
             DataType::Custom(ty) => Ok(write!(f, "{}", ty.sql(dialect)?)?),
             DataType::Enum(vals) => {
@@ -196,16 +203,125 @@ impl DialectDisplay for DataType {
     }
 }
 
+impl DataType {
+    /// The function that casts to this type in place of `CAST`, if the dialect
+    /// has one, as in `PARSE_JSON(x)`
+    pub(crate) fn cast_function<'a>(&self, dialect: &'a Dialect) -> Option<&'a str> {
+        dialect
+            .data_type_name(self.default_spelling()?)
+            .and_then(|mapped| mapped.cast_function.as_deref())
+    }
+
+    /// The type as written in `CAST(x AS type)`: the dialect's cast name for it
+    /// if it has one, or else the type itself. Unsigned integers with a cast
+    /// name are cast to `UNSIGNED`.
+    pub(crate) fn cast_sql(&self, dialect: &Dialect) -> Result<String, SqlGenError> {
+        let cast_name = self
+            .default_spelling()
+            .and_then(|spelling| dialect.data_type_name(spelling))
+            .and_then(|mapped| mapped.cast_name.as_deref());
+        Ok(match cast_name {
+            Some(_) if self.is_unsigned() => "UNSIGNED".to_string(),
+            Some(name) => name.to_string(),
+            None => self.sql(dialect)?,
+        })
+    }
+
+    fn is_unsigned(&self) -> bool {
+        matches!(
+            self,
+            DataType::UnsignedTinyInt(_)
+                | DataType::UnsignedSmallInt(_)
+                | DataType::UnsignedInt(_)
+                | DataType::UnsignedInteger(_)
+                | DataType::UnsignedBigInt(_)
+        )
+    }
+
+    /// The style code passed to `CONVERT` when casting to this type, if the
+    /// dialect has one for it
+    pub(crate) fn convert_style(&self, dialect: &Dialect) -> Option<u32> {
+        dialect
+            .data_type_name(self.default_spelling()?)
+            .and_then(|mapped| mapped.convert_style)
+    }
+
+    /// The default spelling of the type, as used in a dialect's data type
+    /// mapping. Custom, array, enum and set types have none.
+    fn default_spelling(&self) -> Option<&'static str> {
+        Some(match self {
+            DataType::Char(_) => "CHAR",
+            DataType::Varchar(_) => "CHARACTER VARYING",
+            DataType::Nvarchar(_) => "NVARCHAR",
+            DataType::Uuid => "UUID",
+            DataType::Clob(_) => "CLOB",
+            DataType::Binary(_) => "BINARY",
+            DataType::Varbinary(_) => "VARBINARY",
+            DataType::Blob(_) => "BLOB",
+            DataType::Decimal(_, _) => "NUMERIC",
+            DataType::Float(_) => "FLOAT",
+            DataType::TinyInt(_) | DataType::UnsignedTinyInt(_) => "TINYINT",
+            DataType::SmallInt(_) | DataType::UnsignedSmallInt(_) => "SMALLINT",
+            DataType::Int(_) | DataType::UnsignedInt(_) => "INT",
+            DataType::Integer(_) | DataType::UnsignedInteger(_) => "INTEGER",
+            DataType::BigInt(_) | DataType::UnsignedBigInt(_) => "BIGINT",
+            DataType::Real => "REAL",
+            DataType::Double => "DOUBLE",
+            DataType::Boolean => "BOOLEAN",
+            DataType::Date => "DATE",
+            DataType::Time => "TIME",
+            DataType::Datetime => "DATETIME",
+            DataType::Timestamp => "TIMESTAMP",
+            DataType::Interval => "INTERVAL",
+            DataType::Regclass => "REGCLASS",
+            DataType::Text => "TEXT",
+            DataType::String => "STRING",
+            DataType::Bytea => "BYTEA",
+            DataType::Json => "JSON",
+            DataType::Jsonb => "JSONB",
+            DataType::Custom(_) | DataType::Array(_) | DataType::Enum(_) | DataType::Set(_) => {
+                return None
+            }
+        })
+    }
+}
+
 fn format_type_with_optional_length(
-    f: &mut (dyn fmt::Write),
-    _dialect: &Dialect,
+    f: &mut dyn fmt::Write,
+    dialect: &Dialect,
     sql_type: &'static str,
     len: &Option<u64>,
     unsigned: bool,
 ) -> Result<(), SqlGenError> {
-    write!(f, "{}", sql_type)?;
-    if let Some(len) = len {
-        write!(f, "({})", len)?;
+    let params: Vec<u64> = len.iter().copied().collect();
+    format_type_with_params(f, dialect, sql_type, &params, unsigned)
+}
+
+fn format_type(
+    f: &mut dyn fmt::Write,
+    dialect: &Dialect,
+    sql_type: &'static str,
+) -> Result<(), SqlGenError> {
+    format_type_with_params(f, dialect, sql_type, &[], false)
+}
+
+/// Write a type using the dialect's spelling, followed by its parameters if the
+/// dialect allows them for this type
+fn format_type_with_params(
+    f: &mut dyn fmt::Write,
+    dialect: &Dialect,
+    sql_type: &'static str,
+    params: &[u64],
+    unsigned: bool,
+) -> Result<(), SqlGenError> {
+    let (name, parameters) = match dialect.data_type_name(sql_type) {
+        Some(mapped) => (mapped.name.as_str(), mapped.parameters),
+        None => (sql_type, true),
+    };
+    write!(f, "{}", name)?;
+    if parameters && !params.is_empty() {
+        let params: Vec<String> = params.iter().map(|p| p.to_string()).collect();
+        write!(f, "({})", params.join(","))?;
     }
     if unsigned {
         write!(f, " UNSIGNED")?;
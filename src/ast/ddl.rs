@@ -0,0 +1,108 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Statements that create tables and views
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ast::*;
+use crate::dialect::{Dialect, DialectDisplay, ViewReplaceStyle};
+
+/// `CREATE [ OR REPLACE ] [ TEMPORARY ] TABLE name AS query`, which creates a
+/// table from the result of a query like [SelectInto] without the query
+/// having to be a `SELECT`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateTableAs {
+    /// Replace the table if it exists
+    pub or_replace: bool,
+    pub temporary: bool,
+    pub name: ObjectName,
+    pub query: Box<Query>,
+}
+
+impl DialectDisplay for CreateTableAs {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if self.or_replace && !dialect.supports_create_or_replace_table {
+            return Err(dialect.unsupported("CREATE OR REPLACE TABLE"));
+        }
+        let or_replace = if self.or_replace { " OR REPLACE" } else { "" };
+        let temporary = if self.temporary { " TEMPORARY" } else { "" };
+        Ok(write!(
+            f,
+            "CREATE{}{} TABLE {} AS {}",
+            or_replace,
+            temporary,
+            self.name.sql(dialect)?,
+            self.query.sql(dialect)?
+        )?)
+    }
+}
+
+/// `CREATE [ OR REPLACE ] [ MATERIALIZED ] VIEW name [ (columns) ] AS query`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreateView {
+    /// Replace the view if it exists
+    pub or_replace: bool,
+    /// Store the result of the query rather than running it when the view is
+    /// queried
+    pub materialized: bool,
+    pub name: ObjectName,
+    /// The names of the columns of the view, or the names of the columns of
+    /// the query if empty
+    pub columns: Vec<Ident>,
+    pub query: Box<Query>,
+}
+
+impl DialectDisplay for CreateView {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if self.materialized && !dialect.supports_materialized_views {
+            return Err(dialect.unsupported("Materialized view"));
+        }
+        if self.or_replace && self.materialized && !dialect.supports_replace_materialized_view {
+            return Err(dialect.unsupported("CREATE OR REPLACE MATERIALIZED VIEW"));
+        }
+        let or_replace = match (self.or_replace, dialect.view_replace_style) {
+            (false, _) => "",
+            (true, ViewReplaceStyle::OrReplace) => " OR REPLACE",
+            (true, ViewReplaceStyle::OrAlter) => " OR ALTER",
+            (true, ViewReplaceStyle::Unsupported) => {
+                return Err(dialect.unsupported("CREATE OR REPLACE VIEW"))
+            }
+        };
+        let materialized = if self.materialized {
+            " MATERIALIZED"
+        } else {
+            ""
+        };
+        write!(
+            f,
+            "CREATE{}{} VIEW {}",
+            or_replace,
+            materialized,
+            self.name.sql(dialect)?
+        )?;
+        if !self.columns.is_empty() {
+            write!(
+                f,
+                " ({})",
+                display_comma_separated(&self.columns).sql(dialect)?
+            )?;
+        }
+        Ok(write!(f, " AS {}", self.query.sql(dialect)?)?)
+    }
+}
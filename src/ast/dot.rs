@@ -0,0 +1,171 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Graphviz DOT output of the structure of a query, for debugging
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::ast::*;
+
+impl Query {
+    /// Describe the structure of the query as a Graphviz DOT graph, with a
+    /// node for each query, set operation, select, join and table, and edges
+    /// to their children. Expressions are not included.
+    pub fn to_dot(&self) -> String {
+        let mut graph = DotGraph::default();
+        graph.query(self);
+        let mut dot = String::from("digraph query {\n    node [shape=box];\n");
+        for (id, label) in graph.nodes.iter().enumerate() {
+            dot.push_str(&format!("    n{} [label=\"{}\"];\n", id, escape(label)));
+        }
+        for (from, to) in &graph.edges {
+            dot.push_str(&format!("    n{} -> n{};\n", from, to));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[derive(Default)]
+struct DotGraph {
+    nodes: Vec<String>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl DotGraph {
+    fn node(&mut self, label: String, parent: Option<usize>) -> usize {
+        let id = self.nodes.len();
+        self.nodes.push(label);
+        if let Some(parent) = parent {
+            self.edges.push((parent, id));
+        }
+        id
+    }
+
+    fn query(&mut self, query: &Query) -> usize {
+        let id = self.node("Query".to_string(), None);
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                let cte_id = self.node(format!("CTE {}", cte.alias.name.value), Some(id));
+                let query_id = self.query(&cte.query);
+                self.edges.push((cte_id, query_id));
+            }
+        }
+        self.set_expr(&query.body, id);
+        id
+    }
+
+    fn set_expr(&mut self, set_expr: &SetExpr, parent: usize) {
+        match set_expr {
+            SetExpr::Select(select) => {
+                let id = self.node("Select".to_string(), Some(parent));
+                for table in &select.from {
+                    self.table_with_joins(table, id);
+                }
+            }
+            SetExpr::Query(query) => {
+                let id = self.query(query);
+                self.edges.push((parent, id));
+            }
+            SetExpr::SetOperation {
+                op,
+                all,
+                left,
+                right,
+            } => {
+                let op = match op {
+                    SetOperator::Union => "UNION",
+                    SetOperator::Except => "EXCEPT",
+                    SetOperator::Intersect => "INTERSECT",
+                };
+                let label = format!("{}{}", op, if *all { " ALL" } else { "" });
+                let id = self.node(label, Some(parent));
+                self.set_expr(left, id);
+                self.set_expr(right, id);
+            }
+            SetExpr::Values(values) => {
+                self.node(format!("Values ({} rows)", values.0.len()), Some(parent));
+            }
+        }
+    }
+
+    fn table_with_joins(&mut self, table: &TableWithJoins, parent: usize) {
+        // Joins are nested left to right, so that `a JOIN b JOIN c` has the
+        // join of `a` and `b` as the left child of the join with `c`
+        let mut left = self.table_factor(&table.relation);
+        for join in &table.joins {
+            let kind = match &join.join_operator {
+                JoinOperator::Inner(_) => "INNER JOIN",
+                JoinOperator::LeftOuter(_) => "LEFT JOIN",
+                JoinOperator::RightOuter(_) => "RIGHT JOIN",
+                JoinOperator::FullOuter(_) => "FULL JOIN",
+                JoinOperator::CrossJoin => "CROSS JOIN",
+                JoinOperator::CrossApply => "CROSS APPLY",
+                JoinOperator::OuterApply => "OUTER APPLY",
+            };
+            let id = self.node(kind.to_string(), None);
+            let right = self.table_factor(&join.relation);
+            self.edges.push((id, left));
+            self.edges.push((id, right));
+            left = id;
+        }
+        self.edges.push((parent, left));
+    }
+
+    fn table_factor(&mut self, table_factor: &TableFactor) -> usize {
+        match table_factor {
+            TableFactor::Table { name, alias, .. } => {
+                let name: Vec<&str> = name.0.iter().map(|ident| ident.value.as_str()).collect();
+                self.node(
+                    format!("Table {}{}", name.join("."), alias_label(alias)),
+                    None,
+                )
+            }
+            TableFactor::Derived {
+                subquery, alias, ..
+            } => {
+                let id = self.node(format!("Derived{}", alias_label(alias)), None);
+                let query_id = self.query(subquery);
+                self.edges.push((id, query_id));
+                id
+            }
+            TableFactor::TableFunction { alias, .. } => {
+                self.node(format!("Table function{}", alias_label(alias)), None)
+            }
+            TableFactor::UNNEST { alias, .. } => {
+                self.node(format!("UNNEST{}", alias_label(alias)), None)
+            }
+            TableFactor::NestedJoin(table) => {
+                let id = self.node("Nested join".to_string(), None);
+                self.table_with_joins(table, id);
+                id
+            }
+        }
+    }
+}
+
+fn alias_label(alias: &Option<TableAlias>) -> String {
+    match alias {
+        Some(alias) => format!(" AS {}", alias.name.value),
+        None => String::new(),
+    }
+}
+
+/// Escape a label for a double quoted DOT string
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,93 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `EXPLAIN` of a query, for debugging generated queries
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, format, string::ToString, vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ast::*;
+use crate::dialect::{Dialect, DialectDisplay, ExplainStyle};
+
+/// `EXPLAIN [ ANALYZE ] [ VERBOSE ] query`, with an output format in the
+/// syntax of the dialect
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Explain {
+    /// Run the query and report its actual costs
+    pub analyze: bool,
+    pub verbose: bool,
+    /// The format of the plan, or the engine's default if `None`
+    pub format: Option<ExplainFormat>,
+    pub query: Box<Query>,
+}
+
+/// The output format of an [Explain]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ExplainFormat {
+    Text,
+    Json,
+}
+
+impl DialectDisplay for ExplainFormat {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
+        Ok(f.write_str(match self {
+            ExplainFormat::Text => "TEXT",
+            ExplainFormat::Json => "JSON",
+        })?)
+    }
+}
+
+impl DialectDisplay for Explain {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        write!(f, "EXPLAIN")?;
+        match (dialect.explain_style, self.format) {
+            // The format is only accepted in the parenthesized option list,
+            // which then holds the other options too
+            (ExplainStyle::Options, Some(format)) => {
+                let mut options = vec![];
+                if self.analyze {
+                    options.push("ANALYZE".to_string());
+                }
+                if self.verbose {
+                    options.push("VERBOSE".to_string());
+                }
+                options.push(format!("FORMAT {}", format.sql(dialect)?));
+                write!(f, " ({})", options.join(", "))?;
+            }
+            (ExplainStyle::Options, None) => {
+                if self.analyze {
+                    write!(f, " ANALYZE")?;
+                }
+                if self.verbose {
+                    write!(f, " VERBOSE")?;
+                }
+            }
+            (ExplainStyle::Assignment, format) => {
+                if self.verbose {
+                    return Err(dialect.unsupported("EXPLAIN VERBOSE"));
+                }
+                if self.analyze {
+                    write!(f, " ANALYZE")?;
+                }
+                if let Some(format) = format {
+                    write!(f, " FORMAT={}", format.sql(dialect)?)?;
+                }
+            }
+        }
+        Ok(write!(f, " {}", self.query.sql(dialect)?)?)
+    }
+}
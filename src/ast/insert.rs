@@ -0,0 +1,115 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, string::ToString, vec::Vec};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ast::*;
+use crate::dialect::{Dialect, DialectDisplay, UpsertStyle};
+
+/// `INSERT INTO table [ (columns) ] { VALUES ... | SELECT ... } [ ON CONFLICT ... ]`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Insert {
+    pub table_name: ObjectName,
+    /// The columns to insert into, or all columns of the table if empty
+    pub columns: Vec<Ident>,
+    /// The rows to insert, a query whose body is `VALUES` or a `SELECT`
+    pub source: Box<Query>,
+    pub on_conflict: Option<OnConflict>,
+}
+
+/// What to do with a row that conflicts with an existing row
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum OnConflict {
+    /// `ON CONFLICT [ (columns) ] DO NOTHING`
+    DoNothing { conflict_target: Vec<Ident> },
+    /// `ON CONFLICT (columns) DO UPDATE SET ...`. The row that was to be
+    /// inserted is referenced as `excluded`, as in `a = excluded.a`.
+    DoUpdate {
+        conflict_target: Vec<Ident>,
+        assignments: Vec<Assignment>,
+    },
+}
+
+impl DialectDisplay for Insert {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        write!(f, "INSERT INTO {}", self.table_name.sql(dialect)?)?;
+        if !self.columns.is_empty() {
+            write!(
+                f,
+                " ({})",
+                display_comma_separated(&self.columns).sql(dialect)?
+            )?;
+        }
+        write!(f, " {}", self.source.sql(dialect)?)?;
+        let on_conflict = match &self.on_conflict {
+            Some(on_conflict) => on_conflict,
+            None => return Ok(()),
+        };
+        match (dialect.upsert_style, on_conflict) {
+            (UpsertStyle::OnConflict, OnConflict::DoNothing { conflict_target }) => {
+                write!(f, " ON CONFLICT")?;
+                if !conflict_target.is_empty() {
+                    write!(
+                        f,
+                        " ({})",
+                        display_comma_separated(conflict_target).sql(dialect)?
+                    )?;
+                }
+                write!(f, " DO NOTHING")?;
+            }
+            (
+                UpsertStyle::OnConflict,
+                OnConflict::DoUpdate {
+                    conflict_target,
+                    assignments,
+                },
+            ) => {
+                if conflict_target.is_empty() {
+                    return Err(SqlGenError::DialectError(
+                        "ON CONFLICT DO UPDATE requires conflict target columns".to_string(),
+                    ));
+                }
+                write!(
+                    f,
+                    " ON CONFLICT ({}) DO UPDATE SET {}",
+                    display_comma_separated(conflict_target).sql(dialect)?,
+                    display_comma_separated(assignments).sql(dialect)?
+                )?;
+            }
+            // MySQL updates on a conflict with any unique key, and names the row
+            // to be inserted with an alias of the VALUES. The rows of a SELECT
+            // can't be named that way.
+            (UpsertStyle::OnDuplicateKey, OnConflict::DoUpdate { assignments, .. }) => {
+                if !matches!(self.source.body.as_ref(), SetExpr::Values(_)) {
+                    return Err(dialect.unsupported("ON CONFLICT DO UPDATE from a SELECT"));
+                }
+                write!(f, " AS {}", Ident::new("excluded").sql(dialect)?)?;
+                write!(
+                    f,
+                    " ON DUPLICATE KEY UPDATE {}",
+                    display_comma_separated(assignments).sql(dialect)?
+                )?;
+            }
+            (UpsertStyle::OnDuplicateKey, OnConflict::DoNothing { .. }) => {
+                return Err(dialect.unsupported("ON CONFLICT DO NOTHING"))
+            }
+            (UpsertStyle::Unsupported, _) => return Err(dialect.unsupported("ON CONFLICT")),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checks for queries that render to valid SQL but are likely to be wrong or
+//! to be rejected by some engines
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, vec::Vec};
+
+use crate::ast::scope::{add_qualifiers, qualifiers, same_ident};
+use crate::ast::visit::{walk_expr, walk_query, walk_select, Visitor};
+use crate::ast::*;
+use crate::dialect::{Dialect, DialectDisplay, IdentifierCase};
+
+/// How serious a [Lint] is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The query is valid but fragile or non-portable
+    Warning,
+    /// The query is almost certainly wrong
+    Error,
+}
+
+/// The check that produced a [Lint]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintKind {
+    /// `SELECT DISTINCT` ordered by an expression that isn't selected, which
+    /// engines such as PostgreSQL reject
+    DistinctOrderBy,
+    /// A wildcard in the projection of a branch of `UNION`, `EXCEPT` or
+    /// `INTERSECT`, whose columns then silently change with the schema
+    SetOperationWildcard,
+    /// A `NATURAL` join, whose join columns silently change with the schema
+    NaturalJoin,
+    /// A qualified reference to a column merged by `JOIN ... USING`, such as
+    /// `t.id` after `USING (id)`, which engines such as Oracle reject
+    QualifiedUsingColumn,
+    /// A comparison with `NULL` such as `x = NULL`, which is never true and
+    /// was probably meant to be `x IS NULL`
+    NullComparison,
+}
+
+/// A problem found in a query by [Query::lint]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    pub kind: LintKind,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Query {
+    /// Check the query, and the queries nested in it, for constructs that are
+    /// likely to be wrong or not portable
+    pub fn lint(&self) -> Result<Vec<Lint>, SqlGenError> {
+        let mut linter = Linter::new(IdentifierCase::Insensitive);
+        linter.visit_query(self)?;
+        Ok(linter.lints)
+    }
+
+    /// Like [Query::lint], comparing identifiers by the dialect's
+    /// [IdentifierCase]
+    pub fn lint_for(&self, dialect: &Dialect) -> Result<Vec<Lint>, SqlGenError> {
+        let mut linter = Linter::new(dialect.identifier_case);
+        linter.visit_query(self)?;
+        Ok(linter.lints)
+    }
+}
+
+struct Linter {
+    /// How identifiers are compared
+    case: IdentifierCase,
+    lints: Vec<Lint>,
+    /// The tables in scope of the selects being visited, innermost last
+    scopes: Vec<UsingScope>,
+}
+
+/// The qualifiers of the tables in the `FROM` clause of a select, and the
+/// qualified columns that `USING` joins merge
+struct UsingScope {
+    qualifiers: Vec<Ident>,
+    merged: Vec<(Ident, Ident)>,
+}
+
+/// Add the qualified columns merged by the `USING` joins of `table`: each
+/// column qualified by each table on either side of its join
+fn add_using_columns(merged: &mut Vec<(Ident, Ident)>, table: &TableWithJoins) {
+    if let TableFactor::NestedJoin(nested) = &table.relation {
+        add_using_columns(merged, nested);
+    }
+    let mut left = vec![];
+    add_qualifiers(&mut left, &table.relation);
+    for join in &table.joins {
+        if let TableFactor::NestedJoin(nested) = &join.relation {
+            add_using_columns(merged, nested);
+        }
+        let mut right = vec![];
+        add_qualifiers(&mut right, &join.relation);
+        if let JoinOperator::Inner(JoinConstraint::Using(columns))
+        | JoinOperator::LeftOuter(JoinConstraint::Using(columns))
+        | JoinOperator::RightOuter(JoinConstraint::Using(columns))
+        | JoinOperator::FullOuter(JoinConstraint::Using(columns)) = &join.join_operator
+        {
+            for qualifier in left.iter().chain(&right) {
+                for column in columns {
+                    merged.push((qualifier.clone(), column.clone()));
+                }
+            }
+        }
+        left.extend(right);
+    }
+}
+
+/// Whether two expressions are equal, comparing column references by `case`
+fn same_column_expr(a: &Expr, b: &Expr, case: IdentifierCase) -> bool {
+    match (a, b) {
+        (Expr::Identifier(a), Expr::Identifier(b)) => same_ident(a, b, case),
+        (Expr::CompoundIdentifier(a), Expr::CompoundIdentifier(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_ident(a, b, case))
+        }
+        _ => a == b,
+    }
+}
+
+impl Linter {
+    fn new(case: IdentifierCase) -> Self {
+        Linter {
+            case,
+            lints: vec![],
+            scopes: vec![],
+        }
+    }
+
+    /// Flag `SELECT DISTINCT ... ORDER BY x` where `x` is neither selected nor
+    /// the alias or position of a selected expression
+    fn check_distinct_order_by(&mut self, query: &Query) -> Result<(), SqlGenError> {
+        let select = match query.body.as_ref() {
+            SetExpr::Select(select) if select.distinct => select,
+            _ => return Ok(()),
+        };
+        let has_wildcard = select.projection.iter().any(|item| {
+            matches!(
+                item,
+                SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..)
+            )
+        });
+        let same = |a: &Expr, b: &Expr| same_column_expr(a, b, self.case);
+        for order_by in &query.order_by {
+            let expr = &order_by.expr;
+            let selected = select.projection.iter().any(|item| match item {
+                SelectItem::UnnamedExpr(selected) => same(selected, expr),
+                SelectItem::ExprWithAlias {
+                    expr: selected,
+                    alias,
+                } => {
+                    same(selected, expr)
+                        || matches!(expr, Expr::Identifier(ident) if same_ident(ident, alias, self.case))
+                }
+                SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..) => false,
+            });
+            // Columns may come from a wildcard, so only other expressions are
+            // flagged
+            let column = matches!(expr, Expr::Identifier(_) | Expr::CompoundIdentifier(_));
+            let position = matches!(expr, Expr::Value(Value::Number(_, _)));
+            if !(selected || position || has_wildcard && column) {
+                self.lints.push(Lint {
+                    kind: LintKind::DistinctOrderBy,
+                    severity: Severity::Warning,
+                    message: format!(
+                        "ORDER BY expression {} is not in the select list of SELECT DISTINCT",
+                        expr.sql(&Dialect::generic())?
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Flag `SELECT *` and `SELECT t.*` in the branches of a set operation.
+    /// Branches that are parenthesized set operations are checked when their
+    /// own query is visited.
+    fn check_set_operation_wildcard(&mut self, query: &Query) -> Result<(), SqlGenError> {
+        if let SetExpr::SetOperation { .. } = query.body.as_ref() {
+            self.check_set_operation_branch(None, &query.body)?;
+        }
+        Ok(())
+    }
+
+    fn check_set_operation_branch(
+        &mut self,
+        op: Option<&SetOperator>,
+        branch: &SetExpr,
+    ) -> Result<(), SqlGenError> {
+        match (op, branch) {
+            (
+                _,
+                SetExpr::SetOperation {
+                    op, left, right, ..
+                },
+            ) => {
+                self.check_set_operation_branch(Some(op), left)?;
+                self.check_set_operation_branch(Some(op), right)?;
+            }
+            (Some(op), SetExpr::Select(select)) => {
+                let has_wildcard = select.projection.iter().any(|item| {
+                    matches!(
+                        item,
+                        SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..)
+                    )
+                });
+                if has_wildcard {
+                    self.lints.push(Lint {
+                        kind: LintKind::SetOperationWildcard,
+                        severity: Severity::Warning,
+                        message: format!(
+                            "A branch of {} selects a wildcard, so its columns change with the schema",
+                            op.sql(&Dialect::generic())?
+                        ),
+                    });
+                }
+            }
+            (Some(_), SetExpr::Query(query))
+                if !matches!(query.body.as_ref(), SetExpr::SetOperation { .. }) =>
+            {
+                self.check_set_operation_branch(op, &query.body)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Flag the `NATURAL` joins of a `FROM` clause, including those in nested
+    /// joins
+    fn check_natural_join(&mut self, table: &TableWithJoins) -> Result<(), SqlGenError> {
+        if let TableFactor::NestedJoin(nested) = &table.relation {
+            self.check_natural_join(nested)?;
+        }
+        for join in &table.joins {
+            if let TableFactor::NestedJoin(nested) = &join.relation {
+                self.check_natural_join(nested)?;
+            }
+            if let JoinOperator::Inner(JoinConstraint::Natural)
+            | JoinOperator::LeftOuter(JoinConstraint::Natural)
+            | JoinOperator::RightOuter(JoinConstraint::Natural)
+            | JoinOperator::FullOuter(JoinConstraint::Natural) = &join.join_operator
+            {
+                self.lints.push(Lint {
+                    kind: LintKind::NaturalJoin,
+                    severity: Severity::Warning,
+                    message: format!(
+                        "NATURAL join with {} joins on the columns the tables share, which change with the schema",
+                        join.relation.sql(&Dialect::generic())?
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Flag `qualifier.column` where the innermost select with a table named
+    /// `qualifier` merges `column` in a `USING` join
+    fn check_qualified_using_column(&mut self, expr: &Expr) -> Result<(), SqlGenError> {
+        let (qualifier, column) = match expr {
+            Expr::CompoundIdentifier(parts) => match parts.as_slice() {
+                [.., qualifier, column] => (qualifier, column),
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let same = |a: &Ident, b: &Ident| same_ident(a, b, self.case);
+        let scope = self
+            .scopes
+            .iter()
+            .rev()
+            .find(|scope| scope.qualifiers.iter().any(|name| same(name, qualifier)));
+        let merged = scope.is_some_and(|scope| {
+            scope
+                .merged
+                .iter()
+                .any(|(name, merged)| same(name, qualifier) && same(merged, column))
+        });
+        if merged {
+            self.lints.push(Lint {
+                kind: LintKind::QualifiedUsingColumn,
+                severity: Severity::Warning,
+                message: format!(
+                    "Column {} is merged by a USING join and shouldn't be qualified",
+                    expr.sql(&Dialect::generic())?
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Flag `=` and `<>` comparisons with the `NULL` literal
+    fn check_null_comparison(&mut self, expr: &Expr) -> Result<(), SqlGenError> {
+        if let Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::Eq | BinaryOperator::NotEq),
+            right,
+        } = expr
+        {
+            if left.is_null() || right.is_null() {
+                let test = if *op == BinaryOperator::Eq {
+                    "IS NULL"
+                } else {
+                    "IS NOT NULL"
+                };
+                self.lints.push(Lint {
+                    kind: LintKind::NullComparison,
+                    severity: Severity::Warning,
+                    message: format!(
+                        "Comparison {} is never true, use {} to test for NULL",
+                        expr.sql(&Dialect::generic())?,
+                        test
+                    ),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Visitor for Linter {
+    fn visit_query(&mut self, query: &Query) -> Result<(), SqlGenError> {
+        self.check_distinct_order_by(query)?;
+        self.check_set_operation_wildcard(query)?;
+        walk_query(self, query)
+    }
+
+    fn visit_select(&mut self, select: &Select) -> Result<(), SqlGenError> {
+        let mut merged = vec![];
+        for table in &select.from {
+            self.check_natural_join(table)?;
+            add_using_columns(&mut merged, table);
+        }
+        self.scopes.push(UsingScope {
+            qualifiers: qualifiers(&select.from),
+            merged,
+        });
+        walk_select(self, select)?;
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) -> Result<(), SqlGenError> {
+        self.check_qualified_using_column(expr)?;
+        self.check_null_comparison(expr)?;
+        walk_expr(self, expr)
+    }
+}
@@ -12,9 +12,18 @@
 
 //! SQL Abstract Syntax Tree (AST) types
 mod data_type;
+mod ddl;
+mod dot;
+mod explain;
+mod insert;
+mod lint;
 mod operator;
 mod query;
+mod rewrite;
+mod scope;
+mod statement;
 mod value;
+mod visitor;
 
 #[cfg(not(feature = "std"))]
 use alloc::{
@@ -24,33 +33,50 @@ use alloc::{
 };
 use core::fmt;
 
-use crate::dialect::{Dialect, DialectDisplay};
+use crate::ast::query::RenderDepthGuard;
+use crate::dialect::{
+    string_arg, ArrayIndexStyle, ArrayStyle, BooleanLiteralStyle, CastStyle, CountDistinctStyle,
+    DateAddStyle, DatePartStyle, Dialect, DialectDisplay, EpochStyle, IdentifierHook,
+    IntervalMultiplicationStyle, ModuloStyle, RegexMatchStyle, StringConcatStyle, StructStyle,
+    TryCastStyle, UnknownFunctionPolicy,
+};
+use crate::keywords::ALL_KEYWORDS;
 use crate::parser::SqlGenError;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 pub use self::data_type::DataType;
+pub use self::ddl::{CreateTableAs, CreateView};
+pub use self::explain::{Explain, ExplainFormat};
+pub use self::insert::{Insert, OnConflict};
+pub use self::lint::{Lint, LintKind, Severity};
 pub use self::operator::{BinaryOperator, UnaryOperator};
 pub use self::query::{
-    Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, LockType, Offset, OffsetRows,
-    OrderByExpr, Query, Select, SelectInto, SelectItem, SetExpr, SetOperator, TableAlias,
-    TableFactor, TableWithJoins, Top, Values, With,
+    Cte, Fetch, Join, JoinConstraint, JoinOperator, LateralView, LimitBy, LockType, Offset,
+    OffsetRows, OrderByExpr, Query, Select, SelectInto, SelectItem, SetExpr, SetOperator, Setting,
+    TableAlias, TableFactor, TableSample, TableSampleMethod, TableWithJoins, Top, Values,
+    WildcardOptions, With,
 };
+pub use self::rewrite::Schema;
+pub use self::statement::{render_script, Statement};
 pub use self::value::{DateTimeField, TrimWhereField, Value};
+pub use self::visitor::{visit, visit_mut};
 
+/// The items of a slice rendered with a separator between them, which renders
+/// nothing for an empty slice
 struct DisplaySeparated<'a, T>
 where
     T: DialectDisplay,
 {
     slice: &'a [T],
-    sep: &'static str,
+    sep: &'a str,
 }
 
 impl<'a, T> DialectDisplay for DisplaySeparated<'a, T>
 where
     T: DialectDisplay,
 {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         let mut delim = "";
         for t in self.slice {
             write!(f, "{}", delim)?;
@@ -61,7 +87,7 @@ where
     }
 }
 
-fn display_separated<'a, T>(slice: &'a [T], sep: &'static str) -> DisplaySeparated<'a, T>
+fn display_separated<'a, T>(slice: &'a [T], sep: &'a str) -> DisplaySeparated<'a, T>
 where
     T: DialectDisplay,
 {
@@ -84,6 +110,9 @@ pub struct Ident {
     /// The starting quote if any. Valid quote characters are the single quote,
     /// double quote, backtick, and opening square bracket.
     pub quote_style: Option<char>,
+    /// Whether the identifier is quoted even when the dialect doesn't quote
+    /// identifiers, with the dialect's quote, `quote_style` or a double quote
+    pub force_quote: bool,
 }
 
 impl Ident {
@@ -95,6 +124,7 @@ impl Ident {
         Ident {
             value: value.into(),
             quote_style: None,
+            force_quote: false,
         }
     }
 
@@ -108,6 +138,21 @@ impl Ident {
         Ident {
             value: value.into(),
             quote_style: Some(quote),
+            force_quote: false,
+        }
+    }
+
+    /// Create a new identifier with the given value that is always quoted,
+    /// such as a case sensitive name in a dialect that doesn't quote
+    /// identifiers
+    pub fn force_quoted<S>(value: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Ident {
+            value: value.into(),
+            quote_style: None,
+            force_quote: true,
         }
     }
 }
@@ -117,31 +162,116 @@ impl From<&str> for Ident {
         Ident {
             value: value.to_string(),
             quote_style: None,
+            force_quote: false,
         }
     }
 }
 
 impl DialectDisplay for Ident {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
-        Ok(match dialect.quote_style {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        self.fmt_with(
+            f,
+            dialect,
+            dialect.identifier_hook.as_ref(),
+            dialect.quote_style,
+            false,
+        )
+    }
+}
+
+impl Ident {
+    /// Render the identifier with the given hook and quote style in place of
+    /// the dialect's. Without quotes, keywords are only written if
+    /// `allow_keywords`, as for function names such as `left`
+    fn fmt_with(
+        &self,
+        f: &mut dyn fmt::Write,
+        dialect: &Dialect,
+        hook: Option<&IdentifierHook>,
+        quote_style: Option<char>,
+        allow_keywords: bool,
+    ) -> Result<(), SqlGenError> {
+        let (value, raw) = match hook {
+            Some(hook) => ((hook.hook)(self), !hook.quote),
+            None => (self.value.clone(), false),
+        };
+        if value.is_empty() {
+            return Err(SqlGenError::InvalidIdentifier(value));
+        }
+        if raw {
+            // The hook's text is written as is
+            return Ok(f.write_str(&value)?);
+        }
+        let quote_style = match quote_style {
+            None if self.force_quote => Some(self.quote_style.unwrap_or('"')),
+            quote_style => quote_style,
+        };
+        Ok(match quote_style {
             Some(q) if q == '"' || q == '\'' || q == '`' => {
-                let escaped = value::escape_quoted_string(&self.value, q);
+                let escaped = value::escape_quoted_string(&value, q);
                 write!(f, "{}{}{}", q, escaped.sql(dialect)?, q)
             }
-            Some(q) if q == '[' => write!(f, "[{}]", self.value),
-            None => f.write_str(&self.value),
+            Some('[') => write!(f, "[{}]", value.replace(']', "]]")),
+            None => {
+                // Without quotes only plain words are safe to write
+                let mut chars = value.chars();
+                let plain = chars
+                    .next()
+                    .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+                let keyword = ALL_KEYWORDS
+                    .binary_search(&value.to_ascii_uppercase().as_str())
+                    .is_ok();
+                if !plain || (keyword && !allow_keywords) {
+                    return Err(SqlGenError::InvalidIdentifier(value));
+                }
+                f.write_str(&value)
+            }
             _ => panic!("unexpected quote style"),
         }?)
     }
 }
 
+/// The parts of a name rendered without the identifier hook, for the names
+/// that aren't passed to it, such as functions and collations
+pub(crate) struct Unhooked<'a> {
+    pub parts: &'a [Ident],
+    pub quote_style: Option<char>,
+    /// Whether keywords may be written without quotes
+    pub allow_keywords: bool,
+}
+
+impl<'a> DialectDisplay for Unhooked<'a> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        for (i, part) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_char('.')?;
+            }
+            part.fmt_with(f, dialect, None, self.quote_style, self.allow_keywords)?;
+        }
+        Ok(())
+    }
+}
+
+impl ObjectName {
+    /// The name rendered with the dialect's quote style, without the
+    /// identifier hook
+    pub(crate) fn unhooked(&self, dialect: &Dialect) -> Unhooked<'_> {
+        Unhooked {
+            parts: &self.0,
+            quote_style: dialect.quote_style,
+            allow_keywords: false,
+        }
+    }
+}
+
 /// A name of a table, view, custom type, etc., possibly multi-part, i.e. db.schema.obj
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ObjectName(pub Vec<Ident>);
 
 impl DialectDisplay for ObjectName {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(write!(
             f,
             "{}",
@@ -163,13 +293,82 @@ pub struct Array {
 }
 
 impl DialectDisplay for Array {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
-        Ok(write!(
-            f,
-            "{}[{}]",
-            if self.named { "ARRAY" } else { "" },
-            display_comma_separated(&self.elem).sql(dialect)?
-        )?)
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        let elem = display_comma_separated(&self.elem).sql(dialect)?;
+        match dialect.array_style {
+            ArrayStyle::AsWritten if self.named => write!(f, "ARRAY[{}]", elem)?,
+            ArrayStyle::AsWritten | ArrayStyle::Brackets => write!(f, "[{}]", elem)?,
+            ArrayStyle::Keyword => write!(f, "ARRAY[{}]", elem)?,
+            ArrayStyle::Function => write!(f, "array({})", elem)?,
+            ArrayStyle::Unsupported => return Err(dialect.unsupported("Array")),
+        }
+        Ok(())
+    }
+}
+
+/// A node annotated with `/* ... */` comments before and after it, e.g. to
+/// trace generated SQL back to the plan that produced it. `*/` and `/*` in the
+/// comments are broken up, so that a comment can't end early or nest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Commented<T> {
+    pub node: T,
+    pub leading_comment: Option<String>,
+    pub trailing_comment: Option<String>,
+}
+
+impl<T> Commented<T> {
+    pub fn new(node: T) -> Self {
+        Self {
+            node,
+            leading_comment: None,
+            trailing_comment: None,
+        }
+    }
+
+    pub fn with_leading_comment(mut self, comment: &str) -> Self {
+        self.leading_comment = Some(comment.to_string());
+        self
+    }
+
+    pub fn with_trailing_comment(mut self, comment: &str) -> Self {
+        self.trailing_comment = Some(comment.to_string());
+        self
+    }
+}
+
+impl<T: DialectDisplay> DialectDisplay for Commented<T> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        fn escape(comment: &str) -> String {
+            comment.replace("*/", "* /").replace("/*", "/ *")
+        }
+        if let Some(comment) = &self.leading_comment {
+            write!(f, "/* {} */ ", escape(comment))?;
+        }
+        self.node.fmt(f, dialect)?;
+        if let Some(comment) = &self.trailing_comment {
+            write!(f, " /* {} */", escape(comment))?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a 1-based array index to a 0-based one
+fn zero_based_index(index: &Expr) -> Expr {
+    if let Expr::Value(Value::Number(n, false)) = index {
+        if let Ok(n) = n.to_string().parse::<i64>() {
+            return Expr::Value(Value::Number((n - 1).to_string().parse().unwrap(), false));
+        }
+    }
+    let index = if index.precedence() < BinaryOperator::Minus.precedence() {
+        Expr::Nested(Box::new(index.clone()))
+    } else {
+        index.clone()
+    };
+    Expr::BinaryOp {
+        left: Box::new(index),
+        op: BinaryOperator::Minus,
+        right: Box::new(Expr::Value(Value::Number("1".parse().unwrap(), false))),
     }
 }
 
@@ -217,12 +416,21 @@ pub enum Expr {
         array_expr: Box<Expr>,
         negated: bool,
     },
-    /// `<expr> [ NOT ] BETWEEN <low> AND <high>`
+    /// `<expr> [ NOT ] BETWEEN [ SYMMETRIC ] <low> AND <high>`
     Between {
         expr: Box<Expr>,
         negated: bool,
         low: Box<Expr>,
         high: Box<Expr>,
+        /// Whether the bounds may be given in either order, as in PostgreSQL
+        symmetric: bool,
+    },
+    /// `<expr> LIKE ANY (<pattern>, ...)` or `<expr> ILIKE ANY (<pattern>, ...)`,
+    /// matching any of a list of patterns as in Snowflake
+    LikeAny {
+        expr: Box<Expr>,
+        case_insensitive: bool,
+        patterns: Vec<Expr>,
     },
     /// Binary operation e.g. `1 + 1` or `foo > bar`
     BinaryOp {
@@ -280,6 +488,8 @@ pub enum Expr {
     },
     /// Nested expression e.g. `(foo > bar)` or `(1)`
     Nested(Box<Expr>),
+    /// An expression annotated with comments, which the parser never produces
+    Commented(Box<Commented<Expr>>),
     /// A literal value, such as string, number, date or NULL
     Value(Value),
     /// A constant of form `<data_type> 'value'`.
@@ -326,266 +536,716 @@ pub enum Expr {
     Array(Array),
 }
 
+impl Expr {
+    /// The precedence of the expression's outermost operator, matching the
+    /// parser. Expressions that are delimited on both sides, such as function
+    /// calls, never need parentheses and have the highest precedence.
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            Expr::BinaryOp { op, .. } => op.precedence(),
+            Expr::Commented(commented) => commented.node.precedence(),
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                ..
+            } => 15,
+            Expr::UnaryOp {
+                op: UnaryOperator::PGPostfixFactorial,
+                ..
+            } => 50,
+            Expr::UnaryOp { .. } => 30,
+            Expr::IsFalse(_)
+            | Expr::IsTrue(_)
+            | Expr::IsNull(_)
+            | Expr::IsNotNull(_)
+            | Expr::IsDistinctFrom(_, _)
+            | Expr::IsNotDistinctFrom(_, _) => 17,
+            Expr::InList { .. }
+            | Expr::InSubquery { .. }
+            | Expr::InUnnest { .. }
+            | Expr::Between { .. }
+            | Expr::LikeAny { .. }
+            | Expr::AtTimeZone { .. } => 20,
+            Expr::MapAccess { .. }
+            | Expr::ArrayIndex { .. }
+            | Expr::CompositeAccess { .. }
+            | Expr::Collate { .. } => 50,
+            _ => u8::MAX,
+        }
+    }
+}
+
+impl Expr {
+    /// Whether the expression is syntactically known to be a boolean, such as
+    /// a comparison or a logical operator
+    pub(crate) fn is_boolean(&self) -> bool {
+        match self {
+            Expr::BinaryOp { op, .. } => matches!(
+                op,
+                BinaryOperator::Gt
+                    | BinaryOperator::Lt
+                    | BinaryOperator::GtEq
+                    | BinaryOperator::LtEq
+                    | BinaryOperator::Eq
+                    | BinaryOperator::NotEq
+                    | BinaryOperator::And
+                    | BinaryOperator::Or
+                    | BinaryOperator::Xor
+                    | BinaryOperator::Like
+                    | BinaryOperator::NotLike
+                    | BinaryOperator::ILike
+                    | BinaryOperator::NotILike
+                    | BinaryOperator::PGRegexMatch
+                    | BinaryOperator::PGRegexIMatch
+                    | BinaryOperator::PGRegexNotMatch
+                    | BinaryOperator::PGRegexNotIMatch
+            ),
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                ..
+            }
+            | Expr::IsFalse(_)
+            | Expr::IsTrue(_)
+            | Expr::IsNull(_)
+            | Expr::IsNotNull(_)
+            | Expr::IsDistinctFrom(_, _)
+            | Expr::IsNotDistinctFrom(_, _)
+            | Expr::InList { .. }
+            | Expr::InSubquery { .. }
+            | Expr::InUnnest { .. }
+            | Expr::Between { .. }
+            | Expr::LikeAny { .. }
+            | Expr::Exists { .. }
+            | Expr::Value(Value::Boolean(_)) => true,
+            Expr::Nested(expr) => expr.is_boolean(),
+            Expr::Commented(commented) => commented.node.is_boolean(),
+            _ => false,
+        }
+    }
+
+    /// Whether the expression is the `NULL` literal
+    pub(crate) fn is_null(&self) -> bool {
+        match self {
+            Expr::Value(Value::Null) => true,
+            Expr::Nested(expr) => expr.is_null(),
+            Expr::Commented(commented) => commented.node.is_null(),
+            _ => false,
+        }
+    }
+}
+
 impl DialectDisplay for Expr {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
-        Ok(match self {
-            Expr::Identifier(s) => write!(f, "{}", s.sql(dialect)?),
-            Expr::MapAccess { column, keys } => {
-                write!(f, "{}", column.sql(dialect)?)?;
-                for k in keys {
-                    match k {
-                        k @ Expr::Value(Value::Number(_, _)) => write!(f, "[{}]", k.sql(dialect)?)?,
-                        Expr::Value(Value::SingleQuotedString(s)) => write!(f, "[\"{}\"]", s)?,
-                        _ => write!(f, "[{}]", k.sql(dialect)?)?,
-                    }
-                }
-                Ok(())
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        // Operands render recursively, so a long chain of operators is
+        // rejected like deeply nested queries. Each kind of expression renders
+        // in a function of its own, which keeps the stack frame of a level small
+        let _guard = RenderDepthGuard::enter(dialect)?;
+        match self {
+            Expr::IsTrue(..)
+            | Expr::IsFalse(..)
+            | Expr::IsNull(..)
+            | Expr::IsNotNull(..)
+            | Expr::Exists { .. }
+            | Expr::IsDistinctFrom(..)
+            | Expr::IsNotDistinctFrom(..) => write_test(f, self, dialect),
+            Expr::InList { .. }
+            | Expr::InSubquery { .. }
+            | Expr::InUnnest { .. }
+            | Expr::Between { .. }
+            | Expr::LikeAny { .. } => write_predicate(f, self, dialect),
+            Expr::BinaryOp { .. } | Expr::AnyOp(..) | Expr::AllOp(..) | Expr::UnaryOp { .. } => {
+                write_operator(f, self, dialect)
             }
-            Expr::CompoundIdentifier(s) => write!(f, "{}", display_separated(s, ".").sql(dialect)?),
-            Expr::IsTrue(ast) => write!(f, "{} IS TRUE", ast.sql(dialect)?),
-            Expr::IsFalse(ast) => write!(f, "{} IS FALSE", ast.sql(dialect)?),
-            Expr::IsNull(ast) => write!(f, "{} IS NULL", ast.sql(dialect)?),
-            Expr::IsNotNull(ast) => write!(f, "{} IS NOT NULL", ast.sql(dialect)?),
-            Expr::InList {
-                expr,
-                list,
-                negated,
-            } => write!(
+            Expr::Cast { .. }
+            | Expr::TryCast { .. }
+            | Expr::Extract { .. }
+            | Expr::Collate { .. }
+            | Expr::TypedString { .. }
+            | Expr::AtTimeZone { .. } => write_conversion(f, self, dialect),
+            Expr::Position { .. }
+            | Expr::Function(..)
+            | Expr::ListAgg(..)
+            | Expr::Substring { .. }
+            | Expr::Trim { .. } => write_function(f, self, dialect),
+            Expr::Case { .. } | Expr::GroupingSets(..) | Expr::Cube(..) | Expr::Rollup(..) => {
+                write_grouping(f, self, dialect)
+            }
+            Expr::Identifier(..)
+            | Expr::MapAccess { .. }
+            | Expr::CompoundIdentifier(..)
+            | Expr::Nested(..)
+            | Expr::Commented(..)
+            | Expr::Value(..)
+            | Expr::Subquery(..)
+            | Expr::Tuple(..)
+            | Expr::ArrayIndex { .. }
+            | Expr::Array(..)
+            | Expr::CompositeAccess { .. } => write_term(f, self, dialect),
+        }
+    }
+}
+
+/// Render `IS` tests and `EXISTS`
+fn write_test(f: &mut dyn fmt::Write, expr: &Expr, dialect: &Dialect) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::IsTrue(ast) => write!(f, "{} IS TRUE", ast.sql(dialect)?),
+        Expr::IsFalse(ast) => write!(f, "{} IS FALSE", ast.sql(dialect)?),
+        Expr::IsNull(ast) => write!(f, "{} IS NULL", ast.sql(dialect)?),
+        Expr::IsNotNull(ast) => write!(f, "{} IS NOT NULL", ast.sql(dialect)?),
+        Expr::Exists { subquery, negated } => write!(
+            f,
+            "{}EXISTS ({})",
+            if *negated { "NOT " } else { "" },
+            subquery.sql(dialect)?
+        ),
+        Expr::IsDistinctFrom(a, b) => write!(
+            f,
+            "{} IS DISTINCT FROM {}",
+            a.sql(dialect)?,
+            b.sql(dialect)?
+        ),
+        Expr::IsNotDistinctFrom(a, b) => write!(
+            f,
+            "{} IS NOT DISTINCT FROM {}",
+            a.sql(dialect)?,
+            b.sql(dialect)?
+        ),
+        _ => unreachable!(),
+    }?)
+}
+
+/// Render `IN`, `BETWEEN` and `LIKE ANY`
+fn write_predicate(
+    f: &mut dyn fmt::Write,
+    expr: &Expr,
+    dialect: &Dialect,
+) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            if list.is_empty() {
+                return Err(SqlGenError::DialectError(format!(
+                    "{} {}IN with an empty list cannot be rendered",
+                    expr.sql(dialect)?,
+                    if *negated { "NOT " } else { "" },
+                )));
+            }
+            write!(
                 f,
                 "{} {}IN ({})",
-                expr.sql(dialect)?,
+                predicate_operand(expr, dialect)?,
                 if *negated { "NOT " } else { "" },
                 display_comma_separated(list).sql(dialect)?
-            ),
-            Expr::InSubquery {
-                expr,
-                subquery,
-                negated,
-            } => write!(
-                f,
-                "{} {}IN ({})",
-                expr.sql(dialect)?,
-                if *negated { "NOT " } else { "" },
-                subquery.sql(dialect)?
-            ),
-            Expr::InUnnest {
-                expr,
-                array_expr,
-                negated,
-            } => write!(
-                f,
-                "{} {}IN UNNEST({})",
-                expr.sql(dialect)?,
-                if *negated { "NOT " } else { "" },
-                array_expr.sql(dialect)?
-            ),
-            Expr::Between {
-                expr,
-                negated,
-                low,
-                high,
-            } => write!(
+            )
+        }
+        Expr::InSubquery {
+            expr,
+            subquery,
+            negated,
+        } => write!(
+            f,
+            "{} {}IN ({})",
+            predicate_operand(expr, dialect)?,
+            if *negated { "NOT " } else { "" },
+            subquery.sql(dialect)?
+        ),
+        Expr::InUnnest {
+            expr,
+            array_expr,
+            negated,
+        } => write!(
+            f,
+            "{} {}IN UNNEST({})",
+            predicate_operand(expr, dialect)?,
+            if *negated { "NOT " } else { "" },
+            array_expr.sql(dialect)?
+        ),
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+            symmetric,
+        } => {
+            if *symmetric && !dialect.supports_between_symmetric {
+                return Err(dialect.unsupported("BETWEEN SYMMETRIC"));
+            }
+            write!(
                 f,
-                "{} {}BETWEEN {} AND {}",
-                expr.sql(dialect)?,
+                "{} {}BETWEEN {}{} AND {}",
+                predicate_operand(expr, dialect)?,
                 if *negated { "NOT " } else { "" },
-                low.sql(dialect)?,
-                high.sql(dialect)?
-            ),
-            Expr::BinaryOp { left, op, right } => write!(
-                f,
-                "{} {} {}",
-                left.sql(dialect)?,
-                op.sql(dialect)?,
-                right.sql(dialect)?
-            ),
-            Expr::AnyOp(expr) => write!(f, "ANY({})", expr.sql(dialect)?),
-            Expr::AllOp(expr) => write!(f, "ALL({})", expr.sql(dialect)?),
-            Expr::UnaryOp { op, expr } => {
-                if op == &UnaryOperator::PGPostfixFactorial {
-                    write!(f, "{}{}", expr.sql(dialect)?, op.sql(dialect)?)
-                } else {
-                    write!(f, "{} {}", op.sql(dialect)?, expr.sql(dialect)?)
-                }
+                if *symmetric { "SYMMETRIC " } else { "" },
+                between_bound(low, dialect)?,
+                between_bound(high, dialect)?
+            )
+        }
+        Expr::LikeAny { patterns, .. } if patterns.is_empty() => {
+            return Err(SqlGenError::DialectError(
+                "LIKE ANY must have a pattern".to_string(),
+            ))
+        }
+        Expr::LikeAny {
+            expr,
+            case_insensitive,
+            patterns,
+        } if dialect.supports_like_any => write!(
+            f,
+            "{} {} ANY ({})",
+            predicate_operand(expr, dialect)?,
+            if *case_insensitive { "ILIKE" } else { "LIKE" },
+            display_comma_separated(patterns).sql(dialect)?
+        ),
+        Expr::LikeAny {
+            expr,
+            case_insensitive,
+            patterns,
+        } => {
+            let op = if *case_insensitive {
+                BinaryOperator::ILike
+            } else {
+                BinaryOperator::Like
+            };
+            let matches = patterns
+                .iter()
+                .map(|pattern| Expr::BinaryOp {
+                    left: expr.clone(),
+                    op: op.clone(),
+                    right: Box::new(pattern.clone()),
+                })
+                .reduce(|left, right| Expr::BinaryOp {
+                    left: Box::new(left),
+                    op: BinaryOperator::Or,
+                    right: Box::new(right),
+                })
+                .expect("LIKE ANY has a pattern");
+            write!(f, "({})", matches.sql(dialect)?)
+        }
+        _ => unreachable!(),
+    }?)
+}
+
+/// Render unary and binary operators, rewritten for the dialect where needed
+fn write_operator(
+    f: &mut dyn fmt::Write,
+    expr: &Expr,
+    dialect: &Dialect,
+) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::BinaryOp {
+            op: BinaryOperator::StringConcat,
+            ..
+        } if matches!(
+            dialect.string_concat,
+            StringConcatStyle::Function | StringConcatStyle::GuardedFunction
+        ) =>
+        {
+            write!(f, "{}", concat_function(expr, dialect)?)
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Multiply,
+            right,
+        } if dialect.interval_multiplication != IntervalMultiplicationStyle::Operator
+            && matches!(
+                (left.as_ref(), right.as_ref()),
+                (Expr::Value(Value::Interval { .. }), _) | (_, Expr::Value(Value::Interval { .. }))
+            ) =>
+        {
+            let (interval, factor) = match left.as_ref() {
+                Expr::Value(interval @ Value::Interval { .. }) => (interval, right),
+                _ => match right.as_ref() {
+                    Expr::Value(interval) => (interval, left),
+                    _ => unreachable!(),
+                },
+            };
+            write!(f, "{}", multiply_interval(interval, factor, dialect)?)
+        }
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::Plus | BinaryOperator::Minus),
+            right,
+        } if dialect.date_add_style != DateAddStyle::Operator
+            && matches!(
+                (op, left.as_ref(), right.as_ref()),
+                (_, _, Expr::Value(Value::Interval { .. }))
+                    | (BinaryOperator::Plus, Expr::Value(Value::Interval { .. }), _)
+            ) =>
+        {
+            let (date, interval) = match right.as_ref() {
+                Expr::Value(interval @ Value::Interval { .. }) => (left, interval),
+                _ => match left.as_ref() {
+                    Expr::Value(interval) => (right, interval),
+                    _ => unreachable!(),
+                },
+            };
+            let subtract = *op == BinaryOperator::Minus;
+            write!(f, "{}", add_interval(date, interval, subtract, dialect)?)
+        }
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::ILike | BinaryOperator::NotILike),
+            right,
+        } if !dialect.supports_ilike => write!(
+            f,
+            "lower({}) {} lower({})",
+            left.sql(dialect)?,
+            if *op == BinaryOperator::ILike {
+                "LIKE"
+            } else {
+                "NOT LIKE"
+            },
+            right.sql(dialect)?
+        ),
+        Expr::BinaryOp {
+            left,
+            op:
+                op @ (BinaryOperator::PGRegexMatch
+                | BinaryOperator::PGRegexIMatch
+                | BinaryOperator::PGRegexNotMatch
+                | BinaryOperator::PGRegexNotIMatch),
+            right,
+        } if dialect.regex_match_style != RegexMatchStyle::Operator => {
+            write!(f, "{}", regex_match(left, op, right, dialect)?)
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Modulo,
+            right,
+        } if dialect.modulo_style == ModuloStyle::Function => {
+            write!(f, "MOD({}, {})", left.sql(dialect)?, right.sql(dialect)?)
+        }
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::Eq | BinaryOperator::NotEq),
+            right,
+        } if dialect.rewrite_null_comparisons && left.is_null() != right.is_null() => {
+            let operand = if right.is_null() { left } else { right };
+            let test = if *op == BinaryOperator::Eq {
+                Expr::IsNull(operand.clone())
+            } else {
+                Expr::IsNotNull(operand.clone())
+            };
+            write!(f, "{}", test.sql(dialect)?)
+        }
+        Expr::BinaryOp {
+            left,
+            op: op @ (BinaryOperator::And | BinaryOperator::Or | BinaryOperator::Xor),
+            right,
+        } => write!(
+            f,
+            "{} {} {}",
+            predicate_sql(left, dialect)?,
+            op.sql(dialect)?,
+            predicate_sql(right, dialect)?
+        ),
+        Expr::BinaryOp { left, op, right } => write!(
+            f,
+            "{} {} {}",
+            left.sql(dialect)?,
+            op.sql(dialect)?,
+            right.sql(dialect)?
+        ),
+        Expr::AnyOp(expr) => write!(f, "ANY({})", expr.sql(dialect)?),
+        Expr::AllOp(expr) => write!(f, "ALL({})", expr.sql(dialect)?),
+        Expr::UnaryOp { op, expr } => {
+            if op == &UnaryOperator::PGPostfixFactorial {
+                write!(f, "{}{}", expr.sql(dialect)?, op.sql(dialect)?)
+            } else if op == &UnaryOperator::Not {
+                write!(f, "NOT {}", predicate_sql(expr, dialect)?)
+            } else {
+                write!(f, "{} {}", op.sql(dialect)?, expr.sql(dialect)?)
+            }
+        }
+        _ => unreachable!(),
+    }?)
+}
+
+/// Render casts, `EXTRACT` and the other conversions of a value
+fn write_conversion(
+    f: &mut dyn fmt::Write,
+    expr: &Expr,
+    dialect: &Dialect,
+) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::Cast { expr, data_type } => return write_cast(f, expr.as_ref(), data_type, dialect),
+        Expr::TryCast { expr, data_type } => {
+            if data_type.cast_function(dialect).is_some() {
+                return Err(dialect.unsupported(format!("TRY_CAST to {}", data_type.sql(dialect)?)));
             }
-            Expr::Cast { expr, data_type } => write!(
+            let name = match dialect.try_cast_style {
+                TryCastStyle::TryCast => "TRY_CAST",
+                TryCastStyle::SafeCast => "SAFE_CAST",
+                TryCastStyle::Cast => "CAST",
+                TryCastStyle::Unsupported => return Err(dialect.unsupported("TRY_CAST")),
+            };
+            write!(
                 f,
-                "CAST({} AS {})",
+                "{}({} AS {})",
+                name,
                 expr.sql(dialect)?,
                 data_type.sql(dialect)?
-            ),
-            Expr::TryCast { expr, data_type } => write!(
+            )
+        }
+        Expr::Extract {
+            field: DateTimeField::Epoch,
+            expr,
+        } => match dialect.epoch_style {
+            EpochStyle::Extract if dialect.date_part_style == DatePartStyle::DatePart => {
+                write!(f, "date_part('epoch', {})", expr.sql(dialect)?)
+            }
+            EpochStyle::Extract => write!(f, "EXTRACT(EPOCH FROM {})", expr.sql(dialect)?),
+            EpochStyle::UnixTimestamp => write!(f, "UNIX_TIMESTAMP({})", expr.sql(dialect)?),
+            EpochStyle::Strftime => write!(f, "strftime('%s', {})", expr.sql(dialect)?),
+            EpochStyle::UnixSeconds => write!(f, "UNIX_SECONDS({})", expr.sql(dialect)?),
+            EpochStyle::ToUnixtime => write!(f, "to_unixtime({})", expr.sql(dialect)?),
+            EpochStyle::DateDiffBig => write!(
                 f,
-                "TRY_CAST({} AS {})",
-                expr.sql(dialect)?,
-                data_type.sql(dialect)?
+                "DATEDIFF_BIG(SECOND, '1970-01-01', {})",
+                expr.sql(dialect)?
             ),
-            Expr::Extract { field, expr } => write!(
+            EpochStyle::DateSubtraction => write!(
                 f,
-                "EXTRACT({} FROM {})",
-                field.sql(dialect)?,
+                "((CAST({} AS DATE) - DATE '1970-01-01') * 86400)",
                 expr.sql(dialect)?
             ),
-            Expr::Position { expr, r#in } => write!(
+        },
+        Expr::Extract { field, expr } if dialect.date_part_style == DatePartStyle::DatePart => {
+            write!(
                 f,
-                "POSITION({} IN {})",
-                expr.sql(dialect)?,
-                r#in.sql(dialect)?
-            ),
-            Expr::Collate { expr, collation } => write!(
+                "date_part('{}', {})",
+                field.sql(dialect)?.to_ascii_lowercase(),
+                expr.sql(dialect)?
+            )
+        }
+        Expr::Extract { field, expr } => write!(
+            f,
+            "EXTRACT({} FROM {})",
+            field.sql(dialect)?,
+            expr.sql(dialect)?
+        ),
+        collated @ Expr::Collate { expr, collation } => {
+            // Collations are quoted like identifiers, but aren't passed to
+            // the identifier hook
+            let expr = if expr.precedence() < collated.precedence() {
+                format!("({})", expr.sql(dialect)?)
+            } else {
+                expr.sql(dialect)?
+            };
+            write!(
                 f,
                 "{} COLLATE {}",
-                expr.sql(dialect)?,
-                collation.sql(dialect)?
-            ),
-            Expr::Nested(ast) => write!(f, "({})", ast.sql(dialect)?),
-            Expr::Value(v) => write!(f, "{}", v.sql(dialect)?),
-            Expr::TypedString { data_type, value } => {
-                write!(f, "{}", data_type.sql(dialect)?)?;
+                expr,
+                collation.unhooked(dialect).sql(dialect)?
+            )
+        }
+        Expr::TypedString { data_type, value } => {
+            write!(f, "{}", data_type.sql(dialect)?)?;
+            write!(
+                f,
+                " '{}'",
+                &value::escape_single_quote_string(value).sql(dialect)?
+            )
+        }
+        Expr::AtTimeZone {
+            timestamp,
+            time_zone,
+        } => {
+            write!(
+                f,
+                "{} AT TIME ZONE '{}'",
+                timestamp.sql(dialect)?,
+                time_zone
+            )
+        }
+        _ => unreachable!(),
+    }?)
+}
+
+/// Render function calls, including those with keyword arguments
+fn write_function(
+    f: &mut dyn fmt::Write,
+    expr: &Expr,
+    dialect: &Dialect,
+) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::Position { expr, r#in } => write!(
+            f,
+            "POSITION({} IN {})",
+            expr.sql(dialect)?,
+            r#in.sql(dialect)?
+        ),
+        Expr::Function(fun) if fun.is_rounding() => match &dialect.rounding_result_type {
+            Some(data_type) => return write_cast(f, fun, data_type, dialect),
+            None => write!(f, "{}", fun.sql(dialect)?),
+        },
+        Expr::Function(fun) => write!(f, "{}", fun.sql(dialect)?),
+        Expr::ListAgg(listagg) => write!(f, "{}", listagg.sql(dialect)?),
+        Expr::Substring {
+            expr,
+            substring_from,
+            substring_for,
+        } => {
+            write!(f, "SUBSTRING({}", expr.sql(dialect)?)?;
+            if let Some(from_part) = substring_from {
+                write!(f, " FROM {}", from_part.sql(dialect)?)?;
+            }
+            if let Some(from_part) = substring_for {
+                write!(f, " FOR {}", from_part.sql(dialect)?)?;
+            }
+
+            write!(f, ")")
+        }
+        Expr::Trim { expr, trim_where } => {
+            write!(f, "TRIM(")?;
+            if let Some((ident, trim_char)) = trim_where {
                 write!(
                     f,
-                    " '{}'",
-                    &value::escape_single_quote_string(value).sql(dialect)?
-                )
+                    "{} {} FROM {}",
+                    ident.sql(dialect)?,
+                    trim_char.sql(dialect)?,
+                    expr.sql(dialect)?
+                )?;
+            } else {
+                write!(f, "{}", expr.sql(dialect)?)?;
             }
-            Expr::Function(fun) => write!(f, "{}", fun.sql(dialect)?),
-            Expr::Case {
-                operand,
-                conditions,
-                results,
-                else_result,
-            } => {
-                write!(f, "CASE")?;
-                if let Some(operand) = operand {
-                    write!(f, " {}", operand.sql(dialect)?)?;
-                }
-                for (c, r) in conditions.iter().zip(results) {
-                    write!(f, " WHEN {} THEN {}", c.sql(dialect)?, r.sql(dialect)?)?;
-                }
 
-                if let Some(else_result) = else_result {
-                    write!(f, " ELSE {}", else_result.sql(dialect)?)?;
-                }
-                write!(f, " END")
+            write!(f, ")")
+        }
+        _ => unreachable!(),
+    }?)
+}
+
+/// Render `CASE` and the grouping sets of `GROUP BY`
+fn write_grouping(
+    f: &mut dyn fmt::Write,
+    expr: &Expr,
+    dialect: &Dialect,
+) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::Case {
+            operand,
+            conditions,
+            results,
+            else_result,
+        } => {
+            if conditions.is_empty() || conditions.len() != results.len() {
+                return Err(SqlGenError::DialectError(format!(
+                    "CASE needs a THEN result for each of at least one WHEN condition, found {} conditions and {} results",
+                    conditions.len(),
+                    results.len()
+                )));
             }
-            Expr::Exists { subquery, negated } => write!(
-                f,
-                "{}EXISTS ({})",
-                if *negated { "NOT " } else { "" },
-                subquery.sql(dialect)?
-            ),
-            Expr::Subquery(s) => write!(f, "({})", s.sql(dialect)?),
-            Expr::ListAgg(listagg) => write!(f, "{}", listagg.sql(dialect)?),
-            Expr::GroupingSets(sets) => {
-                write!(f, "GROUPING SETS (")?;
-                let mut sep = "";
-                for set in sets {
-                    write!(f, "{}", sep)?;
-                    sep = ", ";
-                    write!(f, "({})", display_comma_separated(set).sql(dialect)?)?;
-                }
-                write!(f, ")")
+            write!(f, "CASE")?;
+            if let Some(operand) = operand {
+                write!(f, " {}", operand.sql(dialect)?)?;
             }
-            Expr::Cube(sets) => {
-                write!(f, "CUBE (")?;
-                let mut sep = "";
-                for set in sets {
-                    write!(f, "{}", sep)?;
-                    sep = ", ";
-                    if set.len() == 1 {
-                        write!(f, "{}", set[0].sql(dialect)?)?;
-                    } else {
-                        write!(f, "({})", display_comma_separated(set).sql(dialect)?)?;
-                    }
-                }
-                write!(f, ")")
-            }
-            Expr::Rollup(sets) => {
-                write!(f, "ROLLUP (")?;
-                let mut sep = "";
-                for set in sets {
-                    write!(f, "{}", sep)?;
-                    sep = ", ";
-                    if set.len() == 1 {
-                        write!(f, "{}", set[0].sql(dialect)?)?;
-                    } else {
-                        write!(f, "({})", display_comma_separated(set).sql(dialect)?)?;
-                    }
-                }
-                write!(f, ")")
+            for (c, r) in conditions.iter().zip(results) {
+                let c = match operand {
+                    Some(_) => c.sql(dialect)?,
+                    None => predicate_sql(c, dialect)?,
+                };
+                write!(f, " WHEN {} THEN {}", c, r.sql(dialect)?)?;
             }
-            Expr::Substring {
-                expr,
-                substring_from,
-                substring_for,
-            } => {
-                write!(f, "SUBSTRING({}", expr.sql(dialect)?)?;
-                if let Some(from_part) = substring_from {
-                    write!(f, " FROM {}", from_part.sql(dialect)?)?;
-                }
-                if let Some(from_part) = substring_for {
-                    write!(f, " FOR {}", from_part.sql(dialect)?)?;
-                }
 
-                write!(f, ")")
+            if let Some(else_result) = else_result {
+                write!(f, " ELSE {}", else_result.sql(dialect)?)?;
             }
-            Expr::IsDistinctFrom(a, b) => write!(
-                f,
-                "{} IS DISTINCT FROM {}",
-                a.sql(dialect)?,
-                b.sql(dialect)?
-            ),
-            Expr::IsNotDistinctFrom(a, b) => write!(
-                f,
-                "{} IS NOT DISTINCT FROM {}",
-                a.sql(dialect)?,
-                b.sql(dialect)?
-            ),
-            Expr::Trim { expr, trim_where } => {
-                write!(f, "TRIM(")?;
-                if let Some((ident, trim_char)) = trim_where {
-                    write!(
-                        f,
-                        "{} {} FROM {}",
-                        ident.sql(dialect)?,
-                        trim_char.sql(dialect)?,
-                        expr.sql(dialect)?
-                    )?;
+            write!(f, " END")
+        }
+        Expr::GroupingSets(sets) => {
+            write!(f, "GROUPING SETS (")?;
+            let mut sep = "";
+            for set in sets {
+                write!(f, "{}", sep)?;
+                sep = ", ";
+                write!(f, "({})", display_comma_separated(set).sql(dialect)?)?;
+            }
+            write!(f, ")")
+        }
+        Expr::Cube(sets) => {
+            write!(f, "CUBE (")?;
+            let mut sep = "";
+            for set in sets {
+                write!(f, "{}", sep)?;
+                sep = ", ";
+                if set.len() == 1 {
+                    write!(f, "{}", set[0].sql(dialect)?)?;
                 } else {
-                    write!(f, "{}", expr.sql(dialect)?)?;
+                    write!(f, "({})", display_comma_separated(set).sql(dialect)?)?;
                 }
-
-                write!(f, ")")
-            }
-            Expr::Tuple(exprs) => {
-                write!(f, "({})", display_comma_separated(exprs).sql(dialect)?)
             }
-            Expr::ArrayIndex { obj, indexes } => {
-                write!(f, "{}", obj.sql(dialect)?)?;
-                for i in indexes {
-                    write!(f, "[{}]", i.sql(dialect)?)?;
+            write!(f, ")")
+        }
+        Expr::Rollup(sets) => {
+            write!(f, "ROLLUP (")?;
+            let mut sep = "";
+            for set in sets {
+                write!(f, "{}", sep)?;
+                sep = ", ";
+                if set.len() == 1 {
+                    write!(f, "{}", set[0].sql(dialect)?)?;
+                } else {
+                    write!(f, "({})", display_comma_separated(set).sql(dialect)?)?;
                 }
-                Ok(())
             }
-            Expr::Array(set) => {
-                write!(f, "{}", set.sql(dialect)?)
-            }
-            Expr::CompositeAccess { expr, key } => {
-                write!(f, "{}.{}", expr.sql(dialect)?, key.sql(dialect)?)
+            write!(f, ")")
+        }
+        _ => unreachable!(),
+    }?)
+}
+
+/// Render identifiers, literals, subqueries and the other terms
+fn write_term(f: &mut dyn fmt::Write, expr: &Expr, dialect: &Dialect) -> Result<(), SqlGenError> {
+    Ok(match expr {
+        Expr::Identifier(s) => write!(f, "{}", s.sql(dialect)?),
+        Expr::MapAccess { column, keys } => {
+            write!(f, "{}", column.sql(dialect)?)?;
+            for k in keys {
+                match k {
+                    k @ Expr::Value(Value::Number(_, _)) => write!(f, "[{}]", k.sql(dialect)?)?,
+                    Expr::Value(Value::SingleQuotedString(s)) => write!(f, "[\"{}\"]", s)?,
+                    _ => write!(f, "[{}]", k.sql(dialect)?)?,
+                }
             }
-            Expr::AtTimeZone {
-                timestamp,
-                time_zone,
-            } => {
-                write!(
-                    f,
-                    "{} AT TIME ZONE '{}'",
-                    timestamp.sql(dialect)?,
-                    time_zone
-                )
+            Ok(())
+        }
+        Expr::CompoundIdentifier(s) => write!(f, "{}", display_separated(s, ".").sql(dialect)?),
+        Expr::Nested(ast) => write!(f, "({})", ast.sql(dialect)?),
+        Expr::Commented(commented) => write!(f, "{}", commented.sql(dialect)?),
+        Expr::Value(v) => write!(f, "{}", v.sql(dialect)?),
+        Expr::Subquery(s) => write!(f, "({})", s.sql(dialect)?),
+        Expr::Tuple(exprs) => {
+            write!(f, "({})", display_comma_separated(exprs).sql(dialect)?)
+        }
+        Expr::ArrayIndex { obj, indexes } => {
+            write!(f, "{}", obj.sql(dialect)?)?;
+            for i in indexes {
+                match dialect.array_index_style {
+                    ArrayIndexStyle::OneBased => write!(f, "[{}]", i.sql(dialect)?)?,
+                    ArrayIndexStyle::ZeroBased => {
+                        write!(f, "[{}]", zero_based_index(i).sql(dialect)?)?
+                    }
+                    ArrayIndexStyle::Offset => {
+                        write!(f, "[OFFSET({})]", zero_based_index(i).sql(dialect)?)?
+                    }
+                    ArrayIndexStyle::Unsupported => {
+                        return Err(dialect.unsupported("Array indexing"))
+                    }
+                }
             }
-        }?)
-    }
+            Ok(())
+        }
+        Expr::Array(set) => {
+            write!(f, "{}", set.sql(dialect)?)
+        }
+        Expr::CompositeAccess { expr, key } => {
+            write!(f, "{}.{}", expr.sql(dialect)?, key.sql(dialect)?)
+        }
+        _ => unreachable!(),
+    }?)
 }
 
 /// A window specification (i.e. `OVER (PARTITION BY .. ORDER BY .. etc.)`)
@@ -598,7 +1258,7 @@ pub struct WindowSpec {
 }
 
 impl DialectDisplay for WindowSpec {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         let mut delim = "";
         if !self.partition_by.is_empty() {
             delim = " ";
@@ -618,6 +1278,9 @@ impl DialectDisplay for WindowSpec {
             )?;
         }
         if let Some(window_frame) = &self.window_frame {
+            if window_frame.units == WindowFrameUnits::Groups && !dialect.supports_groups_frames {
+                return Err(dialect.unsupported("GROUPS window frame"));
+            }
             f.write_str(delim)?;
             if let Some(end_bound) = &window_frame.end_bound {
                 write!(
@@ -635,6 +1298,15 @@ impl DialectDisplay for WindowSpec {
                     window_frame.start_bound.sql(dialect)?
                 )?;
             }
+            if let Some(exclude) = &window_frame.exclude {
+                if !dialect.supports_window_frame_exclude {
+                    return Err(dialect.unsupported(format!(
+                        "EXCLUDE {} in window frames",
+                        exclude.sql(dialect)?
+                    )));
+                }
+                write!(f, " EXCLUDE {}", exclude.sql(dialect)?)?;
+            }
         }
         Ok(())
     }
@@ -654,7 +1326,8 @@ pub struct WindowFrame {
     /// indicates the shorthand form (e.g. `ROWS 1 PRECEDING`), which must
     /// behave the same as `end_bound = WindowFrameBound::CurrentRow`.
     pub end_bound: Option<WindowFrameBound>,
-    // TBD: EXCLUDE
+    /// The rows excluded from the frame by `EXCLUDE ...`
+    pub exclude: Option<WindowFrameExclude>,
 }
 
 impl Default for WindowFrame {
@@ -666,6 +1339,7 @@ impl Default for WindowFrame {
             units: WindowFrameUnits::Range,
             start_bound: WindowFrameBound::Preceding(None),
             end_bound: None,
+            exclude: None,
         }
     }
 }
@@ -679,7 +1353,7 @@ pub enum WindowFrameUnits {
 }
 
 impl DialectDisplay for WindowFrameUnits {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             WindowFrameUnits::Rows => "ROWS",
             WindowFrameUnits::Range => "RANGE",
@@ -701,7 +1375,7 @@ pub enum WindowFrameBound {
 }
 
 impl DialectDisplay for WindowFrameBound {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             WindowFrameBound::CurrentRow => f.write_str("CURRENT ROW"),
             WindowFrameBound::Preceding(None) => f.write_str("UNBOUNDED PRECEDING"),
@@ -712,6 +1386,31 @@ impl DialectDisplay for WindowFrameBound {
     }
 }
 
+/// Specifies [WindowFrame]'s `exclude`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowFrameExclude {
+    /// `EXCLUDE CURRENT ROW`
+    CurrentRow,
+    /// `EXCLUDE GROUP`, the current row and its peers
+    Group,
+    /// `EXCLUDE TIES`, the peers of the current row
+    Ties,
+    /// `EXCLUDE NO OTHERS`, which excludes nothing
+    NoOthers,
+}
+
+impl DialectDisplay for WindowFrameExclude {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
+        Ok(f.write_str(match self {
+            WindowFrameExclude::CurrentRow => "CURRENT ROW",
+            WindowFrameExclude::Group => "GROUP",
+            WindowFrameExclude::Ties => "TIES",
+            WindowFrameExclude::NoOthers => "NO OTHERS",
+        })?)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum AddDropSync {
@@ -721,7 +1420,7 @@ pub enum AddDropSync {
 }
 
 impl DialectDisplay for AddDropSync {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             AddDropSync::SYNC => f.write_str("SYNC PARTITIONS"),
             AddDropSync::DROP => f.write_str("DROP PARTITIONS"),
@@ -742,7 +1441,7 @@ pub enum ShowCreateObject {
 }
 
 impl DialectDisplay for ShowCreateObject {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             ShowCreateObject::Event => f.write_str("EVENT"),
             ShowCreateObject::Function => f.write_str("FUNCTION"),
@@ -762,7 +1461,7 @@ pub enum CommentObject {
 }
 
 impl DialectDisplay for CommentObject {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             CommentObject::Column => f.write_str("COLUMN"),
             CommentObject::Table => f.write_str("TABLE"),
@@ -793,7 +1492,7 @@ pub enum FetchDirection {
 }
 
 impl DialectDisplay for FetchDirection {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         match self {
             FetchDirection::Count { limit } => f.write_str(&limit.sql(dialect)?)?,
             FetchDirection::Next => f.write_str("NEXT")?,
@@ -852,7 +1551,7 @@ pub enum Action {
 }
 
 impl DialectDisplay for Action {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         match self {
             Action::Connect => f.write_str("CONNECT")?,
             Action::Create => f.write_str("CREATE")?,
@@ -899,7 +1598,7 @@ pub enum GrantObjects {
 }
 
 impl DialectDisplay for GrantObjects {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             GrantObjects::Sequences(sequences) => {
                 write!(
@@ -945,7 +1644,7 @@ pub struct Assignment {
 }
 
 impl DialectDisplay for Assignment {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(write!(
             f,
             "{} = {}",
@@ -966,7 +1665,7 @@ pub enum FunctionArgExpr {
 }
 
 impl DialectDisplay for FunctionArgExpr {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             FunctionArgExpr::Expr(expr) => write!(f, "{}", expr.sql(dialect)?),
             FunctionArgExpr::QualifiedWildcard(prefix) => write!(f, "{}.*", prefix.sql(dialect)?),
@@ -983,9 +1682,16 @@ pub enum FunctionArg {
 }
 
 impl DialectDisplay for FunctionArg {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             FunctionArg::Named { name, arg } => {
+                // Argument names are quoted like identifiers, but aren't passed
+                // to the identifier hook
+                let name = Unhooked {
+                    parts: core::slice::from_ref(name),
+                    quote_style: dialect.quote_style,
+                    allow_keywords: false,
+                };
                 write!(f, "{} => {}", name.sql(dialect)?, arg.sql(dialect)?)
             }
             FunctionArg::Unnamed(unnamed_arg) => write!(f, "{}", unnamed_arg.sql(dialect)?),
@@ -1001,7 +1707,7 @@ pub enum CloseCursor {
 }
 
 impl DialectDisplay for CloseCursor {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             CloseCursor::All => write!(f, "ALL"),
             CloseCursor::Specific { name } => write!(f, "{}", name.sql(dialect)?),
@@ -1018,15 +1724,653 @@ pub struct Function {
     pub over: Option<WindowSpec>,
     // aggregate functions may specify eg `COUNT(DISTINCT x)`
     pub distinct: bool,
+    /// `ORDER BY` inside the arguments of an aggregate function, e.g.
+    /// `array_agg(x ORDER BY y)`
+    pub order_by: Vec<OrderByExpr>,
+    /// `FILTER (WHERE <expr>)` of an aggregate function
+    pub filter: Option<Box<Expr>>,
+    /// `WITHIN GROUP (ORDER BY <within_group1>[, ...])` of an ordered-set
+    /// aggregate function such as `percentile_cont`
+    pub within_group: Vec<OrderByExpr>,
+}
+
+impl Function {
+    /// Check the arguments of calls that are known to be invalid regardless of
+    /// dialect: `count` and `sum` without an argument, `sum` of a wildcard,
+    /// `translate` with literal `from` and `to` strings of different lengths,
+    /// and aggregates with `DISTINCT` ordered by something other than their
+    /// arguments
+    fn validate_args(&self, fn_name: &str, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if self.distinct {
+            for order_by in &self.order_by {
+                let is_arg = self.args.iter().any(|arg| {
+                    matches!(arg, FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) if *expr == order_by.expr)
+                });
+                if !is_arg {
+                    return Err(SqlGenError::DialectError(format!(
+                        "ORDER BY expression {} of function {} with DISTINCT must be one of its arguments",
+                        order_by.expr.sql(dialect)?,
+                        fn_name
+                    )));
+                }
+            }
+        }
+
+        match fn_name {
+            "count" | "sum" => {
+                if self.args.is_empty() {
+                    return Err(SqlGenError::DialectError(format!(
+                        "Function {} requires at least one argument",
+                        fn_name
+                    )));
+                }
+                let wildcard = self.args.iter().any(|arg| {
+                    matches!(
+                        arg,
+                        FunctionArg::Unnamed(FunctionArgExpr::Wildcard)
+                            | FunctionArg::Unnamed(FunctionArgExpr::QualifiedWildcard(_))
+                    )
+                });
+                if wildcard && fn_name != "count" {
+                    return Err(SqlGenError::DialectError(format!(
+                        "Function {} does not accept a wildcard argument",
+                        fn_name
+                    )));
+                }
+            }
+            "translate" => {
+                if let [_, from, to] = self.args.as_slice() {
+                    if let (Some(from), Some(to)) = (string_arg(from), string_arg(to)) {
+                        if from.chars().count() != to.chars().count() {
+                            return Err(SqlGenError::DialectError(format!(
+                                "Function translate expects from and to strings of equal length, found '{}' and '{}'",
+                                from, to
+                            )));
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// The quantity and unit of a single unit interval literal such as
+/// `INTERVAL '2' HOUR` or `INTERVAL '2 hours'`
+pub(crate) fn interval_quantity(interval: &Value) -> Option<(String, DateTimeField)> {
+    let (value, leading_field) = match interval {
+        Value::Interval {
+            value,
+            leading_field,
+            leading_precision: None,
+            last_field: None,
+            fractional_seconds_precision: None,
+        } => (value.as_ref(), leading_field),
+        _ => return None,
+    };
+    let (quantity, unit) = match (value, leading_field) {
+        (Expr::Value(Value::Number(n, false)), Some(unit)) => (n.to_string(), unit.clone()),
+        (Expr::Value(Value::SingleQuotedString(s)), Some(unit)) => {
+            (s.trim().to_string(), unit.clone())
+        }
+        (Expr::Value(Value::SingleQuotedString(s)), None) => {
+            let mut parts = s.split_whitespace();
+            let (quantity, unit) = (parts.next()?, parts.next()?);
+            if parts.next().is_some() {
+                return None;
+            }
+            let unit = match unit.to_ascii_lowercase().trim_end_matches('s') {
+                "year" => DateTimeField::Year,
+                "month" => DateTimeField::Month,
+                "week" => DateTimeField::Week,
+                "day" => DateTimeField::Day,
+                "hour" => DateTimeField::Hour,
+                "minute" => DateTimeField::Minute,
+                "second" => DateTimeField::Second,
+                _ => return None,
+            };
+            (quantity.to_string(), unit)
+        }
+        _ => return None,
+    };
+    if !is_decimal_literal(&quantity) {
+        return None;
+    }
+    Some((quantity, unit))
+}
+
+/// Whether `s` is a finite decimal number such as `2`, `-1.5` or `.25`,
+/// without an exponent
+fn is_decimal_literal(s: &str) -> bool {
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    (!whole.is_empty() || !fraction.is_empty())
+        && whole.bytes().all(|b| b.is_ascii_digit())
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Render `interval * factor` for dialects that can't multiply intervals with
+/// the `*` operator
+fn multiply_interval(
+    interval: &Value,
+    factor: &Expr,
+    dialect: &Dialect,
+) -> Result<String, SqlGenError> {
+    if dialect.interval_multiplication == IntervalMultiplicationStyle::Quantity {
+        if let Some((quantity, unit)) = interval_quantity(interval) {
+            let factor = if factor.precedence() == u8::MAX {
+                factor.sql(dialect)?
+            } else {
+                format!("({})", factor.sql(dialect)?)
+            };
+            let quantity = if quantity == "1" {
+                factor
+            } else {
+                format!("({} * {})", quantity, factor)
+            };
+            return Ok(format!("INTERVAL {} {}", quantity, unit.sql(dialect)?));
+        }
+    }
+    Err(dialect.unsupported(format!("Multiplying {}", interval.sql(dialect)?)))
+}
+
+/// Render `date + interval` or `date - interval` for dialects that add
+/// intervals with a function
+fn add_interval(
+    date: &Expr,
+    interval: &Value,
+    subtract: bool,
+    dialect: &Dialect,
+) -> Result<String, SqlGenError> {
+    // DATEADD and DATE_ADD truncate fractional quantities
+    let quantity =
+        interval_quantity(interval).and_then(|(quantity, unit)| whole_quantity(&quantity, unit));
+    let (quantity, unit) = match quantity {
+        Some(quantity) => quantity,
+        None => {
+            return Err(dialect.unsupported(format!("Adding {} to a date", interval.sql(dialect)?)))
+        }
+    };
+    Ok(match dialect.date_add_style {
+        DateAddStyle::DateAdd => {
+            let quantity = match (subtract, quantity.strip_prefix('-')) {
+                (true, Some(positive)) => positive.to_string(),
+                (true, None) => format!("-{}", quantity),
+                (false, _) => quantity,
+            };
+            format!(
+                "DATEADD({}, {}, {})",
+                unit.sql(dialect)?,
+                quantity,
+                date.sql(dialect)?
+            )
+        }
+        DateAddStyle::DateAddInterval | DateAddStyle::Operator => format!(
+            "{}({}, INTERVAL {} {})",
+            if subtract { "DATE_SUB" } else { "DATE_ADD" },
+            date.sql(dialect)?,
+            quantity,
+            unit.sql(dialect)?
+        ),
+    })
+}
+
+/// An interval quantity as a whole number of `unit`, or of the largest smaller
+/// unit of a fixed length that makes it whole, as in `1.5 hours` to
+/// `90 minutes`
+fn whole_quantity(quantity: &str, unit: DateTimeField) -> Option<(String, DateTimeField)> {
+    let (sign, digits) = match quantity.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", quantity.strip_prefix('+').unwrap_or(quantity)),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let mut scaled: u128 = format!("{}{}", whole, fraction).parse().ok()?;
+    let divisor = 10u128.checked_pow(fraction.len() as u32)?;
+    let mut unit = unit;
+    while !scaled.is_multiple_of(divisor) {
+        let (smaller, factor) = match unit {
+            DateTimeField::Week => (DateTimeField::Day, 7),
+            DateTimeField::Day => (DateTimeField::Hour, 24),
+            DateTimeField::Hour => (DateTimeField::Minute, 60),
+            DateTimeField::Minute => (DateTimeField::Second, 60),
+            _ => return None,
+        };
+        scaled = scaled.checked_mul(factor)?;
+        unit = smaller;
+    }
+    let quantity = scaled / divisor;
+    let sign = if quantity == 0 { "" } else { sign };
+    Some((format!("{}{}", sign, quantity), unit))
+}
+
+/// Rewrite `date_part('field', source)` to `EXTRACT(field FROM source)`
+fn date_part_extract(args: &[FunctionArg]) -> Result<Expr, SqlGenError> {
+    match args {
+        [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(
+            field,
+        )))), FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))] => {
+            let field = DateTimeField::from_name(field).ok_or_else(|| {
+                SqlGenError::DialectError(format!(
+                    "Unknown date_part field {} cannot be rendered as EXTRACT by dialect",
+                    field
+                ))
+            })?;
+            Ok(Expr::Extract {
+                field,
+                expr: Box::new(expr.clone()),
+            })
+        }
+        _ => Err(SqlGenError::DialectError(
+            "date_part without a literal field cannot be rendered as EXTRACT by dialect"
+                .to_string(),
+        )),
+    }
+}
+
+/// Render a cast of `expr` to `data_type`, with the dialect's cast function
+/// or `CONVERT` if it has one
+fn write_cast<E: DialectDisplay>(
+    f: &mut dyn fmt::Write,
+    expr: &E,
+    data_type: &DataType,
+    dialect: &Dialect,
+) -> Result<(), SqlGenError> {
+    Ok(match data_type.cast_function(dialect) {
+        Some(function) => write!(f, "{}({})", function, expr.sql(dialect)?),
+        None if dialect.cast_style == CastStyle::Convert => {
+            write!(
+                f,
+                "CONVERT({}, {}",
+                data_type.sql(dialect)?,
+                expr.sql(dialect)?
+            )?;
+            if let Some(style) = data_type.convert_style(dialect) {
+                write!(f, ", {}", style)?;
+            }
+            write!(f, ")")
+        }
+        None => write!(
+            f,
+            "CAST({} AS {})",
+            expr.sql(dialect)?,
+            data_type.cast_sql(dialect)?
+        ),
+    }?)
+}
+
+/// Render an expression in a position that needs a predicate, such as `WHERE`
+/// or an operand of `AND`. Boolean literals spelled as integers are written as
+/// the comparisons `1 = 1` and `1 = 0` there, since a bare integer isn't a
+/// predicate.
+pub(crate) fn predicate_sql(expr: &Expr, dialect: &Dialect) -> Result<String, SqlGenError> {
+    match expr {
+        Expr::Value(Value::Boolean(v))
+            if dialect.boolean_literal_style == BooleanLiteralStyle::Integer =>
+        {
+            Ok(if *v { "1 = 1" } else { "1 = 0" }.to_string())
+        }
+        Expr::Nested(inner) if matches!(inner.as_ref(), Expr::Value(Value::Boolean(_))) => {
+            Ok(format!("({})", predicate_sql(inner, dialect)?))
+        }
+        _ => expr.sql(dialect),
+    }
+}
+
+/// Render the left operand of `IN` or `BETWEEN`, parenthesized if it binds
+/// more loosely than them, as in `(a OR b) IN (...)`
+fn predicate_operand(expr: &Expr, dialect: &Dialect) -> Result<String, SqlGenError> {
+    if expr.precedence() < 20 {
+        Ok(format!("({})", expr.sql(dialect)?))
+    } else {
+        expr.sql(dialect)
+    }
+}
+
+/// Render a bound of `BETWEEN`, parenthesized unless it binds more tightly
+/// than `BETWEEN`, as in `x BETWEEN (a = b) AND c`
+fn between_bound(expr: &Expr, dialect: &Dialect) -> Result<String, SqlGenError> {
+    if expr.precedence() <= 20 {
+        Ok(format!("({})", expr.sql(dialect)?))
+    } else {
+        expr.sql(dialect)
+    }
+}
+
+/// Render a regular expression match for dialects that don't spell it `~`
+fn regex_match(
+    expr: &Expr,
+    op: &BinaryOperator,
+    pattern: &Expr,
+    dialect: &Dialect,
+) -> Result<String, SqlGenError> {
+    let negated = matches!(
+        op,
+        BinaryOperator::PGRegexNotMatch | BinaryOperator::PGRegexNotIMatch
+    );
+    let case_insensitive = matches!(
+        op,
+        BinaryOperator::PGRegexIMatch | BinaryOperator::PGRegexNotIMatch
+    );
+    if dialect.regex_match_style == RegexMatchStyle::Unsupported {
+        return Err(
+            dialect.unsupported(format!("Regular expression operator {}", op.sql(dialect)?))
+        );
+    }
+    let not = if negated { "NOT " } else { "" };
+    if dialect.regex_match_style == RegexMatchStyle::RegexpLikeMatchParameter {
+        let flags = if case_insensitive { ", 'i'" } else { "" };
+        return Ok(format!(
+            "{}REGEXP_LIKE({}, {}{})",
+            not,
+            expr.sql(dialect)?,
+            pattern.sql(dialect)?,
+            flags
+        ));
+    }
+    let pattern = match pattern {
+        Expr::Value(Value::SingleQuotedString(p)) if case_insensitive => {
+            Expr::Value(Value::SingleQuotedString(format!("(?i){}", p))).sql(dialect)?
+        }
+        _ if case_insensitive => {
+            return Err(SqlGenError::DialectError(format!(
+                "Regular expression operator {} requires a literal pattern for dialect",
+                op.sql(dialect)?
+            )))
+        }
+        _ => pattern.sql(dialect)?,
+    };
+    let expr = expr.sql(dialect)?;
+    Ok(match dialect.regex_match_style {
+        RegexMatchStyle::Regexp => format!("{} {}REGEXP {}", expr, not, pattern),
+        RegexMatchStyle::Rlike => format!("{} {}RLIKE {}", expr, not, pattern),
+        RegexMatchStyle::RegexpContains => format!("{}REGEXP_CONTAINS({}, {})", not, expr, pattern),
+        RegexMatchStyle::RegexpLike => format!("{}REGEXP_LIKE({}, {})", not, expr, pattern),
+        RegexMatchStyle::Operator
+        | RegexMatchStyle::RegexpLikeMatchParameter
+        | RegexMatchStyle::Unsupported => unreachable!(),
+    })
+}
+
+/// Render a chain of `||` with the `concat` function, guarded to keep the NULL
+/// result of `||` where the dialect needs it
+fn concat_function(expr: &Expr, dialect: &Dialect) -> Result<String, SqlGenError> {
+    let mut operands = Vec::new();
+    concat_operands(expr, &mut operands);
+    let concat = format!(
+        "concat({})",
+        operands
+            .iter()
+            .map(|operand| operand.sql(dialect))
+            .collect::<Result<Vec<_>, SqlGenError>>()?
+            .join(", ")
+    );
+    // Any NULL operand makes a || b NULL, literals other than NULL
+    // never do
+    let nullable = operands
+        .into_iter()
+        .filter(|operand| !matches!(operand, Expr::Value(value) if *value != Value::Null))
+        .map(|operand| Expr::IsNull(Box::new(operand.clone())).sql(dialect))
+        .collect::<Result<Vec<_>, SqlGenError>>()?;
+    if dialect.string_concat == StringConcatStyle::GuardedFunction && !nullable.is_empty() {
+        Ok(format!(
+            "CASE WHEN {} THEN NULL ELSE {} END",
+            nullable.join(" OR "),
+            concat
+        ))
+    } else {
+        Ok(concat)
+    }
+}
+
+/// Collect the operands of a chain of `||`, which is associative, including
+/// parenthesized chains
+fn concat_operands<'a>(expr: &'a Expr, operands: &mut Vec<&'a Expr>) {
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::StringConcat,
+            right,
+        } => {
+            concat_operands(left, operands);
+            concat_operands(right, operands);
+        }
+        Expr::Nested(inner)
+            if matches!(
+                inner.as_ref(),
+                Expr::BinaryOp {
+                    op: BinaryOperator::StringConcat,
+                    ..
+                }
+            ) =>
+        {
+            concat_operands(inner, operands)
+        }
+        _ => operands.push(expr),
+    }
+}
+
+/// Render the arguments of `concat(...)` as a parenthesized chain of `||`.
+/// `concat` skips NULL arguments while `||` propagates them, so each argument
+/// other than a literal is wrapped in `coalesce(x, '')`.
+fn concat_operator(args: &[FunctionArg], dialect: &Dialect) -> Result<String, SqlGenError> {
+    let operands = args
+        .iter()
+        .map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr @ Expr::Value(value)))
+                if *value != Value::Null =>
+            {
+                expr.sql(dialect)
+            }
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => {
+                Ok(format!("coalesce({}, '')", expr.sql(dialect)?))
+            }
+            _ => Err(SqlGenError::DialectError(format!(
+                "Argument {} of concat cannot be rendered with || by dialect",
+                arg.sql(dialect)?
+            ))),
+        })
+        .collect::<Result<Vec<_>, SqlGenError>>()?;
+    Ok(format!("({})", operands.join(" || ")))
+}
+
+/// Render the arguments of `named_struct('a', 1, ...)` as a struct literal in
+/// the dialect's spelling. Field names must be string literals.
+fn struct_literal(args: &[FunctionArg], dialect: &Dialect) -> Result<String, SqlGenError> {
+    if dialect.struct_style == StructStyle::Unsupported {
+        return Err(dialect.unsupported("Struct literal"));
+    }
+    if !args.len().is_multiple_of(2) {
+        return Err(SqlGenError::DialectError(
+            "named_struct expects pairs of field names and values".to_string(),
+        ));
+    }
+    let fields = args
+        .chunks(2)
+        .map(|pair| match pair {
+            [FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                Value::SingleQuotedString(name),
+            ))), FunctionArg::Unnamed(FunctionArgExpr::Expr(value))] => {
+                let value = value.sql(dialect)?;
+                Ok(match dialect.struct_style {
+                    StructStyle::Braces => format!(
+                        "'{}': {}",
+                        value::escape_single_quote_string(name).sql(dialect)?,
+                        value
+                    ),
+                    _ => format!("{} AS {}", value, Ident::new(name).sql(dialect)?),
+                })
+            }
+            _ => Err(SqlGenError::DialectError(format!(
+                "Field {} of named_struct must be a string literal name and a value",
+                display_comma_separated(pair).sql(dialect)?
+            ))),
+        })
+        .collect::<Result<Vec<_>, SqlGenError>>()?
+        .join(", ");
+    Ok(match dialect.struct_style {
+        StructStyle::Braces => format!("{{{}}}", fields),
+        _ => format!("STRUCT({})", fields),
+    })
+}
+
+fn is_boolean_arg(arg: &FunctionArg) -> bool {
+    matches!(arg, FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) if expr.is_boolean())
+}
+
+/// Rewrite a boolean argument `x` to `CASE WHEN x THEN 1 ELSE 0 END`
+fn coerce_boolean_arg(arg: &FunctionArg) -> FunctionArg {
+    match arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) if expr.is_boolean() => {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Case {
+                operand: None,
+                conditions: vec![expr.clone()],
+                results: vec![Expr::Value(Value::Number("1".parse().unwrap(), false))],
+                else_result: Some(Box::new(Expr::Value(Value::Number(
+                    "0".parse().unwrap(),
+                    false,
+                )))),
+            }))
+        }
+        _ => arg.clone(),
+    }
+}
+
+/// Rewrite the argument of `f(x) FILTER (WHERE cond)` to `CASE WHEN cond THEN x END`,
+/// which aggregates the same rows since aggregates skip NULLs. `count(*)` counts `1`.
+fn filter_args(
+    fn_name: &str,
+    args: &[FunctionArg],
+    filter: &Expr,
+) -> Result<Vec<FunctionArg>, SqlGenError> {
+    let arg = match args {
+        [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)] => {
+            Expr::Value(Value::Number("1".parse().unwrap(), false))
+        }
+        [FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))] => expr.clone(),
+        _ => {
+            return Err(SqlGenError::DialectError(format!(
+                "FILTER on function {} cannot be rewritten for dialect",
+                fn_name
+            )))
+        }
+    };
+    Ok(vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+        Expr::Case {
+            operand: None,
+            conditions: vec![filter.clone()],
+            results: vec![arg],
+            else_result: None,
+        },
+    ))])
+}
+
+/// The single argument counting the distinct combinations of `args`
+fn count_distinct_arg(args: &[FunctionArg], dialect: &Dialect) -> Result<Expr, SqlGenError> {
+    let exprs = args
+        .iter()
+        .map(|arg| match arg {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr.clone()),
+            _ => Err(SqlGenError::DialectError(
+                "count(DISTINCT ...) of several arguments must have expression arguments"
+                    .to_string(),
+            )),
+        })
+        .collect::<Result<Vec<_>, SqlGenError>>()?;
+    // Every style but the native one counts NULL, so that combinations with
+    // a NULL are replaced by NULL, as `count(DISTINCT a, b)` skips them
+    let any_null = exprs
+        .iter()
+        .map(|expr| Expr::IsNull(Box::new(expr.clone())))
+        .reduce(|left, right| Expr::BinaryOp {
+            left: Box::new(left),
+            op: BinaryOperator::Or,
+            right: Box::new(right),
+        })
+        .expect("count has several arguments");
+    let combination = match dialect.count_distinct_style {
+        CountDistinctStyle::Concat { max_length } => {
+            let data_type = match max_length {
+                Some(length) => DataType::Varchar(Some(length)),
+                None => DataType::Text,
+            };
+            let casts = exprs.into_iter().map(|expr| Expr::Cast {
+                expr: Box::new(expr),
+                data_type: data_type.clone(),
+            });
+            let separator = Expr::Value(Value::SingleQuotedString("|".to_string()));
+            if dialect.string_concat == StringConcatStyle::GuardedFunction {
+                // No operand is NULL here, so concat needs no guard
+                let mut args = vec![];
+                for cast in casts {
+                    if !args.is_empty() {
+                        args.push(FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                            separator.clone(),
+                        )));
+                    }
+                    args.push(FunctionArg::Unnamed(FunctionArgExpr::Expr(cast)));
+                }
+                Expr::Function(Function {
+                    name: ObjectName(vec![Ident::new("concat")]),
+                    args,
+                    over: None,
+                    distinct: false,
+                    order_by: vec![],
+                    filter: None,
+                    within_group: vec![],
+                })
+            } else {
+                casts
+                    .reduce(|left, right| Expr::BinaryOp {
+                        left: Box::new(Expr::BinaryOp {
+                            left: Box::new(left),
+                            op: BinaryOperator::StringConcat,
+                            right: Box::new(separator.clone()),
+                        }),
+                        op: BinaryOperator::StringConcat,
+                        right: Box::new(right),
+                    })
+                    .expect("count has several arguments")
+            }
+        }
+        _ => Expr::Tuple(exprs),
+    };
+    Ok(Expr::Case {
+        operand: None,
+        conditions: vec![any_null],
+        results: vec![Expr::Value(Value::Null)],
+        else_result: Some(Box::new(combination)),
+    })
+}
+
+impl Function {
+    /// Whether the function is `floor`, `ceil` or `trunc`, whose result type
+    /// differs between dialects
+    fn is_rounding(&self) -> bool {
+        match self.name.0.as_slice() {
+            [name] => ["floor", "ceil", "ceiling", "trunc"]
+                .iter()
+                .any(|rounding| name.value.eq_ignore_ascii_case(rounding)),
+            _ => false,
+        }
+    }
 }
 
 impl DialectDisplay for Function {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
-        let mut name = self.name.clone();
-        let mut function_dialect = dialect.clone();
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        // Arguments render recursively, with a larger stack frame than the
+        // expression around the call
+        let _guard = RenderDepthGuard::enter(dialect)?;
+        let name = &self.name;
+        // Function names aren't passed to the identifier hook
+        let mut function_name = Unhooked {
+            parts: &name.0,
+            quote_style: dialect.quote_style,
+            allow_keywords: true,
+        };
         if !dialect.quote_functions {
             // Remove quotes around function name
-            function_dialect.quote_style = None;
+            function_name.quote_style = None;
         }
 
         if name.0.len() != 1 {
@@ -1037,32 +2381,113 @@ impl DialectDisplay for Function {
             )));
         }
 
-        let fn_name: String = name.0.get(0).cloned().unwrap().value.to_ascii_lowercase();
+        let fn_name: String = name.0.first().cloned().unwrap().value.to_ascii_lowercase();
+        if dialect.strict {
+            self.validate_args(&fn_name, dialect)?;
+        }
 
-        // Check for transform
-        if let Some(tx) = dialect.function_transforms.get(&fn_name) {
-            let args = self
-                .args
-                .iter()
-                .map(|arg| arg.sql(&dialect))
-                .collect::<Result<Vec<_>, SqlGenError>>()?;
-            write!(f, "{}", tx.transform(&fn_name, args.as_slice()))?;
+        // Dialects without boolean coercion get boolean sums counted with CASE
+        let coerced;
+        let call = if !dialect.supports_boolean_coercion
+            && matches!(fn_name.as_str(), "sum" | "avg")
+            && self.args.iter().any(is_boolean_arg)
+        {
+            coerced = Function {
+                args: self.args.iter().map(coerce_boolean_arg).collect(),
+                ..self.clone()
+            };
+            &coerced
+        } else {
+            self
+        };
+
+        // Dialects without count(DISTINCT a, b) get the arguments combined
+        let combined;
+        let call = if fn_name == "count"
+            && call.distinct
+            && call.args.len() > 1
+            && dialect.count_distinct_style != CountDistinctStyle::Native
+        {
+            combined = Function {
+                args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                    count_distinct_arg(&call.args, dialect)?,
+                ))],
+                ..call.clone()
+            };
+            &combined
+        } else {
+            call
+        };
+
+        // Dialects without FILTER support get the filter applied to the argument
+        let filtered;
+        let call = match &call.filter {
+            Some(filter) if !dialect.supports_aggregate_filter => {
+                filtered = Function {
+                    args: filter_args(&fn_name, &call.args, filter)?,
+                    ..call.clone()
+                };
+                &filtered
+            }
+            _ => call,
+        };
+
+        // Check for concat(...) spelled as ||, date_part spelled as EXTRACT,
+        // named_struct spelled as a struct literal, and for transforms
+        if fn_name == "concat" && dialect.string_concat == StringConcatStyle::Operator {
+            write!(f, "{}", concat_operator(&call.args, dialect)?)?;
+        } else if fn_name == "named_struct" && dialect.struct_style != StructStyle::Function {
+            write!(f, "{}", struct_literal(&call.args, dialect)?)?;
+        } else if fn_name == "date_part" && dialect.date_part_style == DatePartStyle::Extract {
+            write!(f, "{}", date_part_extract(&call.args)?.sql(dialect)?)?;
+        } else if let Some(tx) = dialect.function_transform(&fn_name) {
+            write!(f, "{}", tx.transform_function(&fn_name, call, dialect)?)?;
         } else {
             // Validate function
-            if !dialect.functions.is_empty() && !dialect.functions.contains(&fn_name) {
-                // Function not allowed for dialect
-                return Err(SqlGenError::DialectError(format!(
-                    "Function {} is not supported by dialect",
-                    fn_name
-                )));
+            if !dialect.allows_function(&fn_name) {
+                match dialect.unknown_function_policy {
+                    UnknownFunctionPolicy::PassThrough => {}
+                    UnknownFunctionPolicy::Error => {
+                        // Function not allowed for dialect
+                        return Err(dialect.unsupported(format!("Function {}", fn_name)));
+                    }
+                    UnknownFunctionPolicy::Quote => {
+                        function_name.quote_style = dialect.quote_style;
+                    }
+                }
             }
             write!(
                 f,
-                "{}({}{})",
-                name.sql(&function_dialect)?,
+                "{}({}{}",
+                function_name.sql(dialect)?,
                 if self.distinct { "DISTINCT " } else { "" },
-                display_comma_separated(&self.args).sql(dialect)?,
+                display_comma_separated(&call.args).sql(dialect)?,
             )?;
+            if !self.order_by.is_empty() {
+                if !dialect.supports_aggregate_order_by {
+                    return Err(dialect.unsupported(format!("ORDER BY in function {}", fn_name)));
+                }
+                write!(
+                    f,
+                    " ORDER BY {}",
+                    display_comma_separated(&self.order_by).sql(dialect)?
+                )?;
+            }
+            write!(f, ")")?;
+        }
+
+        if !self.within_group.is_empty() {
+            write!(
+                f,
+                " WITHIN GROUP (ORDER BY {})",
+                display_comma_separated(&self.within_group).sql(dialect)?
+            )?;
+        }
+
+        if let Some(filter) = &self.filter {
+            if dialect.supports_aggregate_filter {
+                write!(f, " FILTER (WHERE {})", predicate_sql(filter, dialect)?)?;
+            }
         }
 
         if let Some(o) = &self.over {
@@ -1086,7 +2511,7 @@ pub enum FileFormat {
 }
 
 impl DialectDisplay for FileFormat {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         use self::FileFormat::*;
         Ok(f.write_str(match self {
             TEXTFILE => "TEXTFILE",
@@ -1113,7 +2538,7 @@ pub struct ListAgg {
 }
 
 impl DialectDisplay for ListAgg {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(
             f,
             "LISTAGG({}{}",
@@ -1153,7 +2578,7 @@ pub enum ListAggOnOverflow {
 }
 
 impl DialectDisplay for ListAggOnOverflow {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(f, " ON OVERFLOW")?;
         Ok(match self {
             ListAggOnOverflow::Error => write!(f, " ERROR"),
@@ -1183,7 +2608,7 @@ pub enum ObjectType {
 }
 
 impl DialectDisplay for ObjectType {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             ObjectType::Table => "TABLE",
             ObjectType::View => "VIEW",
@@ -1202,7 +2627,7 @@ pub enum KillType {
 }
 
 impl DialectDisplay for KillType {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             // MySQL
             KillType::Connection => "CONNECTION",
@@ -1221,7 +2646,7 @@ pub struct SqlOption {
 }
 
 impl DialectDisplay for SqlOption {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(write!(
             f,
             "{} = {}",
@@ -1239,7 +2664,7 @@ pub enum TransactionMode {
 }
 
 impl DialectDisplay for TransactionMode {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use TransactionMode::*;
         Ok(match self {
             AccessMode(access_mode) => write!(f, "{}", access_mode.sql(dialect)?),
@@ -1256,7 +2681,7 @@ pub enum TransactionAccessMode {
 }
 
 impl DialectDisplay for TransactionAccessMode {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         use TransactionAccessMode::*;
         Ok(f.write_str(match self {
             ReadOnly => "READ ONLY",
@@ -1275,7 +2700,7 @@ pub enum TransactionIsolationLevel {
 }
 
 impl DialectDisplay for TransactionIsolationLevel {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         use TransactionIsolationLevel::*;
         Ok(f.write_str(match self {
             ReadUncommitted => "READ UNCOMMITTED",
@@ -1295,7 +2720,7 @@ pub enum ShowStatementFilter {
 }
 
 impl DialectDisplay for ShowStatementFilter {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use ShowStatementFilter::*;
         Ok(match self {
             Like(pattern) => write!(
@@ -1321,7 +2746,7 @@ pub enum SetVariableValue {
 }
 
 impl DialectDisplay for SetVariableValue {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use SetVariableValue::*;
         Ok(match self {
             Ident(ident) => write!(f, "{}", ident.sql(dialect)?),
@@ -1344,7 +2769,7 @@ pub enum SqliteOnConflict {
 }
 
 impl DialectDisplay for SqliteOnConflict {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         use SqliteOnConflict::*;
         Ok(match self {
             Rollback => write!(f, "ROLLBACK"),
@@ -1372,10 +2797,10 @@ pub enum CopyTarget {
 }
 
 impl DialectDisplay for CopyTarget {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use CopyTarget::*;
         Ok(match self {
-            Stdin { .. } => write!(f, "STDIN"),
+            Stdin => write!(f, "STDIN"),
             Stdout => write!(f, "STDOUT"),
             File { filename } => write!(
                 f,
@@ -1430,7 +2855,7 @@ pub enum CopyOption {
 }
 
 impl DialectDisplay for CopyOption {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use CopyOption::*;
         Ok(match self {
             Format(name) => write!(f, "FORMAT {}", name.sql(dialect)?),
@@ -1489,7 +2914,7 @@ pub enum CopyLegacyOption {
 }
 
 impl DialectDisplay for CopyLegacyOption {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use CopyLegacyOption::*;
         Ok(match self {
             Binary => write!(f, "BINARY"),
@@ -1523,7 +2948,7 @@ pub enum CopyLegacyCsvOption {
 }
 
 impl DialectDisplay for CopyLegacyCsvOption {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use CopyLegacyCsvOption::*;
         Ok(match self {
             Header => write!(f, "HEADER"),
@@ -1545,7 +2970,7 @@ impl DialectDisplay for CopyLegacyCsvOption {
     }
 }
 
-///
+/// A `WHEN [NOT] MATCHED` clause of a `MERGE` statement
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MergeClause {
@@ -1562,7 +2987,7 @@ pub enum MergeClause {
 }
 
 impl DialectDisplay for MergeClause {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         use MergeClause::*;
         write!(f, "WHEN")?;
         Ok(match self {
@@ -1617,7 +3042,7 @@ pub enum DiscardObject {
 }
 
 impl DialectDisplay for DiscardObject {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             DiscardObject::ALL => f.write_str("ALL"),
             DiscardObject::PLANS => f.write_str("PLANS"),
@@ -1636,7 +3061,7 @@ pub enum CreateFunctionUsing {
 }
 
 impl DialectDisplay for CreateFunctionUsing {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(f, "USING ")?;
         Ok(match self {
             CreateFunctionUsing::Jar(uri) => write!(f, "JAR '{uri}'"),
@@ -1650,6 +3075,41 @@ impl DialectDisplay for CreateFunctionUsing {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_display_separated() {
+        let dialect = Dialect::postgres();
+        let idents = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| Ident::new(*name))
+                .collect::<Vec<_>>()
+        };
+
+        let empty = idents(&[]);
+        assert_eq!(display_comma_separated(&empty).sql(&dialect).unwrap(), "");
+        assert_eq!(display_separated(&empty, ";\n").sql(&dialect).unwrap(), "");
+
+        let single = idents(&["a"]);
+        assert_eq!(
+            display_comma_separated(&single).sql(&dialect).unwrap(),
+            "\"a\""
+        );
+        assert_eq!(
+            display_separated(&single, " ").sql(&dialect).unwrap(),
+            "\"a\""
+        );
+
+        let multiple = idents(&["a", "b", "c"]);
+        assert_eq!(
+            display_comma_separated(&multiple).sql(&dialect).unwrap(),
+            r#""a", "b", "c""#
+        );
+        assert_eq!(
+            display_separated(&multiple, " ").sql(&dialect).unwrap(),
+            r#""a" "b" "c""#
+        );
+    }
+
     #[test]
     fn test_window_frame_default() {
         let window_frame = WindowFrame::default();
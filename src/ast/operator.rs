@@ -39,7 +39,7 @@ pub enum UnaryOperator {
 }
 
 impl DialectDisplay for UnaryOperator {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             UnaryOperator::Plus => "+",
             UnaryOperator::Minus => "-",
@@ -91,7 +91,7 @@ pub enum BinaryOperator {
 }
 
 impl DialectDisplay for BinaryOperator {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             BinaryOperator::Plus => "+",
             BinaryOperator::Minus => "-",
@@ -126,3 +126,41 @@ impl DialectDisplay for BinaryOperator {
         })?)
     }
 }
+
+impl BinaryOperator {
+    /// How tightly the operator binds its operands, matching the precedence
+    /// used by the parser
+    pub(crate) fn precedence(&self) -> u8 {
+        match self {
+            BinaryOperator::Or => 5,
+            BinaryOperator::And => 10,
+            BinaryOperator::Gt
+            | BinaryOperator::Lt
+            | BinaryOperator::GtEq
+            | BinaryOperator::LtEq
+            | BinaryOperator::Spaceship
+            | BinaryOperator::Eq
+            | BinaryOperator::NotEq
+            | BinaryOperator::Like
+            | BinaryOperator::NotLike
+            | BinaryOperator::ILike
+            | BinaryOperator::NotILike
+            | BinaryOperator::PGRegexMatch
+            | BinaryOperator::PGRegexIMatch
+            | BinaryOperator::PGRegexNotMatch
+            | BinaryOperator::PGRegexNotIMatch => 20,
+            BinaryOperator::BitwiseOr => 21,
+            BinaryOperator::BitwiseXor
+            | BinaryOperator::PGBitwiseXor
+            | BinaryOperator::PGBitwiseShiftLeft
+            | BinaryOperator::PGBitwiseShiftRight => 22,
+            BinaryOperator::BitwiseAnd => 23,
+            BinaryOperator::Xor => 24,
+            BinaryOperator::Plus | BinaryOperator::Minus => 30,
+            BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Modulo
+            | BinaryOperator::StringConcat => 40,
+        }
+    }
+}
@@ -15,10 +15,14 @@ use alloc::{boxed::Box, vec::Vec};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::fmt::Write;
 
+use crate::ast::scope::correlated_subqueries_in;
 use crate::ast::*;
-use crate::dialect::{Dialect, DialectDisplay};
+use crate::dialect::{
+    BooleanLiteralStyle, Dialect, DialectDisplay, LateralStyle, LimitStyle, UnnestStyle,
+};
 
 /// The most complete variant of a `SELECT` query expression, optionally
 /// including `WITH`, `UNION` / other set operations, and `ORDER BY`.
@@ -31,6 +35,8 @@ pub struct Query {
     pub body: Box<SetExpr>,
     /// ORDER BY
     pub order_by: Vec<OrderByExpr>,
+    /// `LIMIT <N> BY <expr>[, ...]`, as in ClickHouse
+    pub limit_by: Option<LimitBy>,
     /// `LIMIT { <N> | ALL }`
     pub limit: Option<Expr>,
     /// `OFFSET <N> [ { ROW | ROWS } ]`
@@ -39,14 +45,34 @@ pub struct Query {
     pub fetch: Option<Fetch>,
     /// `FOR { UPDATE | SHARE }`
     pub lock: Option<LockType>,
+    /// `SETTINGS <key> = <value>[, ...]`, as in ClickHouse, in the order given
+    pub settings: Vec<Setting>,
 }
 
 impl DialectDisplay for Query {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        // Derived tables and subqueries nest queries without a set expression
+        // in between
+        let _guard = RenderDepthGuard::enter(dialect)?;
         if let Some(ref with) = self.with {
             write!(f, "{} ", with.sql(dialect)?)?;
         }
-        write!(f, "{}", self.body.sql(dialect)?)?;
+        // A limit without ORDER BY or OFFSET can be a TOP of the select
+        let top_select;
+        let (body, limit) = match (self.body.as_ref(), &self.limit) {
+            (SetExpr::Select(select), Some(limit))
+                if dialect.supports_top
+                    && select.top.is_none()
+                    && self.order_by.is_empty()
+                    && self.offset.is_none()
+                    && self.fetch.is_none() =>
+            {
+                top_select = with_top(select, limit);
+                (&top_select, None)
+            }
+            (body, limit) => (body, limit.as_ref()),
+        };
+        write!(f, "{}", body.sql(dialect)?)?;
         if !self.order_by.is_empty() {
             write!(
                 f,
@@ -54,22 +80,90 @@ impl DialectDisplay for Query {
                 display_comma_separated(&self.order_by).sql(dialect)?
             )?;
         }
-        if let Some(ref limit) = self.limit {
-            write!(f, " LIMIT {}", limit.sql(dialect)?)?;
+        if let Some(limit_by) = &self.limit_by {
+            write!(f, " {}", limit_by.sql(dialect)?)?;
+        }
+        if let Some(limit) = limit {
+            if !dialect.limit_allows_expression
+                && !matches!(
+                    limit,
+                    Expr::Value(Value::Number(_, _) | Value::Placeholder(_))
+                )
+            {
+                return Err(SqlGenError::DialectError(format!(
+                    "LIMIT {} is not supported by dialect, the limit must be a number",
+                    limit.sql(dialect)?
+                )));
+            }
+        }
+        let limit_fetch;
+        let fetch = match (limit, dialect.limit_style) {
+            (Some(limit), LimitStyle::Limit) => {
+                write!(f, " LIMIT {}", limit.sql(dialect)?)?;
+                self.fetch.as_ref()
+            }
+            (Some(_), LimitStyle::Fetch | LimitStyle::OffsetFetch) if self.fetch.is_some() => {
+                return Err(SqlGenError::DialectError(
+                    "LIMIT and FETCH can't both be rendered as FETCH".to_string(),
+                ))
+            }
+            (Some(limit), LimitStyle::Fetch | LimitStyle::OffsetFetch) => {
+                limit_fetch = Fetch {
+                    with_ties: false,
+                    percent: false,
+                    quantity: Some(limit.clone()),
+                };
+                Some(&limit_fetch)
+            }
+            (None, _) => self.fetch.as_ref(),
+        };
+        if dialect.require_order_by_with_offset
+            && self.order_by.is_empty()
+            && (self.offset.is_some() || fetch.is_some())
+        {
+            return Err(SqlGenError::DialectError(
+                "OFFSET and FETCH require an ORDER BY".to_string(),
+            ));
         }
         if let Some(ref offset) = self.offset {
             write!(f, " {}", offset.sql(dialect)?)?;
+        } else if fetch.is_some() && dialect.limit_style == LimitStyle::OffsetFetch {
+            write!(f, " OFFSET 0 ROWS")?;
         }
-        if let Some(ref fetch) = self.fetch {
+        if let Some(fetch) = fetch {
             write!(f, " {}", fetch.sql(dialect)?)?;
         }
         if let Some(ref lock) = self.lock {
             write!(f, " {}", lock.sql(dialect)?)?;
         }
+        if !self.settings.is_empty() {
+            if !dialect.supports_query_settings {
+                return Err(dialect.unsupported("SETTINGS"));
+            }
+            write!(
+                f,
+                " SETTINGS {}",
+                display_comma_separated(&self.settings).sql(dialect)?
+            )?;
+        }
         Ok(())
     }
 }
 
+/// `select` limited with `TOP (limit)`. Kept out of [Query::fmt] so that the
+/// copy of the select isn't part of its stack frame when rendering nested
+/// queries.
+#[inline(never)]
+fn with_top(select: &Select, limit: &Expr) -> SetExpr {
+    let mut select = Box::new(select.clone());
+    select.top = Some(Top {
+        with_ties: false,
+        percent: false,
+        quantity: Some(limit.clone()),
+    });
+    SetExpr::Select(select)
+}
+
 /// A node in a tree, representing a "query body" expression, roughly:
 /// `SELECT ... [ {UNION|EXCEPT|INTERSECT} SELECT ...]`
 #[allow(clippy::large_enum_variant)]
@@ -91,8 +185,40 @@ pub enum SetExpr {
     Values(Values),
 }
 
+thread_local! {
+    /// The number of queries, set expressions, expressions and function calls
+    /// being rendered by the current thread
+    static RENDER_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts a node of the tree as being rendered until dropped
+pub(crate) struct RenderDepthGuard;
+
+impl RenderDepthGuard {
+    pub(crate) fn enter(dialect: &Dialect) -> Result<Self, SqlGenError> {
+        let depth = RENDER_DEPTH.with(|depth| {
+            depth.set(depth.get() + 1);
+            depth.get()
+        });
+        let guard = RenderDepthGuard;
+        match dialect.max_render_depth {
+            Some(max_depth) if depth > max_depth => Err(SqlGenError::DepthExceeded(max_depth)),
+            _ => Ok(guard),
+        }
+    }
+}
+
+impl Drop for RenderDepthGuard {
+    fn drop(&mut self) {
+        RENDER_DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
 impl DialectDisplay for SetExpr {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        // Queries and set operations render recursively, so deeply nested
+        // trees are rejected before they can overflow the stack
+        let _guard = RenderDepthGuard::enter(dialect)?;
         match self {
             SetExpr::Select(s) => Ok(write!(f, "{}", s.sql(dialect)?)?),
             SetExpr::Query(q) => Ok(write!(f, "({})", q.sql(dialect)?)?),
@@ -126,7 +252,7 @@ pub enum SetOperator {
 }
 
 impl DialectDisplay for SetOperator {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             SetOperator::Union => "UNION",
             SetOperator::Except => "EXCEPT",
@@ -141,6 +267,11 @@ impl DialectDisplay for SetOperator {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Select {
+    /// Optimizer hints, rendered as `SELECT /*+ hint ... */` for dialects that
+    /// support them and omitted otherwise
+    pub hints: Vec<String>,
+    /// `SELECT DISTINCT`. Every supported dialect treats NULLs as equal to each
+    /// other here, so the rendering doesn't depend on the dialect.
     pub distinct: bool,
     /// MSSQL syntax: `TOP (<N>) [ PERCENT ] [ WITH TIES ]`
     pub top: Option<Top>,
@@ -158,11 +289,43 @@ pub struct Select {
     pub group_by: Vec<Expr>,
     /// HAVING
     pub having: Option<Expr>,
+    /// QUALIFY, filtering on the results of window functions
+    pub qualify: Option<Expr>,
 }
 
 impl DialectDisplay for Select {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
-        write!(f, "SELECT{}", if self.distinct { " DISTINCT" } else { "" })?;
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if dialect.strict && !dialect.supports_correlated_select_subqueries {
+            for item in &self.projection {
+                if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item
+                {
+                    if let Some(subquery) =
+                        correlated_subqueries_in(expr, &self.from, dialect)?.first()
+                    {
+                        return Err(SqlGenError::DialectError(format!(
+                            "Correlated subquery in the select list is not supported by dialect: {}",
+                            subquery.sql(dialect)?
+                        )));
+                    }
+                }
+            }
+        }
+        if self.projection.is_empty() {
+            return Err(SqlGenError::EmptyProjection);
+        }
+        write!(f, "SELECT")?;
+        if !self.hints.is_empty() && dialect.supports_optimizer_hints {
+            // `*/` would end the hint comment early
+            let hints: Vec<String> = self
+                .hints
+                .iter()
+                .map(|hint| hint.replace("*/", "* /"))
+                .collect();
+            write!(f, " /*+ {} */", hints.join(" "))?;
+        }
+        if self.distinct {
+            write!(f, " DISTINCT")?;
+        }
         if let Some(ref top) = self.top {
             write!(f, " {}", top.sql(dialect)?)?;
         }
@@ -177,19 +340,30 @@ impl DialectDisplay for Select {
         }
 
         if !self.from.is_empty() {
-            write!(
-                f,
-                " FROM {}",
-                display_comma_separated(&self.from).sql(dialect)?
-            )?;
+            write!(f, " FROM ")?;
+            for (i, table) in self.from.iter().enumerate() {
+                if i == 0 {
+                    write!(f, "{}", table.sql(dialect)?)?;
+                } else if dialect.lateral_style == LateralStyle::Apply
+                    && table.relation.is_lateral()
+                {
+                    // `FROM a, LATERAL (...)` is a cross join with `a`
+                    write!(f, " CROSS APPLY {}", table.sql(dialect)?)?;
+                } else {
+                    write!(f, ", {}", table.sql(dialect)?)?;
+                }
+            }
+        } else if let Some(dummy) = &dialect.dummy_from_table {
+            // The dummy table isn't passed to the identifier hook
+            write!(f, " FROM {}", dummy.unhooked(dialect).sql(dialect)?)?;
         }
         if !self.lateral_views.is_empty() {
             for lv in &self.lateral_views {
-                write!(f, "{}", lv.sql(dialect)?)?;
+                write!(f, " {}", lv.sql(dialect)?)?;
             }
         }
         if let Some(ref selection) = self.selection {
-            write!(f, " WHERE {}", selection.sql(dialect)?)?;
+            write!(f, " WHERE {}", predicate_sql(selection, dialect)?)?;
         }
         if !self.group_by.is_empty() {
             write!(
@@ -199,7 +373,13 @@ impl DialectDisplay for Select {
             )?;
         }
         if let Some(ref having) = self.having {
-            write!(f, " HAVING {}", having.sql(dialect)?)?;
+            write!(f, " HAVING {}", predicate_sql(having, dialect)?)?;
+        }
+        if let Some(ref qualify) = self.qualify {
+            if !dialect.supports_qualify {
+                return Err(dialect.unsupported("QUALIFY"));
+            }
+            write!(f, " QUALIFY {}", predicate_sql(qualify, dialect)?)?;
         }
         Ok(())
     }
@@ -220,10 +400,10 @@ pub struct LateralView {
 }
 
 impl DialectDisplay for LateralView {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(
             f,
-            " LATERAL VIEW{outer} {} {}",
+            "LATERAL VIEW{outer} {} {}",
             self.lateral_view.sql(dialect)?,
             self.lateral_view_name.sql(dialect)?,
             outer = if self.outer { " OUTER" } else { "" }
@@ -247,7 +427,7 @@ pub struct With {
 }
 
 impl DialectDisplay for With {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(write!(
             f,
             "WITH {}{}",
@@ -270,7 +450,7 @@ pub struct Cte {
 }
 
 impl DialectDisplay for Cte {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(
             f,
             "{} AS ({})",
@@ -293,24 +473,134 @@ pub enum SelectItem {
     /// An expression, followed by `[ AS ] alias`
     ExprWithAlias { expr: Expr, alias: Ident },
     /// `alias.*` or even `schema.table.*`
-    QualifiedWildcard(ObjectName),
+    QualifiedWildcard(ObjectName, WildcardOptions),
     /// An unqualified `*`
-    Wildcard,
+    Wildcard(WildcardOptions),
 }
 
 impl DialectDisplay for SelectItem {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        // A predicate isn't a value where booleans are integers, so it's
+        // selected as 1 or 0 like a boolean literal
+        let value = |expr: &Expr| {
+            if dialect.boolean_literal_style == BooleanLiteralStyle::Integer {
+                boolean_as_integer(expr).sql(dialect)
+            } else {
+                expr.sql(dialect)
+            }
+        };
         Ok(match &self {
-            SelectItem::UnnamedExpr(expr) => write!(f, "{}", expr.sql(dialect)?),
+            SelectItem::UnnamedExpr(expr) => write!(f, "{}", value(expr)?),
             SelectItem::ExprWithAlias { expr, alias } => {
-                write!(f, "{} AS {}", expr.sql(dialect)?, alias.sql(dialect)?)
+                write!(f, "{} AS {}", value(expr)?, alias.sql(dialect)?)
             }
-            SelectItem::QualifiedWildcard(prefix) => write!(f, "{}.*", prefix.sql(dialect)?),
-            SelectItem::Wildcard => write!(f, "*"),
+            SelectItem::QualifiedWildcard(prefix, options) => {
+                write!(f, "{}.*{}", prefix.sql(dialect)?, options.sql(dialect)?)
+            }
+            SelectItem::Wildcard(options) => write!(f, "*{}", options.sql(dialect)?),
         }?)
     }
 }
 
+/// The modifiers of a wildcard in a projection, which are rendered after the
+/// `*`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WildcardOptions {
+    /// `* ILIKE 'pattern'` in Snowflake, selecting only the columns whose name
+    /// matches the pattern
+    pub ilike: Option<String>,
+    /// `* RENAME (a AS b)` in Snowflake, selecting each column `a` as `b`
+    pub rename: Vec<(Ident, Ident)>,
+}
+
+impl WildcardOptions {
+    /// Whether the wildcard has no modifiers and selects every column
+    pub fn is_empty(&self) -> bool {
+        self.ilike.is_none() && self.rename.is_empty()
+    }
+}
+
+impl DialectDisplay for WildcardOptions {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if let Some(pattern) = &self.ilike {
+            if !dialect.supports_wildcard_ilike {
+                return Err(dialect.unsupported("Wildcard ILIKE"));
+            }
+            write!(
+                f,
+                " ILIKE '{}'",
+                value::escape_single_quote_string(pattern).sql(dialect)?
+            )?;
+        }
+        if !self.rename.is_empty() {
+            if !dialect.supports_wildcard_rename {
+                return Err(dialect.unsupported("Wildcard RENAME"));
+            }
+            let renames = self
+                .rename
+                .iter()
+                .map(|(column, alias)| {
+                    Ok(format!(
+                        "{} AS {}",
+                        column.sql(dialect)?,
+                        alias.sql(dialect)?
+                    ))
+                })
+                .collect::<Result<Vec<_>, SqlGenError>>()?;
+            write!(f, " RENAME ({})", renames.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// `TABLESAMPLE <method> (<percentage>) [ REPEATABLE (<seed>) ]`, which reads
+/// a random sample of a table's rows
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TableSample {
+    pub method: TableSampleMethod,
+    /// The percentage of rows to sample
+    pub percentage: Expr,
+    /// The seed that makes the sample the same from one query to the next
+    pub repeatable: Option<Expr>,
+}
+
+impl DialectDisplay for TableSample {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if !dialect.supports_table_sample {
+            return Err(dialect.unsupported("TABLESAMPLE"));
+        }
+        let method = match self.method {
+            TableSampleMethod::Bernoulli => "BERNOULLI",
+            TableSampleMethod::System => "SYSTEM",
+        };
+        write!(
+            f,
+            "TABLESAMPLE {}({})",
+            method,
+            self.percentage.sql(dialect)?
+        )?;
+        if let Some(seed) = &self.repeatable {
+            if !dialect.supports_table_sample_repeatable {
+                return Err(dialect.unsupported("TABLESAMPLE with REPEATABLE"));
+            }
+            write!(f, " REPEATABLE({})", seed.sql(dialect)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// How [TableSample] picks rows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TableSampleMethod {
+    /// Each row is picked with the given probability
+    Bernoulli,
+    /// Each block of rows is picked with the given probability
+    System,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TableWithJoins {
@@ -319,10 +609,10 @@ pub struct TableWithJoins {
 }
 
 impl DialectDisplay for TableWithJoins {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(f, "{}", self.relation.sql(dialect)?)?;
         for join in &self.joins {
-            write!(f, "{}", join.sql(dialect)?)?;
+            write!(f, " {}", join.sql(dialect)?)?;
         }
         Ok(())
     }
@@ -345,6 +635,8 @@ pub enum TableFactor {
         args: Option<Vec<FunctionArg>>,
         /// MSSQL-specific `WITH (...)` hints such as NOLOCK.
         with_hints: Vec<Expr>,
+        /// `TABLESAMPLE ...` after the table and its alias
+        sample: Option<Box<TableSample>>,
     },
     Derived {
         lateral: bool,
@@ -364,11 +656,17 @@ pub enum TableFactor {
     /// | 20      | 1      |
     /// | 30      | 2      |
     /// +---------+--------+
+    ///
+    /// Several arrays can be unnested side by side, as in PostgreSQL's
+    /// `UNNEST(a, b) WITH ORDINALITY`
     UNNEST {
         alias: Option<TableAlias>,
-        array_expr: Box<Expr>,
+        array_exprs: Vec<Expr>,
+        /// `WITH OFFSET`, numbering the elements from 0
         with_offset: bool,
         with_offset_alias: Option<Ident>,
+        /// `WITH ORDINALITY`, numbering the elements from 1
+        with_ordinality: bool,
     },
     /// Represents a parenthesized table factor. The SQL spec only allows a
     /// join expression (`(foo <JOIN> bar [ <JOIN> baz ... ])`) to be nested,
@@ -379,21 +677,32 @@ pub enum TableFactor {
     NestedJoin(Box<TableWithJoins>),
 }
 
+impl TableFactor {
+    /// Whether this is a `LATERAL` derived table
+    fn is_lateral(&self) -> bool {
+        matches!(self, TableFactor::Derived { lateral: true, .. })
+    }
+}
+
 impl DialectDisplay for TableFactor {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         match self {
             TableFactor::Table {
                 name,
                 alias,
                 args,
                 with_hints,
+                sample,
             } => {
                 write!(f, "{}", name.sql(dialect)?)?;
                 if let Some(args) = args {
                     write!(f, "({})", display_comma_separated(args).sql(dialect)?)?;
                 }
                 if let Some(alias) = alias {
-                    write!(f, " AS {}", alias.sql(dialect)?)?;
+                    write!(f, "{}", table_alias(alias, dialect)?)?;
+                }
+                if let Some(sample) = sample {
+                    write!(f, " {}", sample.sql(dialect)?)?;
                 }
                 if !with_hints.is_empty() {
                     write!(
@@ -409,37 +718,77 @@ impl DialectDisplay for TableFactor {
                 subquery,
                 alias,
             } => {
-                if *lateral {
+                // With APPLY, the join renders the lateral table's keyword
+                if *lateral && dialect.lateral_style == LateralStyle::Lateral {
                     write!(f, "LATERAL ")?;
                 }
+                if alias.is_none() && dialect.require_derived_alias {
+                    return Err(dialect.unsupported("Derived table without an alias"));
+                }
                 write!(f, "({})", subquery.sql(dialect)?)?;
                 if let Some(alias) = alias {
-                    write!(f, " AS {}", alias.sql(dialect)?)?;
+                    write!(f, "{}", table_alias(alias, dialect)?)?;
                 }
                 Ok(())
             }
             TableFactor::TableFunction { expr, alias } => {
                 write!(f, "TABLE({})", expr.sql(dialect)?)?;
                 if let Some(alias) = alias {
-                    write!(f, " AS {}", alias.sql(dialect)?)?;
+                    write!(f, "{}", table_alias(alias, dialect)?)?;
                 }
                 Ok(())
             }
             TableFactor::UNNEST {
                 alias,
-                array_expr,
+                array_exprs,
                 with_offset,
                 with_offset_alias,
+                with_ordinality,
             } => {
-                write!(f, "UNNEST({})", array_expr.sql(dialect)?)?;
-                if let Some(alias) = alias {
-                    write!(f, " AS {}", alias.sql(dialect)?)?;
+                if dialect.unnest_style == UnnestStyle::Unsupported {
+                    return Err(dialect.unsupported("UNNEST"));
                 }
-                if *with_offset {
-                    write!(f, " WITH OFFSET")?;
+                if array_exprs.len() > 1 && dialect.unnest_style == UnnestStyle::Offset {
+                    return Err(dialect.unsupported("UNNEST of multiple arrays"));
                 }
-                if let Some(alias) = with_offset_alias {
-                    write!(f, " AS {}", alias.sql(dialect)?)?;
+                write!(
+                    f,
+                    "UNNEST({})",
+                    display_comma_separated(array_exprs).sql(dialect)?
+                )?;
+                match dialect.unnest_style {
+                    UnnestStyle::Offset => {
+                        if *with_ordinality {
+                            return Err(dialect.unsupported("WITH ORDINALITY"));
+                        }
+                        if let Some(alias) = alias {
+                            write!(f, "{}", table_alias(alias, dialect)?)?;
+                        }
+                        if *with_offset {
+                            write!(f, " WITH OFFSET")?;
+                        }
+                        if let Some(alias) = with_offset_alias {
+                            write!(f, " AS {}", alias.sql(dialect)?)?;
+                        }
+                    }
+                    UnnestStyle::Ordinality => {
+                        if *with_offset {
+                            return Err(dialect.unsupported("WITH OFFSET"));
+                        }
+                        if with_offset_alias.is_some() {
+                            return Err(SqlGenError::DialectError(
+                                "WITH ORDINALITY can't be aliased, name its column in the table alias instead"
+                                    .to_string(),
+                            ));
+                        }
+                        if *with_ordinality {
+                            write!(f, " WITH ORDINALITY")?;
+                        }
+                        if let Some(alias) = alias {
+                            write!(f, "{}", table_alias(alias, dialect)?)?;
+                        }
+                    }
+                    UnnestStyle::Unsupported => unreachable!(),
                 }
                 Ok(())
             }
@@ -450,6 +799,15 @@ impl DialectDisplay for TableFactor {
     }
 }
 
+/// Render a table alias after its table, with `AS` unless the dialect omits it
+fn table_alias(alias: &TableAlias, dialect: &Dialect) -> Result<String, SqlGenError> {
+    if dialect.omit_table_alias_as {
+        Ok(format!(" {}", alias.sql(dialect)?))
+    } else {
+        Ok(format!(" AS {}", alias.sql(dialect)?))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct TableAlias {
@@ -458,7 +816,7 @@ pub struct TableAlias {
 }
 
 impl DialectDisplay for TableAlias {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(f, "{}", self.name.sql(dialect)?)?;
         if !self.columns.is_empty() {
             write!(
@@ -479,7 +837,7 @@ pub struct Join {
 }
 
 impl DialectDisplay for Join {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         fn prefix(constraint: &JoinConstraint) -> &'static str {
             match constraint {
                 JoinConstraint::Natural => "NATURAL ",
@@ -493,7 +851,7 @@ impl DialectDisplay for Join {
             let mut repr = String::new();
             match constraint {
                 JoinConstraint::On(expr) => {
-                    write!(repr, " ON {}", expr.sql(dialect)?)?;
+                    write!(repr, " ON {}", predicate_sql(expr, dialect)?)?;
                 }
                 JoinConstraint::Using(attrs) => {
                     write!(
@@ -506,38 +864,68 @@ impl DialectDisplay for Join {
             }
             Ok(repr)
         }
+        if dialect.disallow_natural_join {
+            if let JoinOperator::Inner(JoinConstraint::Natural)
+            | JoinOperator::LeftOuter(JoinConstraint::Natural)
+            | JoinOperator::RightOuter(JoinConstraint::Natural)
+            | JoinOperator::FullOuter(JoinConstraint::Natural) = &self.join_operator
+            {
+                return Err(dialect.unsupported("NATURAL JOIN"));
+            }
+        }
+        if dialect.lateral_style == LateralStyle::Apply && self.relation.is_lateral() {
+            let unconditional = |constraint: &JoinConstraint| {
+                matches!(
+                    constraint,
+                    JoinConstraint::None | JoinConstraint::On(Expr::Value(Value::Boolean(true)))
+                )
+            };
+            let apply = match &self.join_operator {
+                JoinOperator::Inner(constraint) if unconditional(constraint) => "CROSS APPLY",
+                JoinOperator::CrossJoin | JoinOperator::CrossApply => "CROSS APPLY",
+                JoinOperator::LeftOuter(constraint) if unconditional(constraint) => "OUTER APPLY",
+                JoinOperator::OuterApply => "OUTER APPLY",
+                _ => {
+                    return Err(SqlGenError::DialectError(
+                        "Only LATERAL joins without a condition can be rendered as APPLY"
+                            .to_string(),
+                    ))
+                }
+            };
+            return Ok(write!(f, "{} {}", apply, self.relation.sql(dialect)?)?);
+        }
         Ok(match &self.join_operator {
             JoinOperator::Inner(constraint) => write!(
                 f,
-                " {}JOIN {}{}",
+                "{}JOIN {}{}",
                 prefix(constraint),
                 self.relation.sql(dialect)?,
                 suffix(constraint, dialect)?
             ),
             JoinOperator::LeftOuter(constraint) => write!(
                 f,
-                " {}LEFT JOIN {}{}",
+                "{}LEFT JOIN {}{}",
                 prefix(constraint),
                 self.relation.sql(dialect)?,
                 suffix(constraint, dialect)?
             ),
             JoinOperator::RightOuter(constraint) => write!(
                 f,
-                " {}RIGHT JOIN {}{}",
+                "{}RIGHT JOIN {}{}",
                 prefix(constraint),
                 self.relation.sql(dialect)?,
                 suffix(constraint, dialect)?
             ),
             JoinOperator::FullOuter(constraint) => write!(
                 f,
-                " {}FULL JOIN {}{}",
+                "{}FULL JOIN {}{}",
                 prefix(constraint),
                 self.relation.sql(dialect)?,
                 suffix(constraint, dialect)?
             ),
-            JoinOperator::CrossJoin => write!(f, " CROSS JOIN {}", self.relation.sql(dialect)?),
-            JoinOperator::CrossApply => write!(f, " CROSS APPLY {}", self.relation.sql(dialect)?),
-            JoinOperator::OuterApply => write!(f, " OUTER APPLY {}", self.relation.sql(dialect)?),
+            JoinOperator::CrossJoin => write!(f, "CROSS JOIN {}", self.relation.sql(dialect)?),
+            JoinOperator::CrossApply => write!(f, "CROSS APPLY {}", self.relation.sql(dialect)?),
+            JoinOperator::OuterApply => write!(f, "OUTER APPLY {}", self.relation.sql(dialect)?),
         }?)
     }
 }
@@ -577,13 +965,16 @@ pub struct OrderByExpr {
 }
 
 impl DialectDisplay for OrderByExpr {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         write!(f, "{}", self.expr.sql(dialect)?)?;
         match self.asc {
             Some(true) => write!(f, " ASC")?,
             Some(false) => write!(f, " DESC")?,
             None => (),
         }
+        if self.nulls_first.is_some() && !dialect.supports_nulls_ordering {
+            return Err(dialect.unsupported("NULLS FIRST/LAST"));
+        }
         match self.nulls_first {
             Some(true) => write!(f, " NULLS FIRST")?,
             Some(false) => write!(f, " NULLS LAST")?,
@@ -601,13 +992,19 @@ pub struct Offset {
 }
 
 impl DialectDisplay for Offset {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
-        Ok(write!(
-            f,
-            "OFFSET {}{}",
-            self.value.sql(dialect)?,
-            self.rows.sql(dialect)?
-        )?)
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        let value = self.value.sql(dialect)?;
+        let rows = match self.rows {
+            OffsetRows::None if dialect.standard_offset_rows => {
+                if matches!(self.value, Expr::Value(Value::Number(..))) && value == "1" {
+                    &OffsetRows::Row
+                } else {
+                    &OffsetRows::Rows
+                }
+            }
+            ref rows => rows,
+        };
+        Ok(write!(f, "OFFSET {}{}", value, rows.sql(dialect)?)?)
     }
 }
 
@@ -622,7 +1019,7 @@ pub enum OffsetRows {
 }
 
 impl DialectDisplay for OffsetRows {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         match self {
             OffsetRows::None => Ok(()),
             OffsetRows::Row => Ok(write!(f, " ROW")?),
@@ -631,6 +1028,30 @@ impl DialectDisplay for OffsetRows {
     }
 }
 
+/// `LIMIT <N> BY <expr>[, ...]`, which keeps the first `N` rows for each
+/// distinct value of the expressions. It is applied before the query's
+/// `LIMIT`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LimitBy {
+    pub limit: Expr,
+    pub by: Vec<Expr>,
+}
+
+impl DialectDisplay for LimitBy {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if !dialect.supports_limit_by {
+            return Err(dialect.unsupported("LIMIT BY"));
+        }
+        Ok(write!(
+            f,
+            "LIMIT {} BY {}",
+            self.limit.sql(dialect)?,
+            display_comma_separated(&self.by).sql(dialect)?
+        )?)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Fetch {
@@ -640,23 +1061,45 @@ pub struct Fetch {
 }
 
 impl DialectDisplay for Fetch {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         let extension = if self.with_ties { "WITH TIES" } else { "ONLY" };
         Ok(if let Some(ref quantity) = self.quantity {
             let percent = if self.percent { " PERCENT" } else { "" };
-            write!(
-                f,
-                "FETCH FIRST {}{} ROWS {}",
-                quantity.sql(dialect)?,
-                percent,
-                extension
-            )
+            // Only simple quantities may follow FETCH FIRST without parentheses
+            let quantity = match quantity {
+                Expr::Value(_) | Expr::Identifier(_) | Expr::Nested(_) => quantity.sql(dialect)?,
+                _ => format!("({})", quantity.sql(dialect)?),
+            };
+            write!(f, "FETCH FIRST {}{} ROWS {}", quantity, percent, extension)
         } else {
             write!(f, "FETCH FIRST ROWS {}", extension)
         }?)
     }
 }
 
+/// A query setting `key = value`. The key is rendered unquoted, so it must be
+/// a plain identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Setting {
+    pub key: String,
+    pub value: Value,
+}
+
+impl DialectDisplay for Setting {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        let mut chars = self.key.chars();
+        let plain = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !plain {
+            return Err(SqlGenError::InvalidIdentifier(self.key.clone()));
+        }
+        Ok(write!(f, "{} = {}", self.key, self.value.sql(dialect)?)?)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum LockType {
@@ -665,7 +1108,7 @@ pub enum LockType {
 }
 
 impl DialectDisplay for LockType {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         let select_lock = match self {
             LockType::Share => "FOR SHARE",
             LockType::Update => "FOR UPDATE",
@@ -684,7 +1127,7 @@ pub struct Top {
 }
 
 impl DialectDisplay for Top {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         let extension = if self.with_ties { " WITH TIES" } else { "" };
         Ok(if let Some(ref quantity) = self.quantity {
             let percent = if self.percent { " PERCENT" } else { "" };
@@ -706,18 +1149,49 @@ impl DialectDisplay for Top {
 pub struct Values(pub Vec<Vec<Expr>>);
 
 impl DialectDisplay for Values {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        if self.0.is_empty() {
+            return Err(SqlGenError::EmptyValues);
+        }
         write!(f, "VALUES ")?;
         let mut delim = "";
         for row in &self.0 {
             write!(f, "{}", delim)?;
             delim = ", ";
-            write!(f, "({})", display_comma_separated(row).sql(dialect)?)?;
+            if dialect.boolean_literal_style == BooleanLiteralStyle::Integer {
+                // Boolean literals render as 1 and 0, so predicates must too for
+                // the column to have a consistent type
+                let row: Vec<Expr> = row.iter().map(boolean_as_integer).collect();
+                write!(f, "({})", display_comma_separated(&row).sql(dialect)?)?;
+            } else {
+                write!(f, "({})", display_comma_separated(row).sql(dialect)?)?;
+            }
         }
         Ok(())
     }
 }
 
+/// Rewrite a boolean predicate `x` to `CASE WHEN x THEN 1 WHEN NOT x THEN 0 END`,
+/// which is NULL when `x` is
+fn boolean_as_integer(expr: &Expr) -> Expr {
+    if !expr.is_boolean() || matches!(expr, Expr::Value(Value::Boolean(_))) {
+        return expr.clone();
+    }
+    let number = |n: &str| Expr::Value(Value::Number(n.parse().unwrap(), false));
+    Expr::Case {
+        operand: None,
+        conditions: vec![
+            expr.clone(),
+            Expr::UnaryOp {
+                op: UnaryOperator::Not,
+                expr: Box::new(Expr::Nested(Box::new(expr.clone()))),
+            },
+        ],
+        results: vec![number("1"), number("0")],
+        else_result: None,
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SelectInto {
@@ -728,7 +1202,7 @@ pub struct SelectInto {
 }
 
 impl DialectDisplay for SelectInto {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         let temporary = if self.temporary { " TEMPORARY" } else { "" };
         let unlogged = if self.unlogged { " UNLOGGED" } else { "" };
         let table = if self.table { " TABLE" } else { "" };
@@ -0,0 +1,1152 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rewrites of `Query` ASTs that preserve their results
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use std::collections::HashMap;
+
+use crate::ast::scope::same_ident;
+use crate::ast::visit::{self, Visitor};
+use crate::ast::visit_mut::VisitorMut;
+use crate::ast::visit_mut::{
+    walk_expr, walk_function, walk_function_arg, walk_order_by, walk_query, walk_select,
+    walk_table_factor,
+};
+use crate::ast::*;
+use crate::dialect::{Dialect, DialectDisplay, IdentifierCase, PlaceholderStyle};
+
+/// The ordered columns of the tables a query may reference, keyed by table name
+pub type Schema = HashMap<String, Vec<Ident>>;
+
+impl Query {
+    /// Replace `*` and `table.*` items in the projection with the columns they
+    /// select. Columns of named tables are looked up in `schema`, columns of
+    /// CTEs and derived tables are taken from their own projections.
+    pub fn expand_wildcards(&mut self, schema: &Schema) -> Result<(), SqlGenError> {
+        let schema = self.schema_with_ctes(schema)?;
+        expand_set_expr(&mut self.body, &schema)
+    }
+
+    /// Expand wildcards and replace the projection with `ordered_columns`, in
+    /// that order, so that the output columns don't depend on the order in
+    /// which `*` expands. Fails if a requested column isn't produced by the
+    /// query.
+    pub fn pin_output_columns(
+        &mut self,
+        ordered_columns: &[Ident],
+        schema: &Schema,
+    ) -> Result<(), SqlGenError> {
+        self.expand_wildcards(schema)?;
+        pin_set_expr(&mut self.body, ordered_columns)
+    }
+
+    /// Render the query in a canonical form, so that queries that differ only
+    /// in redundant parentheses, implicit sort directions and NULL ordering, or
+    /// the case of function names render identically. This is intended for
+    /// comparing queries, e.g. in snapshot tests, and is not necessarily the
+    /// most readable rendering. NULL ordering is only made explicit for
+    /// dialects that support `NULLS FIRST` and `NULLS LAST`.
+    pub fn canonical_sql(&self, dialect: &Dialect) -> Result<String, SqlGenError> {
+        let mut query = self.clone();
+        Canonicalize { dialect }.visit_query(&mut query)?;
+        query.sql(dialect)
+    }
+
+    /// A copy of the query that returns no rows, for fetching just the output
+    /// schema. The limit is set to `0` and the offset, fetch, ordering and
+    /// locking are dropped. Set operations and `VALUES` are wrapped in
+    /// `SELECT * FROM (...)` so that the limit applies to the whole body in
+    /// every dialect.
+    pub fn schema_probe(&self) -> Query {
+        let mut query = match self.body.as_ref() {
+            SetExpr::Select(_) => self.clone(),
+            SetExpr::Query(_) | SetExpr::SetOperation { .. } | SetExpr::Values(_) => {
+                select_all_from_derived(
+                    Query {
+                        order_by: vec![],
+                        limit_by: None,
+                        limit: None,
+                        offset: None,
+                        fetch: None,
+                        lock: None,
+                        settings: vec![],
+                        ..self.clone()
+                    },
+                    "probe",
+                )
+            }
+        };
+        query.order_by.clear();
+        query.limit = Some(Expr::Value(Value::Number("0".parse().unwrap(), false)));
+        query.limit_by = None;
+        query.offset = None;
+        query.fetch = None;
+        query.lock = None;
+        query
+    }
+
+    /// Move each branch of a top-level `UNION` or `UNION ALL` into its own
+    /// CTE, in order, and replace the body with a union of `SELECT *` from
+    /// those CTEs. Branches are collected through nested unions of the same
+    /// kind, so `a UNION ALL b UNION ALL c` produces three CTEs. The CTEs are
+    /// named `branch_1`, `branch_2` and so on, skipping names used by the
+    /// query's CTEs and tables, and are appended to any existing `WITH`.
+    pub fn union_branches_to_ctes(&mut self) -> Result<(), SqlGenError> {
+        let all = match self.body.as_ref() {
+            SetExpr::SetOperation {
+                op: SetOperator::Union,
+                all,
+                ..
+            } => *all,
+            _ => {
+                return Err(SqlGenError::RewriteError(
+                    "Expected a UNION to move into CTEs".to_string(),
+                ))
+            }
+        };
+
+        let mut used = TableNames::default();
+        used.visit_query(self)?;
+        let body = core::mem::replace(self.body.as_mut(), SetExpr::Values(Values(vec![])));
+        let mut branches = vec![];
+        collect_union_branches(body, all, &mut branches);
+
+        let mut ctes = vec![];
+        let mut index = 0;
+        for branch in branches {
+            let name = loop {
+                index += 1;
+                let name = format!("branch_{}", index);
+                if !used.contains(&name) {
+                    break name;
+                }
+            };
+            let query = match branch {
+                SetExpr::Query(query) => *query,
+                branch => Query {
+                    with: None,
+                    body: Box::new(branch),
+                    order_by: vec![],
+                    limit_by: None,
+                    limit: None,
+                    offset: None,
+                    fetch: None,
+                    lock: None,
+                    settings: vec![],
+                },
+            };
+            ctes.push(Cte {
+                alias: TableAlias {
+                    name: Ident::new(name),
+                    columns: vec![],
+                },
+                query,
+                from: None,
+            });
+        }
+
+        let body = ctes
+            .iter()
+            .map(|cte| select_all_from(&cte.alias.name))
+            .reduce(|left, right| SetExpr::SetOperation {
+                op: SetOperator::Union,
+                all,
+                left: Box::new(left),
+                right: Box::new(right),
+            })
+            .expect("a union has at least two branches");
+        *self.body = body;
+        match &mut self.with {
+            Some(with) => with.cte_tables.extend(ctes),
+            None => {
+                self.with = Some(With {
+                    recursive: false,
+                    cte_tables: ctes,
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// The page `page`, counting from 0, of `page_size` rows of the query.
+    /// The keys of `order_by`, which must not be empty so that pages don't
+    /// overlap, are appended to the query's own `ORDER BY` as tiebreakers. A
+    /// query that already has a limit, offset or fetch is wrapped in
+    /// `SELECT * FROM (...)` so that the page is taken from its rows, in the
+    /// order of `order_by`.
+    pub fn paginate(
+        self,
+        page: u64,
+        page_size: u64,
+        order_by: Vec<OrderByExpr>,
+    ) -> Result<Query, SqlGenError> {
+        if order_by.is_empty() {
+            return Err(SqlGenError::RewriteError(
+                "Pagination requires an ORDER BY for the pages to be deterministic".to_string(),
+            ));
+        }
+        let offset = page.checked_mul(page_size).ok_or_else(|| {
+            SqlGenError::RewriteError(format!(
+                "Offset of page {} of {} rows is too large",
+                page, page_size
+            ))
+        })?;
+        let mut query = if self.limit_by.is_some()
+            || self.limit.is_some()
+            || self.offset.is_some()
+            || self.fetch.is_some()
+        {
+            select_all_from_derived(self, "page")
+        } else {
+            self
+        };
+        let number = |n: u64| Expr::Value(Value::Number(n.to_string().parse().unwrap(), false));
+        for key in order_by {
+            if !query
+                .order_by
+                .iter()
+                .any(|existing| existing.expr == key.expr)
+            {
+                query.order_by.push(key);
+            }
+        }
+        query.limit = Some(number(page_size));
+        query.offset = if offset == 0 {
+            None
+        } else {
+            Some(Offset {
+                value: number(offset),
+                rows: OffsetRows::None,
+            })
+        };
+        Ok(query)
+    }
+
+    /// Remove the `ORDER BY` of derived tables and set operation branches, at
+    /// any depth, that don't also have a limit, offset, fetch or `TOP`, since
+    /// the order of their rows isn't guaranteed to reach the outer query. The
+    /// `ORDER BY` of the query itself is kept.
+    pub fn strip_redundant_inner_order_by(mut self) -> Query {
+        StripInnerOrderBy
+            .visit_query(&mut self)
+            .expect("stripping ORDER BY doesn't fail");
+        self
+    }
+
+    /// Replace the literal values of the query, including those of its CTEs and
+    /// subqueries, by placeholders in the [PlaceholderStyle] of the dialect,
+    /// returning the query and the values in the order of their placeholders.
+    /// `NULL`, intervals and existing placeholders are kept, as are the values
+    /// the dialects require to be literals: the positions in `ORDER BY 1` and
+    /// `GROUP BY 1`, the quantities of `LIMIT`, `OFFSET`, `FETCH` and `TOP`,
+    /// and the units and fields of functions such as `date_trunc`.
+    ///
+    /// [PlaceholderStyle]: crate::dialect::PlaceholderStyle
+    pub fn parameterize(self, dialect: &Dialect) -> (Query, Vec<Value>) {
+        self.parameterize_with(dialect, false)
+    }
+
+    /// Like [Query::parameterize], also replacing `NULL` when
+    /// `parameterize_nulls` is set
+    pub fn parameterize_with(
+        mut self,
+        dialect: &Dialect,
+        parameterize_nulls: bool,
+    ) -> (Query, Vec<Value>) {
+        let mut parameterize = Parameterize {
+            placeholder_style: dialect.placeholder_style,
+            parameterize_nulls,
+            values: vec![],
+        };
+        parameterize
+            .visit_query(&mut self)
+            .expect("parameterizing doesn't fail");
+        (self, parameterize.values)
+    }
+
+    /// The names of the columns produced by the query, after wildcard expansion
+    fn output_columns(&self, schema: &Schema) -> Result<Vec<Ident>, SqlGenError> {
+        let mut query = self.clone();
+        query.expand_wildcards(schema)?;
+        set_expr_output_columns(&query.body)
+    }
+
+    /// Extend `schema` with the output columns of the query's CTEs
+    fn schema_with_ctes(&self, schema: &Schema) -> Result<Schema, SqlGenError> {
+        let mut schema = schema.clone();
+        if let Some(with) = &self.with {
+            for cte in &with.cte_tables {
+                let columns = if cte.alias.columns.is_empty() {
+                    cte.query.output_columns(&schema)?
+                } else {
+                    cte.alias.columns.clone()
+                };
+                schema.insert(cte.alias.name.value.clone(), columns);
+            }
+        }
+        Ok(schema)
+    }
+}
+
+impl Values {
+    /// Cast the columns of the first row to `column_types`, so that engines
+    /// that infer the column types of `VALUES` from the first row don't infer
+    /// the wrong type from a NULL, as in `VALUES (CAST(NULL AS INTEGER), 'a')`.
+    /// Columns with a type of `None` are left as they are.
+    pub fn cast_first_row(&mut self, column_types: &[Option<DataType>]) -> Result<(), SqlGenError> {
+        let row = self.0.first_mut().ok_or_else(|| {
+            SqlGenError::RewriteError("VALUES must have at least one row".to_string())
+        })?;
+        if row.len() != column_types.len() {
+            return Err(SqlGenError::RewriteError(format!(
+                "Expected {} column types for VALUES, found {}",
+                row.len(),
+                column_types.len()
+            )));
+        }
+        for (expr, data_type) in row.iter_mut().zip(column_types) {
+            if let Some(data_type) = data_type {
+                let value = core::mem::replace(expr, Expr::Value(Value::Null));
+                *expr = Expr::Cast {
+                    expr: Box::new(value),
+                    data_type: data_type.clone(),
+                };
+            }
+        }
+        Ok(())
+    }
+}
+
+fn expand_set_expr(body: &mut SetExpr, schema: &Schema) -> Result<(), SqlGenError> {
+    match body {
+        SetExpr::Select(select) => expand_select(select, schema),
+        SetExpr::Query(query) => query.expand_wildcards(schema),
+        SetExpr::SetOperation { left, right, .. } => {
+            expand_set_expr(left, schema)?;
+            expand_set_expr(right, schema)
+        }
+        SetExpr::Values(_) => Ok(()),
+    }
+}
+
+fn expand_select(select: &mut Select, schema: &Schema) -> Result<(), SqlGenError> {
+    if !select.projection.iter().any(|item| {
+        matches!(
+            item,
+            SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..)
+        )
+    }) {
+        return Ok(());
+    }
+
+    let mut relations = Vec::new();
+    let mut columns = Vec::new();
+    for table in &select.from {
+        columns.extend(collect_relations(table, schema, &mut relations)?);
+    }
+    if relations.is_empty() {
+        return Err(SqlGenError::RewriteError(
+            "Cannot expand a wildcard without a FROM clause".to_string(),
+        ));
+    }
+    // Qualify columns when more than one relation may provide them
+    let qualify = relations.len() > 1;
+
+    let mut projection = Vec::new();
+    for item in select.projection.drain(..) {
+        match item {
+            SelectItem::Wildcard(options) | SelectItem::QualifiedWildcard(_, options)
+                if !options.is_empty() =>
+            {
+                return Err(SqlGenError::RewriteError(
+                    "Cannot expand a wildcard with modifiers".to_string(),
+                ))
+            }
+            SelectItem::Wildcard(_) => {
+                projection.extend(columns.iter().map(|(index, column)| match index {
+                    Some(index) if qualify => relations[*index].qualified_item(column),
+                    _ => SelectItem::UnnamedExpr(Expr::Identifier(column.clone())),
+                }));
+            }
+            SelectItem::QualifiedWildcard(prefix, _) => {
+                let relation = relations
+                    .iter()
+                    .find(|relation| relation.matches(&prefix))
+                    .ok_or_else(|| {
+                        SqlGenError::RewriteError(format!(
+                            "Cannot expand {}.*: no such table in FROM clause",
+                            object_name_key(&prefix)
+                        ))
+                    })?;
+                projection.extend(
+                    relation
+                        .columns
+                        .iter()
+                        .map(|column| relation.qualified_item(column)),
+                );
+            }
+            item => projection.push(item),
+        }
+    }
+    select.projection = projection;
+    Ok(())
+}
+
+/// A table in a FROM clause together with the columns it provides
+struct Relation {
+    qualifier: ObjectName,
+    columns: Vec<Ident>,
+}
+
+impl Relation {
+    /// Whether `prefix.*` refers to this relation, e.g. `t.*` for `s.t`
+    fn matches(&self, prefix: &ObjectName) -> bool {
+        let qualifier = &self.qualifier.0;
+        prefix.0.len() <= qualifier.len()
+            && qualifier[qualifier.len() - prefix.0.len()..]
+                .iter()
+                .zip(&prefix.0)
+                .all(|(a, b)| same_ident(a, b, IdentifierCase::Insensitive))
+    }
+
+    fn qualified_item(&self, column: &Ident) -> SelectItem {
+        let mut parts = self.qualifier.0.clone();
+        parts.push(column.clone());
+        SelectItem::UnnamedExpr(Expr::CompoundIdentifier(parts))
+    }
+}
+
+/// A column selected by `*`: the index of the relation providing it, or
+/// `None` for a column merged by a `USING` or `NATURAL` join
+type WildcardColumn = (Option<usize>, Ident);
+
+/// Collect the relations of `table` and return the columns `*` selects from
+/// it, in the order `*` selects them
+fn collect_relations(
+    table: &TableWithJoins,
+    schema: &Schema,
+    relations: &mut Vec<Relation>,
+) -> Result<Vec<WildcardColumn>, SqlGenError> {
+    let mut columns = collect_table_factor(&table.relation, schema, relations)?;
+    for join in &table.joins {
+        let right = collect_table_factor(&join.relation, schema, relations)?;
+        columns = join_columns(columns, right, &join.join_operator);
+    }
+    Ok(columns)
+}
+
+/// The columns `*` selects from a join: the columns merged by `USING` or
+/// `NATURAL` once, unqualified and first, then the other columns of the left
+/// and right sides
+fn join_columns(
+    mut left: Vec<WildcardColumn>,
+    right: Vec<WildcardColumn>,
+    operator: &JoinOperator,
+) -> Vec<WildcardColumn> {
+    let same = |a: &Ident, b: &Ident| same_ident(a, b, IdentifierCase::Insensitive);
+    let constraint = match operator {
+        JoinOperator::Inner(constraint)
+        | JoinOperator::LeftOuter(constraint)
+        | JoinOperator::RightOuter(constraint)
+        | JoinOperator::FullOuter(constraint) => constraint,
+        _ => &JoinConstraint::None,
+    };
+    let mut merged: Vec<Ident> = vec![];
+    match constraint {
+        JoinConstraint::Using(columns) => merged.extend(columns.iter().cloned()),
+        JoinConstraint::Natural => {
+            for (_, column) in &left {
+                if right.iter().any(|(_, other)| same(column, other))
+                    && !merged.iter().any(|name| same(name, column))
+                {
+                    merged.push(column.clone());
+                }
+            }
+        }
+        _ => {}
+    }
+    if merged.is_empty() {
+        left.extend(right);
+        return left;
+    }
+    let rest = left
+        .into_iter()
+        .chain(right)
+        .filter(|(_, column)| !merged.iter().any(|name| same(name, column)));
+    merged
+        .iter()
+        .map(|name| (None, name.clone()))
+        .chain(rest)
+        .collect()
+}
+
+fn collect_table_factor(
+    factor: &TableFactor,
+    schema: &Schema,
+    relations: &mut Vec<Relation>,
+) -> Result<Vec<WildcardColumn>, SqlGenError> {
+    let (qualifier, columns) = match factor {
+        TableFactor::NestedJoin(table) => return collect_relations(table, schema, relations),
+        TableFactor::Table { name, alias, .. } => {
+            let columns = match alias {
+                Some(alias) if !alias.columns.is_empty() => alias.columns.clone(),
+                _ => schema_table(schema, name).cloned().ok_or_else(|| {
+                    SqlGenError::RewriteError(format!(
+                        "Table {} not found in schema",
+                        object_name_key(name)
+                    ))
+                })?,
+            };
+            let qualifier = match alias {
+                Some(alias) => ObjectName(vec![alias.name.clone()]),
+                None => name.clone(),
+            };
+            (qualifier, columns)
+        }
+        TableFactor::Derived {
+            subquery,
+            alias: Some(alias),
+            ..
+        } => {
+            let columns = if alias.columns.is_empty() {
+                subquery.output_columns(schema)?
+            } else {
+                alias.columns.clone()
+            };
+            (ObjectName(vec![alias.name.clone()]), columns)
+        }
+        TableFactor::TableFunction {
+            alias: Some(alias), ..
+        }
+        | TableFactor::UNNEST {
+            alias: Some(alias), ..
+        } if !alias.columns.is_empty() => {
+            (ObjectName(vec![alias.name.clone()]), alias.columns.clone())
+        }
+        _ => {
+            return Err(SqlGenError::RewriteError(
+                "Cannot expand a wildcard over a table without known columns".to_string(),
+            ))
+        }
+    };
+    let index = relations.len();
+    let wildcard_columns = columns
+        .iter()
+        .map(|column| (Some(index), column.clone()))
+        .collect();
+    relations.push(Relation { qualifier, columns });
+    Ok(wildcard_columns)
+}
+
+/// The columns of the table `name` in `schema`, preferring a key of the same
+/// case and otherwise comparing the name ignoring case, as for unquoted
+/// identifiers in most engines
+fn schema_table<'a>(schema: &'a Schema, name: &ObjectName) -> Option<&'a Vec<Ident>> {
+    let key = object_name_key(name);
+    schema.get(&key).or_else(|| {
+        schema
+            .iter()
+            .filter(|(table, _)| table.eq_ignore_ascii_case(&key))
+            .min_by_key(|(table, _)| table.as_str())
+            .map(|(_, columns)| columns)
+    })
+}
+
+/// The key of a table in a `Schema`: its name parts joined with `.`
+fn object_name_key(name: &ObjectName) -> String {
+    name.0
+        .iter()
+        .map(|ident| ident.value.as_str())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// The name of the column produced by a projection item, if it has one
+fn select_item_name(item: &SelectItem) -> Option<&Ident> {
+    match item {
+        SelectItem::ExprWithAlias { alias, .. } => Some(alias),
+        SelectItem::UnnamedExpr(Expr::Identifier(ident)) => Some(ident),
+        SelectItem::UnnamedExpr(Expr::CompoundIdentifier(parts)) => parts.last(),
+        _ => None,
+    }
+}
+
+fn set_expr_output_columns(body: &SetExpr) -> Result<Vec<Ident>, SqlGenError> {
+    match body {
+        SetExpr::Select(select) => select
+            .projection
+            .iter()
+            .map(|item| {
+                select_item_name(item).cloned().ok_or_else(|| {
+                    SqlGenError::RewriteError(
+                        "Cannot determine the name of an unaliased output column".to_string(),
+                    )
+                })
+            })
+            .collect(),
+        SetExpr::Query(query) => set_expr_output_columns(&query.body),
+        // The left-hand side of a set operation names its columns
+        SetExpr::SetOperation { left, .. } => set_expr_output_columns(left),
+        SetExpr::Values(_) => Err(SqlGenError::RewriteError(
+            "Cannot determine the names of VALUES columns".to_string(),
+        )),
+    }
+}
+
+fn pin_set_expr(body: &mut SetExpr, ordered_columns: &[Ident]) -> Result<(), SqlGenError> {
+    match body {
+        SetExpr::Select(select) => {
+            let mut projection = Vec::with_capacity(ordered_columns.len());
+            for column in ordered_columns {
+                let mut matches = select.projection.iter().filter(|item| {
+                    select_item_name(item).map(|name| &name.value) == Some(&column.value)
+                });
+                let item = matches.next().ok_or_else(|| {
+                    SqlGenError::RewriteError(format!("Column {} is not available", column.value))
+                })?;
+                if matches.next().is_some() {
+                    return Err(ambiguous_column(column));
+                }
+                projection.push(item.clone());
+            }
+            select.projection = projection;
+            Ok(())
+        }
+        SetExpr::Query(query) => pin_set_expr(&mut query.body, ordered_columns),
+        SetExpr::SetOperation { left, right, .. } => {
+            // Set operations match columns by position, so each side is pinned
+            // by its own column names
+            let left_columns = set_expr_output_columns(left)?;
+            let positions = ordered_columns
+                .iter()
+                .map(|column| {
+                    let mut positions = left_columns
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, name)| name.value == column.value)
+                        .map(|(i, _)| i);
+                    let position = positions.next().ok_or_else(|| {
+                        SqlGenError::RewriteError(format!(
+                            "Column {} is not available",
+                            column.value
+                        ))
+                    })?;
+                    if positions.next().is_some() {
+                        return Err(ambiguous_column(column));
+                    }
+                    Ok(position)
+                })
+                .collect::<Result<Vec<_>, SqlGenError>>()?;
+            pin_set_expr(left, ordered_columns)?;
+            let right_columns = set_expr_output_columns(right)?;
+            let right_ordered = positions
+                .iter()
+                .map(|i| {
+                    right_columns.get(*i).cloned().ok_or_else(|| {
+                        SqlGenError::RewriteError(
+                            "Set operation sides have different numbers of columns".to_string(),
+                        )
+                    })
+                })
+                .collect::<Result<Vec<_>, SqlGenError>>()?;
+            pin_set_expr(right, &right_ordered)
+        }
+        SetExpr::Values(_) => Err(SqlGenError::RewriteError(
+            "Cannot pin the output columns of VALUES".to_string(),
+        )),
+    }
+}
+
+fn ambiguous_column(column: &Ident) -> SqlGenError {
+    SqlGenError::RewriteError(format!("Column {} is ambiguous", column.value))
+}
+
+/// Append the branches of nested unions with the same `ALL` flag to
+/// `branches`, left to right
+fn collect_union_branches(body: SetExpr, all: bool, branches: &mut Vec<SetExpr>) {
+    match body {
+        SetExpr::SetOperation {
+            op: SetOperator::Union,
+            all: branch_all,
+            left,
+            right,
+        } if branch_all == all => {
+            collect_union_branches(*left, all, branches);
+            collect_union_branches(*right, all, branches);
+        }
+        body => branches.push(body),
+    }
+}
+
+/// `SELECT * FROM (subquery) AS alias`
+fn select_all_from_derived(subquery: Query, alias: &str) -> Query {
+    Query {
+        with: None,
+        body: Box::new(SetExpr::Select(Box::new(Select {
+            hints: vec![],
+            distinct: false,
+            top: None,
+            projection: vec![SelectItem::Wildcard(WildcardOptions::default())],
+            into: None,
+            from: vec![TableWithJoins {
+                relation: TableFactor::Derived {
+                    lateral: false,
+                    subquery: Box::new(subquery),
+                    alias: Some(TableAlias {
+                        name: Ident::new(alias),
+                        columns: vec![],
+                    }),
+                },
+                joins: vec![],
+            }],
+            lateral_views: vec![],
+            selection: None,
+            group_by: vec![],
+            having: None,
+            qualify: None,
+        }))),
+        order_by: vec![],
+        limit_by: None,
+        limit: None,
+        offset: None,
+        fetch: None,
+        lock: None,
+        settings: vec![],
+    }
+}
+
+/// `SELECT * FROM name`
+fn select_all_from(name: &Ident) -> SetExpr {
+    SetExpr::Select(Box::new(Select {
+        hints: vec![],
+        distinct: false,
+        top: None,
+        projection: vec![SelectItem::Wildcard(WildcardOptions::default())],
+        into: None,
+        from: vec![TableWithJoins {
+            relation: TableFactor::Table {
+                name: ObjectName(vec![name.clone()]),
+                alias: None,
+                args: None,
+                with_hints: vec![],
+                sample: None,
+            },
+            joins: vec![],
+        }],
+        lateral_views: vec![],
+        selection: None,
+        group_by: vec![],
+        having: None,
+        qualify: None,
+    }))
+}
+
+/// Collects the names of the CTEs and tables referenced by a query, to pick
+/// names that don't clash with them
+#[derive(Default)]
+struct TableNames(Vec<String>);
+
+impl TableNames {
+    fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|used| used.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Visitor for TableNames {
+    fn visit_query(&mut self, query: &Query) -> Result<(), SqlGenError> {
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.0.push(cte.alias.name.value.clone());
+            }
+        }
+        visit::walk_query(self, query)
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &TableFactor) -> Result<(), SqlGenError> {
+        if let TableFactor::Table { name, .. } = table_factor {
+            self.0
+                .extend(name.0.last().map(|ident| ident.value.clone()));
+        }
+        visit::walk_table_factor(self, table_factor)
+    }
+}
+
+/// Replaces literal values by numbered placeholders, collecting the values
+struct Parameterize {
+    placeholder_style: PlaceholderStyle,
+    parameterize_nulls: bool,
+    values: Vec<Value>,
+}
+
+/// Whether the expression is a position in the select list, as in `GROUP BY 1`
+fn is_position(expr: &Expr) -> bool {
+    matches!(expr, Expr::Value(Value::Number(_, _)))
+}
+
+/// Whether the argument at `index` of a call to the function named `name`
+/// (lowercase) is a unit, field or other value the dialects require to be a
+/// literal
+fn is_literal_arg(name: &str, index: usize) -> bool {
+    match name {
+        "date_trunc" | "datetrunc" | "date_part" | "datepart" | "dateadd" | "datediff"
+        | "datediff_big" | "timestampadd" | "timestampdiff" => index == 0,
+        "time_slice" => index == 1 || index == 2,
+        "string_agg" | "listagg" => index == 1,
+        "regexp_replace" => index == 3,
+        "named_struct" => index.is_multiple_of(2),
+        _ => false,
+    }
+}
+
+impl VisitorMut for Parameterize {
+    fn visit_query(&mut self, query: &mut Query) -> Result<(), SqlGenError> {
+        // Leave the quantities of LIMIT BY, LIMIT, OFFSET and FETCH, visiting
+        // the expressions of LIMIT BY after the rest of the query
+        let limit_by = query.limit_by.take();
+        let limit = query.limit.take();
+        let offset = query.offset.take();
+        let fetch = query.fetch.take();
+        walk_query(self, query)?;
+        query.limit_by = limit_by;
+        query.limit = limit;
+        query.offset = offset;
+        query.fetch = fetch;
+        if let Some(limit_by) = &mut query.limit_by {
+            for expr in &mut limit_by.by {
+                self.visit_expr(expr)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_select(&mut self, select: &mut Select) -> Result<(), SqlGenError> {
+        // Leave the quantity of TOP and positions in GROUP BY, visiting it,
+        // HAVING and QUALIFY after the rest of the select to number the
+        // placeholders in order
+        let top = select.top.take();
+        let group_by = core::mem::take(&mut select.group_by);
+        let having = select.having.take();
+        let qualify = select.qualify.take();
+        walk_select(self, select)?;
+        select.top = top;
+        select.group_by = group_by;
+        select.having = having;
+        select.qualify = qualify;
+        for expr in &mut select.group_by {
+            if !is_position(expr) {
+                self.visit_expr(expr)?;
+            }
+        }
+        if let Some(having) = &mut select.having {
+            self.visit_expr(having)?;
+        }
+        if let Some(qualify) = &mut select.qualify {
+            self.visit_expr(qualify)?;
+        }
+        Ok(())
+    }
+
+    fn visit_order_by(&mut self, order_by: &mut OrderByExpr) -> Result<(), SqlGenError> {
+        if is_position(&order_by.expr) {
+            return Ok(());
+        }
+        walk_order_by(self, order_by)
+    }
+
+    fn visit_function(&mut self, function: &mut Function) -> Result<(), SqlGenError> {
+        // Leave the literal arguments, walking the rest of the call without
+        // its arguments after them
+        let name = function
+            .name
+            .0
+            .last()
+            .map(|ident| ident.value.to_ascii_lowercase())
+            .unwrap_or_default();
+        let mut args = core::mem::take(&mut function.args);
+        for (index, arg) in args.iter_mut().enumerate() {
+            if !is_literal_arg(&name, index) {
+                walk_function_arg(self, arg)?;
+            }
+        }
+        walk_function(self, function)?;
+        function.args = args;
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) -> Result<(), SqlGenError> {
+        match expr {
+            Expr::Value(Value::Interval { .. } | Value::Placeholder(_)) => Ok(()),
+            Expr::Value(Value::Null) if !self.parameterize_nulls => Ok(()),
+            Expr::Value(value) => {
+                let placeholder =
+                    Value::Placeholder(self.placeholder_style.placeholder(self.values.len() + 1));
+                self.values.push(core::mem::replace(value, placeholder));
+                Ok(())
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+/// Clears the `ORDER BY` of the derived tables and set operation branches
+/// visited whose order doesn't affect the rows they produce
+struct StripInnerOrderBy;
+
+impl StripInnerOrderBy {
+    fn strip(query: &mut Query) {
+        let top = matches!(query.body.as_ref(), SetExpr::Select(select) if select.top.is_some());
+        if query.limit_by.is_none()
+            && query.limit.is_none()
+            && query.offset.is_none()
+            && query.fetch.is_none()
+            && !top
+        {
+            query.order_by.clear();
+        }
+    }
+
+    fn strip_branches(set_expr: &mut SetExpr) {
+        if let SetExpr::SetOperation { left, right, .. } = set_expr {
+            for branch in [left, right] {
+                match branch.as_mut() {
+                    SetExpr::Query(query) => Self::strip(query),
+                    branch => Self::strip_branches(branch),
+                }
+            }
+        }
+    }
+}
+
+impl VisitorMut for StripInnerOrderBy {
+    fn visit_query(&mut self, query: &mut Query) -> Result<(), SqlGenError> {
+        Self::strip_branches(&mut query.body);
+        walk_query(self, query)
+    }
+
+    fn visit_table_factor(&mut self, table_factor: &mut TableFactor) -> Result<(), SqlGenError> {
+        if let TableFactor::Derived { subquery, .. } = table_factor {
+            Self::strip(subquery);
+        }
+        walk_table_factor(self, table_factor)
+    }
+}
+
+/// Normalizes a query for `Query::canonical_sql`
+struct Canonicalize<'a> {
+    dialect: &'a Dialect,
+}
+
+/// Remove the parentheses around `expr` if the nested expression binds at least
+/// as tightly as `min_precedence`
+fn unnest(expr: &mut Expr, min_precedence: u8) {
+    if matches!(expr, Expr::Nested(inner) if inner.precedence() >= min_precedence) {
+        if let Expr::Nested(inner) = core::mem::replace(expr, Expr::Value(Value::Null)) {
+            *expr = *inner;
+        }
+    }
+}
+
+/// Whether `operand` is a parenthesized string concatenation under an arithmetic
+/// operator, or the other way around. Dialects disagree on how `||` ranks
+/// against arithmetic (PostgreSQL binds `+` tighter, most others bind `||` like
+/// `*`), so these parentheses are kept even where the parser would not need them.
+fn mixes_concat_and_arithmetic(op: &BinaryOperator, operand: &Expr) -> bool {
+    let is_arithmetic = |op: &BinaryOperator| {
+        matches!(
+            op,
+            BinaryOperator::Plus
+                | BinaryOperator::Minus
+                | BinaryOperator::Multiply
+                | BinaryOperator::Divide
+                | BinaryOperator::Modulo
+        )
+    };
+    match operand {
+        Expr::Nested(inner) => match inner.as_ref() {
+            Expr::BinaryOp { op: inner_op, .. } => {
+                (*op == BinaryOperator::StringConcat && is_arithmetic(inner_op))
+                    || (is_arithmetic(op) && *inner_op == BinaryOperator::StringConcat)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+impl<'a> VisitorMut for Canonicalize<'a> {
+    fn visit_query(&mut self, query: &mut Query) -> Result<(), SqlGenError> {
+        walk_query(self, query)?;
+        if let Some(limit_by) = &mut query.limit_by {
+            for expr in &mut limit_by.by {
+                unnest(expr, 0);
+            }
+        }
+        if let Some(limit) = &mut query.limit {
+            unnest(limit, 0);
+        }
+        if let Some(offset) = &mut query.offset {
+            unnest(&mut offset.value, 0);
+        }
+        Ok(())
+    }
+
+    fn visit_select(&mut self, select: &mut Select) -> Result<(), SqlGenError> {
+        walk_select(self, select)?;
+        for item in &mut select.projection {
+            if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } = item {
+                unnest(expr, 0);
+            }
+        }
+        for table in &mut select.from {
+            for join in &mut table.joins {
+                if let JoinOperator::Inner(JoinConstraint::On(expr))
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) = &mut join.join_operator
+                {
+                    unnest(expr, 0);
+                }
+            }
+        }
+        for expr in select
+            .selection
+            .iter_mut()
+            .chain(select.group_by.iter_mut())
+            .chain(select.having.iter_mut())
+            .chain(select.qualify.iter_mut())
+        {
+            unnest(expr, 0);
+        }
+        Ok(())
+    }
+
+    fn visit_function(&mut self, function: &mut Function) -> Result<(), SqlGenError> {
+        walk_function(self, function)?;
+        if let [ident] = function.name.0.as_mut_slice() {
+            if ident.quote_style.is_none() {
+                ident.value = ident.value.to_ascii_lowercase();
+            }
+        }
+        for arg in &mut function.args {
+            if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr))
+            | FunctionArg::Named {
+                arg: FunctionArgExpr::Expr(expr),
+                ..
+            } = arg
+            {
+                unnest(expr, 0);
+            }
+        }
+        if let Some(filter) = &mut function.filter {
+            unnest(filter, 0);
+        }
+        if let Some(over) = &mut function.over {
+            for expr in &mut over.partition_by {
+                unnest(expr, 0);
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_order_by(&mut self, order_by: &mut OrderByExpr) -> Result<(), SqlGenError> {
+        walk_order_by(self, order_by)?;
+        unnest(&mut order_by.expr, 0);
+        let asc = order_by.asc.unwrap_or(true);
+        order_by.asc = Some(asc);
+        if order_by.nulls_first.is_none() && self.dialect.supports_nulls_ordering {
+            order_by.nulls_first = Some(asc == self.dialect.nulls_first_by_default);
+        }
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &mut Expr) -> Result<(), SqlGenError> {
+        walk_expr(self, expr)?;
+        let precedence = expr.precedence();
+        match expr {
+            // Operands on the left bind to an operator of equal precedence first
+            Expr::BinaryOp { left, op, right } => {
+                if !mixes_concat_and_arithmetic(op, left) {
+                    unnest(left, precedence);
+                }
+                if !mixes_concat_and_arithmetic(op, right) {
+                    unnest(right, precedence + 1);
+                }
+            }
+            Expr::UnaryOp {
+                op: UnaryOperator::PGPostfixFactorial,
+                expr,
+            } => unnest(expr, precedence),
+            Expr::UnaryOp { expr, .. } => unnest(expr, precedence + 1),
+            Expr::IsFalse(expr)
+            | Expr::IsTrue(expr)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::IsDistinctFrom(expr, _)
+            | Expr::IsNotDistinctFrom(expr, _)
+            | Expr::InSubquery { expr, .. }
+            | Expr::InUnnest { expr, .. }
+            | Expr::AtTimeZone {
+                timestamp: expr, ..
+            } => unnest(expr, precedence),
+            Expr::InList { expr, list, .. } => {
+                unnest(expr, precedence);
+                for item in list {
+                    unnest(item, 0);
+                }
+            }
+            Expr::LikeAny { expr, patterns, .. } => {
+                unnest(expr, precedence);
+                for pattern in patterns {
+                    unnest(pattern, 0);
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                unnest(expr, precedence);
+                unnest(low, precedence + 1);
+                unnest(high, precedence + 1);
+            }
+            // Expressions delimited by parentheses or keywords on both sides
+            Expr::Commented(commented) => unnest(&mut commented.node, precedence),
+            Expr::Nested(expr)
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. }
+            | Expr::Extract { expr, .. } => unnest(expr, 0),
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                for expr in operand
+                    .iter_mut()
+                    .map(|expr| expr.as_mut())
+                    .chain(conditions.iter_mut())
+                    .chain(results.iter_mut())
+                    .chain(else_result.iter_mut().map(|expr| expr.as_mut()))
+                {
+                    unnest(expr, 0);
+                }
+            }
+            Expr::Tuple(exprs) => {
+                for expr in exprs {
+                    unnest(expr, 0);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
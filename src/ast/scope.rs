@@ -0,0 +1,192 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracking of the tables in scope of nested queries, to find subqueries that
+//! reference the columns of an enclosing query
+//!
+//! Columns are resolved by their qualifier only, so a subquery referencing an
+//! outer column without a table name or alias is not detected. Qualifiers are
+//! compared ignoring case unless a dialect is given, whose [IdentifierCase]
+//! then decides.
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+use crate::ast::visit::{walk_expr, walk_select, Visitor};
+use crate::ast::*;
+use crate::dialect::{Dialect, IdentifierCase};
+
+impl Query {
+    /// Find the subqueries in expressions, at any depth, that reference the
+    /// columns of an enclosing query by table name or alias
+    pub fn correlated_subqueries(&self) -> Result<Vec<Query>, SqlGenError> {
+        let mut finder = CorrelatedSubqueries::new(IdentifierCase::Insensitive);
+        finder.visit_query(self)?;
+        Ok(finder.found)
+    }
+
+    /// Like [Query::correlated_subqueries], comparing table names and aliases
+    /// by the dialect's [IdentifierCase]
+    pub fn correlated_subqueries_for(&self, dialect: &Dialect) -> Result<Vec<Query>, SqlGenError> {
+        let mut finder = CorrelatedSubqueries::new(dialect.identifier_case);
+        finder.visit_query(self)?;
+        Ok(finder.found)
+    }
+}
+
+/// Find the subqueries in `expr` that reference the tables in `from`
+pub(crate) fn correlated_subqueries_in(
+    expr: &Expr,
+    from: &[TableWithJoins],
+    dialect: &Dialect,
+) -> Result<Vec<Query>, SqlGenError> {
+    let mut finder = CorrelatedSubqueries::new(dialect.identifier_case);
+    finder.scopes.push(qualifiers(from));
+    finder.visit_expr(expr)?;
+    Ok(finder.found)
+}
+
+/// The names that qualify the columns of the tables in a `FROM` clause: the
+/// alias of each table, or the last part of the table's name
+pub(crate) fn qualifiers(from: &[TableWithJoins]) -> Vec<Ident> {
+    let mut qualifiers = vec![];
+    for table in from {
+        add_table_qualifiers(&mut qualifiers, table);
+    }
+    qualifiers
+}
+
+/// Add the names that qualify the columns of `relation`, and of the tables
+/// joined in it
+pub(crate) fn add_qualifiers(qualifiers: &mut Vec<Ident>, relation: &TableFactor) {
+    match relation {
+        TableFactor::Table {
+            alias: Some(alias), ..
+        }
+        | TableFactor::Derived {
+            alias: Some(alias), ..
+        }
+        | TableFactor::TableFunction {
+            alias: Some(alias), ..
+        }
+        | TableFactor::UNNEST {
+            alias: Some(alias), ..
+        } => qualifiers.push(alias.name.clone()),
+        TableFactor::Table { name, .. } => qualifiers.extend(name.0.last().cloned()),
+        TableFactor::NestedJoin(table) => add_table_qualifiers(qualifiers, table),
+        _ => {}
+    }
+}
+
+fn add_table_qualifiers(qualifiers: &mut Vec<Ident>, table: &TableWithJoins) {
+    add_qualifiers(qualifiers, &table.relation);
+    for join in &table.joins {
+        add_qualifiers(qualifiers, &join.relation);
+    }
+}
+
+/// Whether two identifiers name the same table or column when compared by
+/// `case`
+pub(crate) fn same_ident(a: &Ident, b: &Ident, case: IdentifierCase) -> bool {
+    let folded = |ident: &Ident| match (case, ident.quote_style) {
+        (IdentifierCase::FoldLower, None) => ident.value.to_ascii_lowercase(),
+        (IdentifierCase::FoldUpper, None) => ident.value.to_ascii_uppercase(),
+        _ => ident.value.clone(),
+    };
+    match case {
+        IdentifierCase::Sensitive => a.value == b.value,
+        IdentifierCase::Insensitive => a.value.eq_ignore_ascii_case(&b.value),
+        IdentifierCase::FoldLower | IdentifierCase::FoldUpper => folded(a) == folded(b),
+    }
+}
+
+fn in_scope(scopes: &[Vec<Ident>], qualifier: &Ident, case: IdentifierCase) -> bool {
+    scopes
+        .iter()
+        .flatten()
+        .any(|name| same_ident(name, qualifier, case))
+}
+
+/// Collects the qualifiers of column references that aren't resolved by the
+/// tables of the queries being visited
+struct OuterReferences {
+    case: IdentifierCase,
+    scopes: Vec<Vec<Ident>>,
+    references: Vec<Ident>,
+}
+
+impl Visitor for OuterReferences {
+    fn visit_select(&mut self, select: &Select) -> Result<(), SqlGenError> {
+        self.scopes.push(qualifiers(&select.from));
+        walk_select(self, select)?;
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) -> Result<(), SqlGenError> {
+        if let Expr::CompoundIdentifier(parts) = expr {
+            if let [.., qualifier, _] = parts.as_slice() {
+                if !in_scope(&self.scopes, qualifier, self.case) {
+                    self.references.push(qualifier.clone());
+                }
+            }
+        }
+        walk_expr(self, expr)
+    }
+}
+
+struct CorrelatedSubqueries {
+    case: IdentifierCase,
+    scopes: Vec<Vec<Ident>>,
+    found: Vec<Query>,
+}
+
+impl CorrelatedSubqueries {
+    fn new(case: IdentifierCase) -> Self {
+        CorrelatedSubqueries {
+            case,
+            scopes: vec![],
+            found: vec![],
+        }
+    }
+}
+
+impl Visitor for CorrelatedSubqueries {
+    fn visit_select(&mut self, select: &Select) -> Result<(), SqlGenError> {
+        self.scopes.push(qualifiers(&select.from));
+        walk_select(self, select)?;
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) -> Result<(), SqlGenError> {
+        if let Expr::Subquery(subquery)
+        | Expr::Exists { subquery, .. }
+        | Expr::InSubquery { subquery, .. } = expr
+        {
+            let mut outer = OuterReferences {
+                case: self.case,
+                scopes: vec![],
+                references: vec![],
+            };
+            outer.visit_query(subquery)?;
+            if outer
+                .references
+                .iter()
+                .any(|qualifier| in_scope(&self.scopes, qualifier, self.case))
+            {
+                self.found.push(subquery.as_ref().clone());
+            }
+        }
+        walk_expr(self, expr)
+    }
+}
@@ -0,0 +1,129 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A statement of any kind, and batches of statements
+
+#[cfg(not(feature = "std"))]
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ast::*;
+use crate::dialect::{Dialect, DialectDisplay, TransactionStyle};
+
+/// A top-level SQL statement
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Statement {
+    Query(Box<Query>),
+    Insert(Insert),
+    CreateTableAs(CreateTableAs),
+    CreateView(CreateView),
+    Explain(Explain),
+}
+
+impl From<Query> for Statement {
+    fn from(query: Query) -> Self {
+        Statement::Query(Box::new(query))
+    }
+}
+
+impl From<Insert> for Statement {
+    fn from(insert: Insert) -> Self {
+        Statement::Insert(insert)
+    }
+}
+
+impl From<CreateTableAs> for Statement {
+    fn from(create: CreateTableAs) -> Self {
+        Statement::CreateTableAs(create)
+    }
+}
+
+impl From<CreateView> for Statement {
+    fn from(create: CreateView) -> Self {
+        Statement::CreateView(create)
+    }
+}
+
+impl From<Explain> for Statement {
+    fn from(explain: Explain) -> Self {
+        Statement::Explain(explain)
+    }
+}
+
+impl DialectDisplay for Statement {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
+        match self {
+            Statement::Query(query) => query.fmt(f, dialect),
+            Statement::Insert(insert) => insert.fmt(f, dialect),
+            Statement::CreateTableAs(create) => create.fmt(f, dialect),
+            Statement::CreateView(create) => create.fmt(f, dialect),
+            Statement::Explain(explain) => explain.fmt(f, dialect),
+        }
+    }
+}
+
+impl Statement {
+    /// Render the statement terminated by a semicolon
+    pub fn to_statement_sql(&self, dialect: &Dialect) -> Result<String, SqlGenError> {
+        Ok(format!("{};", self.sql(dialect)?))
+    }
+
+    /// Render a batch of statements, each terminated by a semicolon and on its
+    /// own line
+    pub fn batch_sql(statements: &[Statement], dialect: &Dialect) -> Result<String, SqlGenError> {
+        let mut batch = display_separated(statements, ";\n").sql(dialect)?;
+        if !statements.is_empty() {
+            batch.push(';');
+        }
+        Ok(batch)
+    }
+}
+
+/// Render a script of statements, each terminated by a semicolon and on its
+/// own line. When `transactional` is `true`, the statements are wrapped in
+/// the dialect's statements beginning and committing a transaction.
+pub fn render_script(
+    statements: &[Statement],
+    dialect: &Dialect,
+    transactional: bool,
+) -> Result<String, SqlGenError> {
+    let batch = Statement::batch_sql(statements, dialect)?;
+    if !transactional {
+        return Ok(batch);
+    }
+    let (begin, commit) = match dialect.transaction_style {
+        TransactionStyle::Begin => (Some("BEGIN;"), "COMMIT;"),
+        TransactionStyle::StartTransaction => (Some("START TRANSACTION;"), "COMMIT;"),
+        TransactionStyle::BeginTransaction => (Some("BEGIN TRANSACTION;"), "COMMIT TRANSACTION;"),
+        TransactionStyle::Implicit => (None, "COMMIT;"),
+        TransactionStyle::Unsupported => return Err(dialect.unsupported("Transaction")),
+    };
+    let mut script = String::new();
+    if let Some(begin) = begin {
+        script.push_str(begin);
+        script.push('\n');
+    }
+    if !batch.is_empty() {
+        script.push_str(&batch);
+        script.push('\n');
+    }
+    script.push_str(commit);
+    Ok(script)
+}
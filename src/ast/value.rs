@@ -16,7 +16,7 @@ use alloc::boxed::Box;
 use alloc::string::String;
 use core::fmt;
 
-use crate::dialect::{Dialect, DialectDisplay};
+use crate::dialect::{BooleanLiteralStyle, Dialect, DialectDisplay};
 use crate::parser::SqlGenError;
 #[cfg(feature = "bigdecimal")]
 use bigdecimal::BigDecimal;
@@ -41,6 +41,9 @@ pub enum Value {
     EscapedStringLiteral(String),
     /// N'string value'
     NationalStringLiteral(String),
+    /// U&'string value', rendered with the characters that aren't printable
+    /// ASCII written as unicode escapes, as in `U&'caf\00E9'`
+    UnicodeStringLiteral(String),
     /// X'hex value'
     HexStringLiteral(String),
 
@@ -73,7 +76,7 @@ pub enum Value {
 }
 
 impl DialectDisplay for Value {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(match self {
             Value::Number(v, l) => write!(f, "{}{long}", v, long = if *l { "L" } else { "" }),
             Value::DoubleQuotedString(v) => write!(f, "\"{}\"", v),
@@ -84,7 +87,16 @@ impl DialectDisplay for Value {
                 write!(f, "E'{}'", escape_escaped_string(v).sql(dialect)?)
             }
             Value::NationalStringLiteral(v) => write!(f, "N'{}'", v),
+            Value::UnicodeStringLiteral(v) => {
+                if !dialect.supports_unicode_strings {
+                    return Err(dialect.unsupported("Unicode escape strings"));
+                }
+                write!(f, "U&'{}'", escape_unicode_string(v).sql(dialect)?)
+            }
             Value::HexStringLiteral(v) => write!(f, "X'{}'", v),
+            Value::Boolean(v) if dialect.boolean_literal_style == BooleanLiteralStyle::Integer => {
+                write!(f, "{}", if *v { 1 } else { 0 })
+            }
             Value::Boolean(v) => write!(f, "{}", v),
             Value::Interval {
                 value,
@@ -159,8 +171,40 @@ pub enum DateTimeField {
     TimezoneMinute,
 }
 
+impl DateTimeField {
+    /// The field with the given name, as it's written in `EXTRACT` or as the
+    /// first argument of `date_part`, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_uppercase().as_str() {
+            "YEAR" => DateTimeField::Year,
+            "MONTH" => DateTimeField::Month,
+            "WEEK" => DateTimeField::Week,
+            "DAY" => DateTimeField::Day,
+            "HOUR" => DateTimeField::Hour,
+            "MINUTE" => DateTimeField::Minute,
+            "SECOND" => DateTimeField::Second,
+            "CENTURY" => DateTimeField::Century,
+            "DECADE" => DateTimeField::Decade,
+            "DOW" => DateTimeField::Dow,
+            "DOY" => DateTimeField::Doy,
+            "EPOCH" => DateTimeField::Epoch,
+            "ISODOW" => DateTimeField::Isodow,
+            "ISOYEAR" => DateTimeField::Isoyear,
+            "JULIAN" => DateTimeField::Julian,
+            "MICROSECONDS" => DateTimeField::Microseconds,
+            "MILLENIUM" => DateTimeField::Millenium,
+            "MILLISECONDS" => DateTimeField::Milliseconds,
+            "QUARTER" => DateTimeField::Quarter,
+            "TIMEZONE" => DateTimeField::Timezone,
+            "TIMEZONE_HOUR" => DateTimeField::TimezoneHour,
+            "TIMEZONE_MINUTE" => DateTimeField::TimezoneMinute,
+            _ => return None,
+        })
+    }
+}
+
 impl DialectDisplay for DateTimeField {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         Ok(f.write_str(match self {
             DateTimeField::Year => "YEAR",
             DateTimeField::Month => "MONTH",
@@ -194,7 +238,7 @@ pub struct EscapeQuotedString<'a> {
 }
 
 impl<'a> DialectDisplay for EscapeQuotedString<'a> {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         for c in self.string.chars() {
             if c == self.quote {
                 write!(f, "{q}{q}", q = self.quote)?;
@@ -217,7 +261,7 @@ pub fn escape_single_quote_string(s: &str) -> EscapeQuotedString<'_> {
 pub struct EscapeEscapedStringLiteral<'a>(&'a str);
 
 impl<'a> DialectDisplay for EscapeEscapedStringLiteral<'a> {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         for c in self.0.chars() {
             match c {
                 '\'' => {
@@ -235,6 +279,22 @@ impl<'a> DialectDisplay for EscapeEscapedStringLiteral<'a> {
                 '\r' => {
                     write!(f, r#"\r"#)?;
                 }
+                '\u{8}' => {
+                    write!(f, r#"\b"#)?;
+                }
+                '\u{c}' => {
+                    write!(f, r#"\f"#)?;
+                }
+                // PostgreSQL rejects NUL in strings, even escaped
+                '\0' => {
+                    return Err(SqlGenError::DialectError(
+                        "String literals can't contain NUL characters".to_string(),
+                    ));
+                }
+                // Other control characters are never written raw
+                c if c.is_control() => {
+                    write!(f, r#"\u{:04X}"#, c as u32)?;
+                }
                 _ => {
                     write!(f, "{}", c)?;
                 }
@@ -248,6 +308,39 @@ pub fn escape_escaped_string(s: &str) -> EscapeEscapedStringLiteral<'_> {
     EscapeEscapedStringLiteral(s)
 }
 
+pub struct EscapeUnicodeStringLiteral<'a>(&'a str);
+
+impl<'a> DialectDisplay for EscapeUnicodeStringLiteral<'a> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
+        for c in self.0.chars() {
+            match c {
+                '\'' => {
+                    write!(f, "''")?;
+                }
+                '\\' => {
+                    write!(f, r#"\\"#)?;
+                }
+                ' '..='~' => {
+                    write!(f, "{}", c)?;
+                }
+                // Code points beyond the basic multilingual plane take six
+                // digits after `\+`
+                c if (c as u32) > 0xFFFF => {
+                    write!(f, r#"\+{:06X}"#, c as u32)?;
+                }
+                _ => {
+                    write!(f, r#"\{:04X}"#, c as u32)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn escape_unicode_string(s: &str) -> EscapeUnicodeStringLiteral<'_> {
+    EscapeUnicodeStringLiteral(s)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrimWhereField {
@@ -257,7 +350,7 @@ pub enum TrimWhereField {
 }
 
 impl DialectDisplay for TrimWhereField {
-    fn fmt(&self, f: &mut (dyn fmt::Write), _dialect: &Dialect) -> Result<(), SqlGenError> {
+    fn fmt(&self, f: &mut dyn fmt::Write, _dialect: &Dialect) -> Result<(), SqlGenError> {
         use TrimWhereField::*;
         Ok(f.write_str(match self {
             Both => "BOTH",
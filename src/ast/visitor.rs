@@ -0,0 +1,437 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Traversal of query ASTs
+//!
+//! [`Visitor`](visit::Visitor) and [`VisitorMut`](visit_mut::VisitorMut) have
+//! a method per node type whose default
+//! implementation visits the node's children through the matching `walk_*`
+//! function. Implementations override the methods for the nodes they care
+//! about, calling the `walk_*` function to continue into the children.
+
+use crate::ast::*;
+
+macro_rules! make_visitor {
+    ($module:ident, $visitor:ident, $($mutability:ident)?) => {
+        pub mod $module {
+            use super::*;
+
+            pub trait $visitor {
+                fn visit_query(&mut self, query: &$($mutability)? Query) -> Result<(), SqlGenError> {
+                    walk_query(self, query)
+                }
+
+                fn visit_select(&mut self, select: &$($mutability)? Select) -> Result<(), SqlGenError> {
+                    walk_select(self, select)
+                }
+
+                fn visit_table_factor(
+                    &mut self,
+                    table_factor: &$($mutability)? TableFactor,
+                ) -> Result<(), SqlGenError> {
+                    walk_table_factor(self, table_factor)
+                }
+
+                fn visit_expr(&mut self, expr: &$($mutability)? Expr) -> Result<(), SqlGenError> {
+                    walk_expr(self, expr)
+                }
+
+                fn visit_function(&mut self, function: &$($mutability)? Function) -> Result<(), SqlGenError> {
+                    walk_function(self, function)
+                }
+
+                fn visit_order_by(&mut self, order_by: &$($mutability)? OrderByExpr) -> Result<(), SqlGenError> {
+                    walk_order_by(self, order_by)
+                }
+            }
+
+            pub fn walk_query<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                query: &$($mutability)? Query,
+            ) -> Result<(), SqlGenError> {
+                if let Some(with) = &$($mutability)? query.with {
+                    for cte in &$($mutability)? with.cte_tables {
+                        visitor.visit_query(&$($mutability)? cte.query)?;
+                    }
+                }
+                walk_set_expr(visitor, &$($mutability)? query.body)?;
+                for order_by in &$($mutability)? query.order_by {
+                    visitor.visit_order_by(order_by)?;
+                }
+                if let Some(limit_by) = &$($mutability)? query.limit_by {
+                    visitor.visit_expr(&$($mutability)? limit_by.limit)?;
+                    for expr in &$($mutability)? limit_by.by {
+                        visitor.visit_expr(expr)?;
+                    }
+                }
+                if let Some(limit) = &$($mutability)? query.limit {
+                    visitor.visit_expr(limit)?;
+                }
+                if let Some(offset) = &$($mutability)? query.offset {
+                    visitor.visit_expr(&$($mutability)? offset.value)?;
+                }
+                if let Some(Fetch {
+                    quantity: Some(quantity),
+                    ..
+                }) = &$($mutability)? query.fetch
+                {
+                    visitor.visit_expr(quantity)?;
+                }
+                Ok(())
+            }
+
+            pub fn walk_set_expr<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                set_expr: &$($mutability)? SetExpr,
+            ) -> Result<(), SqlGenError> {
+                match set_expr {
+                    SetExpr::Select(select) => visitor.visit_select(select),
+                    SetExpr::Query(query) => visitor.visit_query(query),
+                    SetExpr::SetOperation { left, right, .. } => {
+                        walk_set_expr(visitor, left)?;
+                        walk_set_expr(visitor, right)
+                    }
+                    SetExpr::Values(values) => {
+                        for row in &$($mutability)? values.0 {
+                            for expr in row {
+                                visitor.visit_expr(expr)?;
+                            }
+                        }
+                        Ok(())
+                    }
+                }
+            }
+
+            pub fn walk_select<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                select: &$($mutability)? Select,
+            ) -> Result<(), SqlGenError> {
+                if let Some(Top {
+                    quantity: Some(quantity),
+                    ..
+                }) = &$($mutability)? select.top
+                {
+                    visitor.visit_expr(quantity)?;
+                }
+                for item in &$($mutability)? select.projection {
+                    match item {
+                        SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } => {
+                            visitor.visit_expr(expr)?
+                        }
+                        SelectItem::QualifiedWildcard(..) | SelectItem::Wildcard(_) => {}
+                    }
+                }
+                for table in &$($mutability)? select.from {
+                    walk_table_with_joins(visitor, table)?;
+                }
+                for lateral_view in &$($mutability)? select.lateral_views {
+                    visitor.visit_expr(&$($mutability)? lateral_view.lateral_view)?;
+                }
+                if let Some(selection) = &$($mutability)? select.selection {
+                    visitor.visit_expr(selection)?;
+                }
+                for expr in &$($mutability)? select.group_by {
+                    visitor.visit_expr(expr)?;
+                }
+                if let Some(having) = &$($mutability)? select.having {
+                    visitor.visit_expr(having)?;
+                }
+                if let Some(qualify) = &$($mutability)? select.qualify {
+                    visitor.visit_expr(qualify)?;
+                }
+                Ok(())
+            }
+
+            pub fn walk_table_with_joins<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                table: &$($mutability)? TableWithJoins,
+            ) -> Result<(), SqlGenError> {
+                visitor.visit_table_factor(&$($mutability)? table.relation)?;
+                for join in &$($mutability)? table.joins {
+                    visitor.visit_table_factor(&$($mutability)? join.relation)?;
+                    match &$($mutability)? join.join_operator {
+                        JoinOperator::Inner(JoinConstraint::On(expr))
+                        | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                        | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                        | JoinOperator::FullOuter(JoinConstraint::On(expr)) => {
+                            visitor.visit_expr(expr)?
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(())
+            }
+
+            pub fn walk_table_factor<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                table_factor: &$($mutability)? TableFactor,
+            ) -> Result<(), SqlGenError> {
+                match table_factor {
+                    TableFactor::Table {
+                        args,
+                        with_hints,
+                        sample,
+                        ..
+                    } => {
+                        if let Some(args) = args {
+                            for arg in args {
+                                walk_function_arg(visitor, arg)?;
+                            }
+                        }
+                        for expr in with_hints {
+                            visitor.visit_expr(expr)?;
+                        }
+                        if let Some(sample) = sample {
+                            visitor.visit_expr(&$($mutability)? sample.percentage)?;
+                            if let Some(seed) = &$($mutability)? sample.repeatable {
+                                visitor.visit_expr(seed)?;
+                            }
+                        }
+                        Ok(())
+                    }
+                    TableFactor::Derived { subquery, .. } => visitor.visit_query(subquery),
+                    TableFactor::TableFunction { expr, .. } => visitor.visit_expr(expr),
+                    TableFactor::UNNEST { array_exprs, .. } => {
+                        for array_expr in array_exprs {
+                            visitor.visit_expr(array_expr)?;
+                        }
+                        Ok(())
+                    }
+                    TableFactor::NestedJoin(table) => walk_table_with_joins(visitor, table),
+                }
+            }
+
+            pub fn walk_function<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                function: &$($mutability)? Function,
+            ) -> Result<(), SqlGenError> {
+                for arg in &$($mutability)? function.args {
+                    walk_function_arg(visitor, arg)?;
+                }
+                for order_by in &$($mutability)? function.order_by {
+                    visitor.visit_order_by(order_by)?;
+                }
+                for order_by in &$($mutability)? function.within_group {
+                    visitor.visit_order_by(order_by)?;
+                }
+                if let Some(filter) = &$($mutability)? function.filter {
+                    visitor.visit_expr(filter)?;
+                }
+                if let Some(over) = &$($mutability)? function.over {
+                    for expr in &$($mutability)? over.partition_by {
+                        visitor.visit_expr(expr)?;
+                    }
+                    for order_by in &$($mutability)? over.order_by {
+                        visitor.visit_order_by(order_by)?;
+                    }
+                }
+                Ok(())
+            }
+
+            pub fn walk_function_arg<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                arg: &$($mutability)? FunctionArg,
+            ) -> Result<(), SqlGenError> {
+                match arg {
+                    FunctionArg::Named {
+                        arg: FunctionArgExpr::Expr(expr),
+                        ..
+                    }
+                    | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => visitor.visit_expr(expr),
+                    _ => Ok(()),
+                }
+            }
+
+            pub fn walk_order_by<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                order_by: &$($mutability)? OrderByExpr,
+            ) -> Result<(), SqlGenError> {
+                visitor.visit_expr(&$($mutability)? order_by.expr)
+            }
+
+            pub fn walk_expr<V: $visitor + ?Sized>(
+                visitor: &mut V,
+                expr: &$($mutability)? Expr,
+            ) -> Result<(), SqlGenError> {
+                match expr {
+                    Expr::Identifier(_)
+                    | Expr::CompoundIdentifier(_)
+                    | Expr::Value(_)
+                    | Expr::TypedString { .. } => Ok(()),
+                    Expr::CompositeAccess { expr, .. }
+                    | Expr::IsFalse(expr)
+                    | Expr::IsTrue(expr)
+                    | Expr::IsNull(expr)
+                    | Expr::IsNotNull(expr)
+                    | Expr::AnyOp(expr)
+                    | Expr::AllOp(expr)
+                    | Expr::UnaryOp { expr, .. }
+                    | Expr::Cast { expr, .. }
+                    | Expr::TryCast { expr, .. }
+                    | Expr::AtTimeZone {
+                        timestamp: expr, ..
+                    }
+                    | Expr::Extract { expr, .. }
+                    | Expr::Collate { expr, .. }
+                    | Expr::Nested(expr) => visitor.visit_expr(expr),
+                    Expr::IsDistinctFrom(left, right)
+                    | Expr::IsNotDistinctFrom(left, right)
+                    | Expr::BinaryOp { left, right, .. }
+                    | Expr::Position {
+                        expr: left,
+                        r#in: right,
+                    } => {
+                        visitor.visit_expr(left)?;
+                        visitor.visit_expr(right)
+                    }
+                    Expr::InList { expr, list, .. } => {
+                        visitor.visit_expr(expr)?;
+                        for item in list {
+                            visitor.visit_expr(item)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::Commented(commented) => visitor.visit_expr(&$($mutability)? commented.node),
+                    Expr::LikeAny { expr, patterns, .. } => {
+                        visitor.visit_expr(expr)?;
+                        for pattern in patterns {
+                            visitor.visit_expr(pattern)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::InSubquery { expr, subquery, .. } => {
+                        visitor.visit_expr(expr)?;
+                        visitor.visit_query(subquery)
+                    }
+                    Expr::InUnnest {
+                        expr, array_expr, ..
+                    } => {
+                        visitor.visit_expr(expr)?;
+                        visitor.visit_expr(array_expr)
+                    }
+                    Expr::Between {
+                        expr, low, high, ..
+                    } => {
+                        visitor.visit_expr(expr)?;
+                        visitor.visit_expr(low)?;
+                        visitor.visit_expr(high)
+                    }
+                    Expr::Substring {
+                        expr,
+                        substring_from,
+                        substring_for,
+                    } => {
+                        visitor.visit_expr(expr)?;
+                        if let Some(from) = substring_from {
+                            visitor.visit_expr(from)?;
+                        }
+                        if let Some(r#for) = substring_for {
+                            visitor.visit_expr(r#for)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::Trim { expr, trim_where } => {
+                        visitor.visit_expr(expr)?;
+                        if let Some((_, trim_what)) = trim_where {
+                            visitor.visit_expr(trim_what)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::MapAccess { column, keys } => {
+                        visitor.visit_expr(column)?;
+                        for key in keys {
+                            visitor.visit_expr(key)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::Function(function) => visitor.visit_function(function),
+                    Expr::Case {
+                        operand,
+                        conditions,
+                        results,
+                        else_result,
+                    } => {
+                        if let Some(operand) = operand {
+                            visitor.visit_expr(operand)?;
+                        }
+                        for expr in conditions {
+                            visitor.visit_expr(expr)?;
+                        }
+                        for expr in results {
+                            visitor.visit_expr(expr)?;
+                        }
+                        if let Some(else_result) = else_result {
+                            visitor.visit_expr(else_result)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::Exists { subquery, .. } | Expr::Subquery(subquery) => {
+                        visitor.visit_query(subquery)
+                    }
+                    Expr::ListAgg(ListAgg {
+                        expr,
+                        separator,
+                        on_overflow,
+                        within_group,
+                        ..
+                    }) => {
+                        visitor.visit_expr(expr)?;
+                        if let Some(separator) = separator {
+                            visitor.visit_expr(separator)?;
+                        }
+                        if let Some(ListAggOnOverflow::Truncate {
+                            filler: Some(filler),
+                            ..
+                        }) = on_overflow
+                        {
+                            visitor.visit_expr(filler)?;
+                        }
+                        for order_by in within_group {
+                            visitor.visit_order_by(order_by)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::GroupingSets(sets) | Expr::Cube(sets) | Expr::Rollup(sets) => {
+                        for set in sets {
+                            for expr in set {
+                                visitor.visit_expr(expr)?;
+                            }
+                        }
+                        Ok(())
+                    }
+                    Expr::Tuple(exprs) => {
+                        for expr in exprs {
+                            visitor.visit_expr(expr)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::ArrayIndex { obj, indexes } => {
+                        visitor.visit_expr(obj)?;
+                        for index in indexes {
+                            visitor.visit_expr(index)?;
+                        }
+                        Ok(())
+                    }
+                    Expr::Array(array) => {
+                        for expr in &$($mutability)? array.elem {
+                            visitor.visit_expr(expr)?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+        }
+    };
+}
+
+make_visitor!(visit, Visitor,);
+make_visitor!(visit_mut, VisitorMut, mut);
@@ -1,26 +1,1152 @@
+use crate::ast::{
+    interval_quantity, BinaryOperator, DataType, DateTimeField, Expr, Function, FunctionArg,
+    FunctionArgExpr, Ident, ObjectName, Value,
+};
 use crate::parser::SqlGenError;
 use core::fmt::Debug;
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::sync::Arc;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Dialect {
+    /// The name of the dialect, as reported by errors
+    pub name: &'static str,
     /// The starting quote if any. Valid quote characters are the single quote,
     /// double quote, backtick, and opening square bracket.
     pub quote_style: Option<char>,
     pub quote_functions: bool,
-    pub functions: HashSet<String>,
-    pub function_transforms: HashMap<String, Arc<dyn FunctionTransform>>,
+    pub functions: FunctionSet,
+    pub function_transforms: FunctionTransforms,
+    /// Dialect specific spellings of data types, keyed by the default spelling
+    /// (e.g. `CHARACTER VARYING` or `NUMERIC`). Types without an entry are
+    /// rendered with their default spelling and parameters.
+    pub data_types: HashMap<String, DataTypeName>,
+    /// How to render calls to functions that are not in `functions`
+    pub unknown_function_policy: UnknownFunctionPolicy,
+    /// Reject queries that render to SQL that is syntactically valid but can't
+    /// be right, such as aggregates called without arguments
+    pub strict: bool,
+    /// Whether aggregates accept `FILTER (WHERE ...)`. When `false`, filters are
+    /// rewritten into a `CASE WHEN` argument.
+    pub supports_aggregate_filter: bool,
+    /// Whether NULLs sort before other values in an ascending `ORDER BY` that
+    /// doesn't specify `NULLS FIRST` or `NULLS LAST`
+    pub nulls_first_by_default: bool,
+    /// Whether aggregates accept `ORDER BY` after their arguments, as in
+    /// `array_agg(x ORDER BY y)`
+    pub supports_aggregate_order_by: bool,
+    /// How to render `EXTRACT(EPOCH FROM ...)`
+    pub epoch_style: EpochStyle,
+    /// How to render `CAST(...)` to types without a cast function
+    pub cast_style: CastStyle,
+    /// How to render `TRY_CAST(...)`
+    pub try_cast_style: TryCastStyle,
+    /// Whether `ORDER BY` accepts `NULLS FIRST` and `NULLS LAST`
+    pub supports_nulls_ordering: bool,
+    /// How to render string concatenation, whether written as `a || b` or as
+    /// `concat(a, b)`
+    pub string_concat: StringConcatStyle,
+    /// How to render an interval multiplied by a number, as in
+    /// `INTERVAL '1 hour' * n`
+    pub interval_multiplication: IntervalMultiplicationStyle,
+    /// How to render adding an interval literal to a date or timestamp
+    pub date_add_style: DateAddStyle,
+    /// How to render extracting a field from a date, whether written as
+    /// `EXTRACT(YEAR FROM ts)` or as `date_part('year', ts)`
+    pub date_part_style: DatePartStyle,
+    /// Whether booleans coerce to integers in aggregates, so that `sum(x > 0)`
+    /// counts matching rows. When `false`, boolean arguments of `sum` and `avg`
+    /// are rewritten into `CASE WHEN x > 0 THEN 1 ELSE 0 END`.
+    pub supports_boolean_coercion: bool,
+    /// Whether `ILIKE` is supported. When `false`, `a ILIKE b` is rewritten
+    /// into `lower(a) LIKE lower(b)`.
+    pub supports_ilike: bool,
+    /// Whether `LIKE ANY` and `ILIKE ANY` accept a list of patterns, as in
+    /// `a ILIKE ANY ('x%', 'y%')`. When `false`, they are rewritten into
+    /// `(a ILIKE 'x%' OR a ILIKE 'y%')`.
+    pub supports_like_any: bool,
+    /// How to render the regular expression match operators `~`, `~*`, `!~`
+    /// and `!~*`
+    pub regex_match_style: RegexMatchStyle,
+    /// How to render array literals
+    pub array_style: ArrayStyle,
+    /// How to render indexing into an array. Indexes in the AST are 1-based.
+    pub array_index_style: ArrayIndexStyle,
+    /// How to render `UNNEST` in a `FROM` clause
+    pub unnest_style: UnnestStyle,
+    /// How to render `LATERAL` derived tables
+    pub lateral_style: LateralStyle,
+    /// Produces the text of identifiers in place of their value, e.g. to add a
+    /// namespace to every table. Function, argument and collation names are not
+    /// passed to the hook.
+    pub identifier_hook: Option<IdentifierHook>,
+    /// How to render struct literals, written as `named_struct('a', 1)`
+    pub struct_style: StructStyle,
+    /// Whether subqueries in the select list may reference the columns of the
+    /// enclosing query. When `false`, strict mode rejects those that reference
+    /// an enclosing table by name or alias.
+    pub supports_correlated_select_subqueries: bool,
+    /// Whether `BETWEEN SYMMETRIC` is supported
+    pub supports_between_symmetric: bool,
+    /// Whether window frames accept `EXCLUDE CURRENT ROW`, `EXCLUDE GROUP`,
+    /// `EXCLUDE TIES` and `EXCLUDE NO OTHERS`
+    pub supports_window_frame_exclude: bool,
+    /// Whether window frames accept `GROUPS` units, as well as `ROWS` and
+    /// `RANGE`
+    pub supports_groups_frames: bool,
+    /// The table selected from by a `SELECT` without `FROM`, for engines that
+    /// require one, such as `DUAL` in Oracle. It isn't passed to the identifier
+    /// hook.
+    pub dummy_from_table: Option<ObjectName>,
+    /// Whether `OFFSET` is followed by `ROW` or `ROWS`, as in standard SQL.
+    /// When `true`, an `OFFSET` without either gets `ROW` for an offset of 1
+    /// and `ROWS` otherwise.
+    pub standard_offset_rows: bool,
+    /// Whether tables can be sampled with `TABLESAMPLE BERNOULLI(...)` and
+    /// `TABLESAMPLE SYSTEM(...)`
+    pub supports_table_sample: bool,
+    /// Whether `TABLESAMPLE` accepts a seed with `REPEATABLE(...)`
+    pub supports_table_sample_repeatable: bool,
+    /// How to render the `LIMIT` of a query
+    pub limit_style: LimitStyle,
+    /// Whether the limit may be an expression such as `LIMIT n + 1`, rather
+    /// than only a number or a placeholder
+    pub limit_allows_expression: bool,
+    /// Whether optimizer hints are written in a `/*+ ... */` comment after
+    /// `SELECT`. When `false`, hints are omitted.
+    pub supports_optimizer_hints: bool,
+    /// How to render the boolean literals `TRUE` and `FALSE`
+    pub boolean_literal_style: BooleanLiteralStyle,
+    /// How an `INSERT` handles rows that conflict with existing rows
+    pub upsert_style: UpsertStyle,
+    /// Reject `NATURAL` joins with a `SqlGenError::DialectError`, for engines
+    /// that don't support them or teams that forbid them
+    pub disallow_natural_join: bool,
+    /// Whether `CREATE OR REPLACE TABLE` replaces an existing table
+    pub supports_create_or_replace_table: bool,
+    /// Whether `CREATE MATERIALIZED VIEW` is supported
+    pub supports_materialized_views: bool,
+    /// The syntax of the options of `EXPLAIN`
+    pub explain_style: ExplainStyle,
+    /// The statements that begin and commit a transaction
+    pub transaction_style: TransactionStyle,
+    /// Whether queries may have a `LIMIT n BY expr` clause, as in ClickHouse
+    pub supports_limit_by: bool,
+    /// Whether queries may end with `SETTINGS key = value`, as in ClickHouse
+    pub supports_query_settings: bool,
+    /// The deepest nesting of queries, set operations, expressions and function
+    /// calls that is rendered, beyond which rendering fails with
+    /// `SqlGenError::DepthExceeded` rather than risking a stack overflow.
+    /// `None` for no limit.
+    pub max_render_depth: Option<usize>,
+    /// Whether `x = NULL` and `x <> NULL`, which are never true, are rendered
+    /// as `x IS NULL` and `x IS NOT NULL`. `NULL = NULL` is left as it is.
+    pub rewrite_null_comparisons: bool,
+    /// The type that the results of `floor`, `ceil` and `trunc` are cast to,
+    /// for them to have the same type as in other dialects, such as `DOUBLE`
+    /// to keep DataFusion's float results. `None` for the type the dialect's
+    /// functions return.
+    pub rounding_result_type: Option<DataType>,
+    /// Whether a wildcard may select the columns matching a pattern with
+    /// `* ILIKE 'pattern'`, as in Snowflake
+    pub supports_wildcard_ilike: bool,
+    /// Whether a wildcard may rename columns with `* RENAME (a AS b)`, as in
+    /// Snowflake
+    pub supports_wildcard_rename: bool,
+    /// How to render the remainder of a division, `a % b`
+    pub modulo_style: ModuloStyle,
+    /// Whether a query with an `OFFSET` or `FETCH`, including a `LIMIT`
+    /// rendered as `FETCH`, must have an `ORDER BY`, as in SQL Server
+    pub require_order_by_with_offset: bool,
+    /// Whether identifiers that differ only in case name the same table or
+    /// column. Used when comparing identifiers in analyses such as
+    /// `Query::lint_for`.
+    pub identifier_case: IdentifierCase,
+    /// Whether a derived table, `(SELECT ...)` in a `FROM` clause, must have an
+    /// alias, as in PostgreSQL and SQL Server
+    pub require_derived_alias: bool,
+    /// How to render `count(DISTINCT a, b)` with more than one argument
+    pub count_distinct_style: CountDistinctStyle,
+    /// Whether strings may be written with unicode escapes as `U&'d\0061t'`,
+    /// as in PostgreSQL and Trino
+    pub supports_unicode_strings: bool,
+    /// Whether a `SELECT` may filter on window functions with `QUALIFY`, as in
+    /// Snowflake, BigQuery and ClickHouse
+    pub supports_qualify: bool,
+    /// Whether table aliases follow the table without `AS`, as in Oracle,
+    /// which rejects `FROM t AS x`
+    pub omit_table_alias_as: bool,
+    /// Whether `SELECT TOP (n)` limits the rows of a select, as in SQL Server.
+    /// A `LIMIT` without an `ORDER BY` or `OFFSET` is rendered with it, since
+    /// `FETCH` needs both.
+    pub supports_top: bool,
+    /// How `CREATE OR REPLACE VIEW` is spelled, if at all
+    pub view_replace_style: ViewReplaceStyle,
+    /// Whether `CREATE OR REPLACE MATERIALIZED VIEW` replaces an existing
+    /// materialized view, which PostgreSQL doesn't support
+    pub supports_replace_materialized_view: bool,
+    /// The placeholders [Query::parameterize] replaces literal values with
+    ///
+    /// [Query::parameterize]: crate::ast::Query::parameterize
+    pub placeholder_style: PlaceholderStyle,
+}
+
+/// A dialect shared by the threads rendering with it. Rendering only borrows
+/// the dialect, so a `&SharedDialect` is passed wherever a `&Dialect` is
+/// expected, without cloning its functions and transforms.
+pub type SharedDialect = Arc<Dialect>;
+
+/// A function producing the text of an identifier, see
+/// [Dialect::identifier_hook]
+#[derive(Clone)]
+pub struct IdentifierHook {
+    pub hook: Arc<dyn Fn(&Ident) -> String + Send + Sync>,
+    /// Whether the text produced by the hook is quoted with the dialect's
+    /// quote style. When `false` it is written as is.
+    pub quote: bool,
+}
+
+impl Debug for IdentifierHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdentifierHook")
+            .field("quote", &self.quote)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The spelling of the number of seconds since the Unix epoch of a timestamp
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EpochStyle {
+    /// `EXTRACT(EPOCH FROM ts)`
+    #[default]
+    Extract,
+    /// `UNIX_TIMESTAMP(ts)`, as in MySQL
+    UnixTimestamp,
+    /// `strftime('%s', ts)`, as in SQLite
+    Strftime,
+    /// `UNIX_SECONDS(ts)`, as in BigQuery
+    UnixSeconds,
+    /// `to_unixtime(ts)`, as in Trino
+    ToUnixtime,
+    /// `DATEDIFF_BIG(SECOND, '1970-01-01', ts)`, as in SQL Server
+    DateDiffBig,
+    /// `((CAST(ts AS DATE) - DATE '1970-01-01') * 86400)`, as in Oracle, where
+    /// subtracting dates gives the days between them
+    DateSubtraction,
+}
+
+/// The spelling of a cast
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CastStyle {
+    /// `CAST(x AS type)`
+    #[default]
+    Cast,
+    /// `CONVERT(type, x)`, as in SQL Server, followed by the style code of the
+    /// type if it has one, as in `CONVERT(DATETIME2, x, 126)`
+    Convert,
+}
+
+/// The spelling of a cast that returns NULL instead of failing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TryCastStyle {
+    /// `TRY_CAST(x AS type)`
+    #[default]
+    TryCast,
+    /// `SAFE_CAST(x AS type)`, as in BigQuery
+    SafeCast,
+    /// `CAST(x AS type)`, for dialects where this is acceptable even though
+    /// invalid values fail instead of becoming NULL
+    Cast,
+    /// Casts that can't fail are not supported by the dialect
+    Unsupported,
+}
+
+/// The spelling of string concatenation. Note that `concat(...)` skips NULL
+/// arguments in most dialects (but not MySQL), while `||` returns NULL.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StringConcatStyle {
+    /// Render `a || b` and `concat(a, b)` as written
+    #[default]
+    AsWritten,
+    /// Render both as `a || b`
+    Operator,
+    /// Render both as `concat(a, b)`, as needed for MySQL where `||` is a
+    /// logical OR
+    Function,
+    /// Render both as `concat(a, b)`, guarding `a || b` as `CASE WHEN a IS
+    /// NULL OR b IS NULL THEN NULL ELSE concat(a, b) END` to keep its NULL
+    /// result, as needed for SQL Server whose `concat` skips NULLs
+    GuardedFunction,
+}
+
+/// The spelling of an interval literal multiplied by a number
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntervalMultiplicationStyle {
+    /// `INTERVAL '1 hour' * n`
+    #[default]
+    Operator,
+    /// `INTERVAL n HOUR`, as in MySQL and BigQuery, where the quantity of an
+    /// interval may be an expression. Only intervals of a single unit can be
+    /// rewritten.
+    Quantity,
+    /// Intervals can't be multiplied
+    Unsupported,
+}
+
+/// The spelling of adding an interval literal to, or subtracting it from, a
+/// date or timestamp
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DateAddStyle {
+    /// `ts + INTERVAL '2' DAY`
+    #[default]
+    Operator,
+    /// `DATEADD(DAY, 2, ts)`, as in SQL Server. Only intervals of a single
+    /// unit can be rewritten.
+    DateAdd,
+    /// `DATE_ADD(ts, INTERVAL 2 DAY)` and `DATE_SUB(ts, INTERVAL 2 DAY)`, as in
+    /// MySQL. Only intervals of a single unit can be rewritten.
+    DateAddInterval,
+}
+
+/// The spelling of the remainder of a division. The remainder has the sign of
+/// the dividend, so that `-7 % 3` is `-1`, which is what both `%` and `MOD` do
+/// in the preset dialects; the styles only differ in which spelling the
+/// dialect accepts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ModuloStyle {
+    /// `a % b`
+    #[default]
+    Operator,
+    /// `MOD(a, b)`, as in BigQuery and Oracle, which have no `%` operator
+    Function,
+}
+
+/// The spelling of counting the distinct combinations of several columns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CountDistinctStyle {
+    /// `count(DISTINCT a, b)`, as in MySQL and Spark
+    #[default]
+    Native,
+    /// `count(DISTINCT CASE WHEN a IS NULL OR b IS NULL THEN NULL ELSE (a, b)
+    /// END)`, counting row values, as in PostgreSQL
+    Tuple,
+    /// `count(DISTINCT CASE WHEN a IS NULL OR b IS NULL THEN NULL ELSE CAST(a
+    /// AS TEXT) || '|' || CAST(b AS TEXT) END)`, casting to `VARCHAR` of
+    /// `max_length` instead of `TEXT` if given, as in Oracle, whose `CLOB`
+    /// can't be counted. Different combinations whose text contains the
+    /// separator may collide, such as `('a|', 'b')` and `('a', '|b')`, so that
+    /// the count may be too low.
+    Concat { max_length: Option<u64> },
+}
+
+/// The spelling of extracting a field from a date
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DatePartStyle {
+    /// Render `EXTRACT(YEAR FROM ts)` and `date_part('year', ts)` as written
+    #[default]
+    AsWritten,
+    /// Render both as `EXTRACT(YEAR FROM ts)`. Only `date_part` calls with a
+    /// literal field can be rewritten.
+    Extract,
+    /// Render both as `date_part('year', ts)`
+    DatePart,
+}
+
+/// The spelling of a regular expression match. Dialects other than
+/// [RegexMatchStyle::Operator] and [RegexMatchStyle::RegexpLikeMatchParameter]
+/// match case-insensitively by prefixing a literal pattern with `(?i)`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RegexMatchStyle {
+    /// `a ~ 'p'`, as in PostgreSQL
+    #[default]
+    Operator,
+    /// `a REGEXP 'p'`, as in MySQL
+    Regexp,
+    /// `a RLIKE 'p'`, as in Spark
+    Rlike,
+    /// `REGEXP_CONTAINS(a, 'p')`, as in BigQuery
+    RegexpContains,
+    /// `REGEXP_LIKE(a, 'p')`, as in Druid
+    RegexpLike,
+    /// `REGEXP_LIKE(a, 'p')`, matching case-insensitively with the match
+    /// parameter `'i'` as in `REGEXP_LIKE(a, 'p', 'i')`, as in Oracle, whose
+    /// patterns have no `(?i)` flag
+    RegexpLikeMatchParameter,
+    /// Regular expressions are not supported
+    Unsupported,
+}
+
+/// The spelling of an array literal
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArrayStyle {
+    /// Render `ARRAY[1, 2]` and `[1, 2]` as written
+    #[default]
+    AsWritten,
+    /// `ARRAY[1, 2]`, as in PostgreSQL
+    Keyword,
+    /// `[1, 2]`, as in BigQuery
+    Brackets,
+    /// `array(1, 2)`, as in Spark
+    Function,
+    /// Arrays are not supported
+    Unsupported,
+}
+
+/// The spelling of indexing into an array
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ArrayIndexStyle {
+    /// `a[1]` for the first element, as in PostgreSQL
+    #[default]
+    OneBased,
+    /// `a[0]` for the first element, as in Spark
+    ZeroBased,
+    /// `a[OFFSET(0)]` for the first element, as in BigQuery
+    Offset,
+    /// Arrays can't be indexed
+    Unsupported,
+}
+
+/// The spelling of `UNNEST` in a `FROM` clause. Offsets count from 0 and
+/// ordinalities from 1, so neither is rendered in the style of the other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnnestStyle {
+    /// `UNNEST(a) AS t WITH OFFSET AS o` over a single array, as in BigQuery
+    #[default]
+    Offset,
+    /// `UNNEST(a, b) WITH ORDINALITY AS t` over one or more arrays, as in
+    /// PostgreSQL
+    Ordinality,
+    /// No `UNNEST`, as in SQL Server
+    Unsupported,
+}
+
+/// The spelling of a derived table that references the tables before it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LateralStyle {
+    /// `JOIN LATERAL (...)`
+    #[default]
+    Lateral,
+    /// `CROSS APPLY (...)` and `OUTER APPLY (...)`, as in SQL Server. Only
+    /// lateral joins on `TRUE` or without a condition can be rewritten.
+    Apply,
+}
+
+/// The spelling of a struct literal with named fields
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StructStyle {
+    /// `named_struct('a', 1)`, as in DataFusion and Spark
+    #[default]
+    Function,
+    /// `{'a': 1}`, as in DuckDB
+    Braces,
+    /// `STRUCT(1 AS a)`, as in BigQuery
+    Keyword,
+    /// Structs are not supported
+    Unsupported,
+}
+
+/// The spelling of the limit on the number of rows returned by a query
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LimitStyle {
+    /// `LIMIT n`
+    #[default]
+    Limit,
+    /// `FETCH FIRST n ROWS ONLY` after any `OFFSET`, as in Oracle, which has
+    /// no `LIMIT`
+    Fetch,
+    /// `OFFSET 0 ROWS FETCH FIRST n ROWS ONLY` when there is no `OFFSET`, as
+    /// in SQL Server, where `FETCH` must follow an `OFFSET`
+    OffsetFetch,
+}
+
+/// The spelling of boolean literals
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BooleanLiteralStyle {
+    /// `true` and `false`
+    #[default]
+    Keyword,
+    /// `1` and `0`, as in Oracle, which has no boolean literals
+    Integer,
+}
+
+/// The spelling of a `CREATE VIEW` that replaces an existing view
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ViewReplaceStyle {
+    /// `CREATE OR REPLACE VIEW`
+    #[default]
+    OrReplace,
+    /// `CREATE OR ALTER VIEW`, as in SQL Server
+    OrAlter,
+    /// No way to replace a view, as in SQLite
+    Unsupported,
+}
+
+/// How a dialect compares identifiers that differ only in case
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IdentifierCase {
+    /// Identifiers are compared exactly, quoted or not, as in ClickHouse
+    #[default]
+    Sensitive,
+    /// Identifiers are compared ignoring case, even quoted, as in MySQL and
+    /// SQLite
+    Insensitive,
+    /// Unquoted identifiers are folded to lowercase and quoted identifiers are
+    /// compared exactly, as in PostgreSQL
+    FoldLower,
+    /// Unquoted identifiers are folded to uppercase and quoted identifiers are
+    /// compared exactly, as in Snowflake and Oracle
+    FoldUpper,
+}
+
+/// The spelling of the placeholders of a parameterized query
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    /// `$1`, `$2` and so on, as in PostgreSQL
+    #[default]
+    Dollar,
+    /// `?` for every value, bound in order, as in MySQL and SQLite
+    QuestionMark,
+    /// `:1`, `:2` and so on, as in Oracle
+    Colon,
+    /// `@p1`, `@p2` and so on, as in SQL Server
+    AtP,
+}
+
+impl PlaceholderStyle {
+    /// The placeholder of the `n`th value, counting from 1
+    pub fn placeholder(self, n: usize) -> String {
+        match self {
+            PlaceholderStyle::Dollar => format!("${}", n),
+            PlaceholderStyle::QuestionMark => "?".to_string(),
+            PlaceholderStyle::Colon => format!(":{}", n),
+            PlaceholderStyle::AtP => format!("@p{}", n),
+        }
+    }
+}
+
+/// The clause of an `INSERT` that handles rows conflicting with existing rows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UpsertStyle {
+    /// `ON CONFLICT (columns) DO NOTHING | DO UPDATE SET ...`, as in PostgreSQL
+    /// and SQLite
+    #[default]
+    OnConflict,
+    /// `ON DUPLICATE KEY UPDATE ...`, as in MySQL
+    OnDuplicateKey,
+    /// Conflicting rows can't be handled in an `INSERT`
+    Unsupported,
+}
+
+/// The syntax of the options of `EXPLAIN`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExplainStyle {
+    /// `EXPLAIN ANALYZE VERBOSE`, or `EXPLAIN (ANALYZE, FORMAT JSON)` with a
+    /// format, as in PostgreSQL
+    #[default]
+    Options,
+    /// `EXPLAIN ANALYZE FORMAT=JSON`, as in MySQL, which has no `VERBOSE`
+    Assignment,
+}
+
+/// The statements that begin and commit a transaction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TransactionStyle {
+    /// `BEGIN` and `COMMIT`
+    #[default]
+    Begin,
+    /// `START TRANSACTION` and `COMMIT`, as in MySQL and Trino
+    StartTransaction,
+    /// `BEGIN TRANSACTION` and `COMMIT TRANSACTION`, as in SQL Server and
+    /// BigQuery
+    BeginTransaction,
+    /// Only `COMMIT`, as in Oracle where a transaction begins with the first
+    /// statement
+    Implicit,
+    /// Transactions are not supported
+    Unsupported,
+}
+
+/// How a dialect handles a call to a function that is not in its function set.
+/// Dialects with an empty function set allow every function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnknownFunctionPolicy {
+    /// Render the call like any supported function
+    PassThrough,
+    /// Fail with a `SqlGenError::DialectError`
+    #[default]
+    Error,
+    /// Render the call with a quoted function name, as for a user defined function
+    Quote,
+}
+
+/// The default [Dialect::max_render_depth] of the preset dialects, which
+/// leaves room on the 2 MiB stack of a spawned thread in a debug build
+pub const DEFAULT_MAX_RENDER_DEPTH: usize = 128;
+
+/// The spelling of a data type in a particular dialect
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataTypeName {
+    pub name: String,
+    /// Whether the type accepts length, precision, or scale parameters, as in
+    /// `VARCHAR(255)` or `DECIMAL(10,2)`. Parameters are omitted when `false`.
+    pub parameters: bool,
+    /// A function that casts to the type in place of `CAST(x AS type)`, as in
+    /// `PARSE_JSON(x)`
+    pub cast_function: Option<String>,
+    /// The style code passed to `CONVERT(type, x, style)` when casting to the
+    /// type with [CastStyle::Convert]
+    pub convert_style: Option<u32>,
+    /// The name used in place of the type in `CAST(x AS type)`, without
+    /// parameters, as in MySQL's `CAST(x AS SIGNED)`
+    pub cast_name: Option<String>,
+}
+
+impl DataTypeName {
+    pub fn new(name: &str, parameters: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters,
+            cast_function: None,
+            convert_style: None,
+            cast_name: None,
+        }
+    }
+
+    /// Cast to the type by calling `function` rather than with `CAST`
+    pub fn with_cast_function(mut self, function: &str) -> Self {
+        self.cast_function = Some(function.to_string());
+        self
+    }
+
+    /// Pass the style code `style` to `CONVERT` when casting to the type
+    pub fn with_convert_style(mut self, style: u32) -> Self {
+        self.convert_style = Some(style);
+        self
+    }
+
+    /// Cast to the type with `CAST(x AS name)`, keeping the type's own
+    /// spelling elsewhere
+    pub fn with_cast_name(mut self, name: &str) -> Self {
+        self.cast_name = Some(name.to_string());
+        self
+    }
+}
+
+/// Build a data type mapping from `(default spelling, name, parameters)` entries
+fn data_types(entries: &[(&str, &str, bool)]) -> HashMap<String, DataTypeName> {
+    entries
+        .iter()
+        .map(|(key, name, parameters)| (key.to_string(), DataTypeName::new(name, *parameters)))
+        .collect()
+}
+
+/// Set the functions that cast to the types in a data type mapping, from
+/// `(default spelling, function)` entries
+fn cast_functions(
+    mut data_types: HashMap<String, DataTypeName>,
+    entries: &[(&str, &str)],
+) -> HashMap<String, DataTypeName> {
+    for (key, function) in entries {
+        let data_type = data_types
+            .remove(*key)
+            .unwrap_or_else(|| DataTypeName::new(key, false));
+        data_types.insert(key.to_string(), data_type.with_cast_function(function));
+    }
+    data_types
+}
+
+/// Set the names that the types in a data type mapping are cast to, from
+/// `(default spelling, cast name)` entries
+fn cast_names(
+    mut data_types: HashMap<String, DataTypeName>,
+    entries: &[(&str, &str)],
+) -> HashMap<String, DataTypeName> {
+    for (key, name) in entries {
+        let data_type = data_types
+            .remove(*key)
+            .unwrap_or_else(|| DataTypeName::new(key, true));
+        data_types.insert(key.to_string(), data_type.with_cast_name(name));
+    }
+    data_types
+}
+
+/// Lowercase `name` if it has uppercase letters, without allocating otherwise
+fn lowercase_name(name: &str) -> Cow<'_, str> {
+    if name.bytes().any(|b| b.is_ascii_uppercase()) {
+        Cow::Owned(name.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(name)
+    }
+}
+
+/// The names of the functions that a dialect allows, see [Dialect::functions].
+/// Names are lowercased when inserted, so that looking them up ignoring case
+/// is a single hash lookup.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FunctionSet(HashSet<String>);
+
+impl FunctionSet {
+    /// Add a function, returning whether it wasn't in the set already
+    pub fn insert<S: Into<String>>(&mut self, name: S) -> bool {
+        let mut name = name.into();
+        name.make_ascii_lowercase();
+        self.0.insert(name)
+    }
+
+    /// Remove a function, ignoring case, returning whether it was in the set
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.0.remove(lowercase_name(name).as_ref())
+    }
+
+    /// Whether the set has the function `name`, ignoring case
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(lowercase_name(name).as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The lowercased names of the functions, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for FunctionSet {
+    fn from_iter<I: IntoIterator<Item = S>>(names: I) -> Self {
+        let mut set = FunctionSet::default();
+        set.extend(names);
+        set
+    }
+}
+
+impl<S: Into<String>> Extend<S> for FunctionSet {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, names: I) {
+        for name in names {
+            self.insert(name);
+        }
+    }
+}
+
+/// The transforms that render calls to functions, keyed by the function's
+/// name. Like [FunctionSet], names are lowercased when inserted.
+#[derive(Clone, Debug, Default)]
+pub struct FunctionTransforms(HashMap<String, Arc<dyn FunctionTransform>>);
+
+impl FunctionTransforms {
+    /// Register the transform for a function, returning the transform it
+    /// replaces if any
+    pub fn insert<S: Into<String>>(
+        &mut self,
+        name: S,
+        transform: Arc<dyn FunctionTransform>,
+    ) -> Option<Arc<dyn FunctionTransform>> {
+        let mut name = name.into();
+        name.make_ascii_lowercase();
+        self.0.insert(name, transform)
+    }
+
+    /// Remove the transform for a function, ignoring case
+    pub fn remove(&mut self, name: &str) -> Option<Arc<dyn FunctionTransform>> {
+        self.0.remove(lowercase_name(name).as_ref())
+    }
+
+    /// The transform for the function `name`, ignoring case
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn FunctionTransform>> {
+        self.0.get(lowercase_name(name).as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// The lowercased names of the functions with transforms, in no particular
+    /// order
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.0.keys().map(String::as_str)
+    }
 }
 
+/// Rewrites a call to a function into SQL that is valid for a particular dialect.
+///
+/// Transforms receive the arguments of the call as AST nodes so that they can
+/// inspect literal arguments (e.g. the unit passed to `date_trunc`), and are
+/// responsible for rendering the arguments with the supplied dialect.
 pub trait FunctionTransform: Debug + Send + Sync {
-    fn transform(&self, name: &str, args: &[String]) -> String;
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError>;
+
+    /// Rewrite a whole call, including the `DISTINCT` and `ORDER BY` modifiers
+    /// of an aggregate call. The default implementation passes the arguments to
+    /// `transform`, and fails if there are modifiers it would otherwise drop.
+    fn transform_function(
+        &self,
+        name: &str,
+        function: &Function,
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        if function.distinct {
+            return Err(dialect.unsupported(format!("DISTINCT in function {}", name)));
+        }
+        if !function.order_by.is_empty() {
+            return Err(dialect.unsupported(format!("ORDER BY in function {}", name)));
+        }
+        self.transform(name, &function.args, dialect)
+    }
+
+    /// Whether the transform renders calls at all, rather than failing because
+    /// the dialect has no equivalent of the function
+    fn is_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Render the arguments of a function call, checking that exactly `n` were provided
+fn sql_args(
+    name: &str,
+    args: &[FunctionArg],
+    n: usize,
+    dialect: &Dialect,
+) -> Result<Vec<String>, SqlGenError> {
+    if args.len() != n {
+        return Err(SqlGenError::DialectError(format!(
+            "Function {} expects {} argument(s), found {}",
+            name,
+            n,
+            args.len()
+        )));
+    }
+    args.iter()
+        .map(|arg| arg.sql(dialect))
+        .collect::<Result<Vec<_>, SqlGenError>>()
+}
+
+/// Return the value of a function argument if it is a string literal
+pub(crate) fn string_arg(arg: &FunctionArg) -> Option<&str> {
+    match arg {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(s))))
+        | FunctionArg::Named {
+            arg: FunctionArgExpr::Expr(Expr::Value(Value::SingleQuotedString(s))),
+            ..
+        } => Some(s.as_str()),
+        _ => None,
+    }
+}
+
+/// The features that a dialect renders natively, rather than rewriting them
+/// into an equivalent or failing, see [Dialect::capabilities]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `FILTER (WHERE ...)` on aggregates
+    pub aggregate_filter: bool,
+    /// `ORDER BY` in the arguments of aggregates
+    pub aggregate_order_by: bool,
+    /// `NULLS FIRST` and `NULLS LAST` in `ORDER BY`
+    pub nulls_ordering: bool,
+    /// `ILIKE`
+    pub ilike: bool,
+    /// `LIKE ANY` and `ILIKE ANY` with a list of patterns
+    pub like_any: bool,
+    /// Matching regular expressions, however it's spelled
+    pub regex_match: bool,
+    /// Booleans coercing to integers in aggregates
+    pub boolean_coercion: bool,
+    /// `TRY_CAST` or an equivalent
+    pub try_cast: bool,
+    /// Array literals
+    pub arrays: bool,
+    /// Indexing into arrays
+    pub array_index: bool,
+    /// Struct literals
+    pub structs: bool,
+    /// Select list subqueries referencing the enclosing query
+    pub correlated_select_subqueries: bool,
+    /// `BETWEEN SYMMETRIC`
+    pub between_symmetric: bool,
+    /// `EXCLUDE` in window frames
+    pub window_frame_exclude: bool,
+    /// `GROUPS` window frames
+    pub groups_frames: bool,
+    /// `TABLESAMPLE`
+    pub table_sample: bool,
+    /// `TABLESAMPLE ... REPEATABLE(...)`
+    pub table_sample_repeatable: bool,
+    /// A `LIMIT` that is an expression
+    pub limit_expression: bool,
+    /// `LIMIT n BY expr`
+    pub limit_by: bool,
+    /// `SETTINGS key = value` after a query
+    pub query_settings: bool,
+    /// Optimizer hints after `SELECT`
+    pub optimizer_hints: bool,
+    /// `NATURAL` joins
+    pub natural_join: bool,
+    /// `* ILIKE 'pattern'`
+    pub wildcard_ilike: bool,
+    /// `* RENAME (a AS b)`
+    pub wildcard_rename: bool,
+    /// `INSERT` handling conflicting rows
+    pub upsert: bool,
+    /// `CREATE OR REPLACE TABLE`
+    pub create_or_replace_table: bool,
+    /// `CREATE MATERIALIZED VIEW`
+    pub materialized_views: bool,
+    /// Transactions around a script of statements
+    pub transactions: bool,
+    /// `U&'...'` strings with unicode escapes
+    pub unicode_strings: bool,
+    /// `QUALIFY` filters on window functions
+    pub qualify: bool,
+    /// `AS` before table aliases, as in `FROM t AS x`
+    pub table_alias_as: bool,
+    /// `CREATE OR REPLACE VIEW`, however it's spelled
+    pub create_or_replace_view: bool,
+    /// `CREATE OR REPLACE MATERIALIZED VIEW`
+    pub create_or_replace_materialized_view: bool,
 }
 
 impl Dialect {
+    /// The error for a feature that the dialect doesn't support
+    pub(crate) fn unsupported<S: Into<String>>(&self, feature: S) -> SqlGenError {
+        SqlGenError::UnsupportedFeature {
+            feature: feature.into(),
+            dialect: self.name.to_string(),
+        }
+    }
+
+    /// The features that the dialect renders natively, for choosing how to
+    /// build a query before rendering it
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            aggregate_filter: self.supports_aggregate_filter,
+            aggregate_order_by: self.supports_aggregate_order_by,
+            nulls_ordering: self.supports_nulls_ordering,
+            ilike: self.supports_ilike,
+            like_any: self.supports_like_any,
+            regex_match: self.regex_match_style != RegexMatchStyle::Unsupported,
+            boolean_coercion: self.supports_boolean_coercion,
+            try_cast: self.try_cast_style != TryCastStyle::Unsupported,
+            arrays: self.array_style != ArrayStyle::Unsupported,
+            array_index: self.array_index_style != ArrayIndexStyle::Unsupported,
+            structs: self.struct_style != StructStyle::Unsupported,
+            correlated_select_subqueries: self.supports_correlated_select_subqueries,
+            between_symmetric: self.supports_between_symmetric,
+            window_frame_exclude: self.supports_window_frame_exclude,
+            groups_frames: self.supports_groups_frames,
+            table_sample: self.supports_table_sample,
+            table_sample_repeatable: self.supports_table_sample_repeatable,
+            limit_expression: self.limit_allows_expression,
+            limit_by: self.supports_limit_by,
+            query_settings: self.supports_query_settings,
+            optimizer_hints: self.supports_optimizer_hints,
+            natural_join: !self.disallow_natural_join,
+            wildcard_ilike: self.supports_wildcard_ilike,
+            wildcard_rename: self.supports_wildcard_rename,
+            upsert: self.upsert_style != UpsertStyle::Unsupported,
+            create_or_replace_table: self.supports_create_or_replace_table,
+            materialized_views: self.supports_materialized_views,
+            transactions: self.transaction_style != TransactionStyle::Unsupported,
+            unicode_strings: self.supports_unicode_strings,
+            qualify: self.supports_qualify,
+            table_alias_as: !self.omit_table_alias_as,
+            create_or_replace_view: self.view_replace_style != ViewReplaceStyle::Unsupported,
+            create_or_replace_materialized_view: self.supports_replace_materialized_view,
+        }
+    }
+
+    /// Look up the transform registered for a function, ignoring case
+    pub(crate) fn function_transform(&self, name: &str) -> Option<&Arc<dyn FunctionTransform>> {
+        self.function_transforms.get(name)
+    }
+
+    /// Whether a function may be called in this dialect, ignoring case. An empty
+    /// function set allows every function.
+    pub(crate) fn allows_function(&self, name: &str) -> bool {
+        self.functions.is_empty() || self.functions.contains(name)
+    }
+
+    /// Whether calls to the function `name`, ignoring case, can be rendered for
+    /// this dialect: the function has a transform that renders it, or is in
+    /// the dialect's function set, or the function set is empty. Other calls
+    /// are rendered according to `unknown_function_policy`.
+    pub fn supports_function(&self, name: &str) -> bool {
+        match self.function_transform(name) {
+            Some(transform) => transform.is_supported(),
+            None => self.allows_function(name),
+        }
+    }
+
+    /// The functions of the dialect's function set and transforms that can
+    /// be rendered, in alphabetical order. A dialect with an empty function
+    /// set supports other functions too, see [Dialect::supports_function].
+    pub fn functions(&self) -> impl Iterator<Item = &str> + '_ {
+        let mut names: Vec<&str> = self
+            .functions
+            .iter()
+            .chain(self.function_transforms.keys())
+            .filter(|name| self.supports_function(name))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names.into_iter()
+    }
+
+    /// Look up the dialect specific spelling of a data type
+    pub(crate) fn data_type_name(&self, sql_type: &str) -> Option<&DataTypeName> {
+        self.data_types.get(sql_type)
+    }
+
+    /// Allow calls to the function `name`, for example a user defined function.
+    /// Note that adding a function to a dialect with an empty function set,
+    /// which allows every function, restricts it to the added functions.
+    ///
+    /// ```
+    /// use sqlgen::dialect::{Dialect, DialectDisplay};
+    /// use sqlgen::parser::Parser;
+    ///
+    /// let dialect = Dialect::postgres().with_function("my_udf");
+    /// let query = Parser::parse_sql_query("SELECT my_udf(a) FROM t").unwrap();
+    /// assert_eq!(query.sql(&dialect).unwrap(), r#"SELECT my_udf("a") FROM "t""#);
+    /// ```
+    pub fn with_function<S: Into<String>>(mut self, name: S) -> Self {
+        self.functions.insert(name.into());
+        self
+    }
+
+    /// Allow calls to each of the functions in `names`
+    pub fn with_functions<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.functions.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Render calls to the function `name` with `transform`, replacing any
+    /// transform already registered for it
+    pub fn with_function_transform<S: Into<String>>(
+        mut self,
+        name: S,
+        transform: Arc<dyn FunctionTransform>,
+    ) -> Self {
+        self.function_transforms.insert(name.into(), transform);
+        self
+    }
+
+    /// Render identifiers as the text produced by `hook`, quoted with the
+    /// dialect's quote style if `quote` is `true`
+    ///
+    /// ```
+    /// use sqlgen::dialect::{Dialect, DialectDisplay};
+    /// use sqlgen::parser::Parser;
+    ///
+    /// let dialect = Dialect::postgres().with_identifier_hook(true, |ident| format!("app_{}", ident.value));
+    /// let query = Parser::parse_sql_query("SELECT a FROM t").unwrap();
+    /// assert_eq!(query.sql(&dialect).unwrap(), r#"SELECT "app_a" FROM "app_t""#);
+    /// ```
+    pub fn with_identifier_hook<F>(mut self, quote: bool, hook: F) -> Self
+    where
+        F: Fn(&Ident) -> String + Send + Sync + 'static,
+    {
+        self.identifier_hook = Some(IdentifierHook {
+            hook: Arc::new(hook),
+            quote,
+        });
+        self
+    }
+
+    /// Standard SQL, for engines without a preset. Unlike `Dialect::default()`,
+    /// identifiers are double quoted.
+    pub fn generic() -> Self {
+        Self {
+            name: "generic",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: Default::default(),
+            function_transforms: Default::default(),
+            data_types: Default::default(),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: true,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::Operator,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: false,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Unsupported,
+            array_style: ArrayStyle::Keyword,
+            array_index_style: ArrayIndexStyle::OneBased,
+            unnest_style: UnnestStyle::Ordinality,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: true,
+            supports_window_frame_exclude: true,
+            supports_groups_frames: true,
+            dummy_from_table: None,
+            standard_offset_rows: true,
+            supports_table_sample: true,
+            supports_table_sample_repeatable: true,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: true,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::OnConflict,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Begin,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Sensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Native,
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: true,
+            placeholder_style: PlaceholderStyle::Dollar,
+        }
+    }
+
     pub fn datafusion() -> Self {
         Self {
+            name: "datafusion",
             quote_style: Some('"'),
             quote_functions: false,
             functions: vec![
@@ -76,7 +1202,7 @@ impl Dialect {
                 "sha224",
                 "sha256",
                 "sha384",
-                "Sha512",
+                "sha512",
                 "split_part",
                 "starts_with",
                 "strpos",
@@ -93,6 +1219,7 @@ impl Dialect {
                 "upper",
                 "regexp_match",
                 "struct",
+                "named_struct",
                 // Aggregate functions
                 "min",
                 "max",
@@ -134,18 +1261,88 @@ impl Dialect {
             .map(|name| name.to_string())
             .collect(),
             function_transforms: Default::default(),
+            data_types: data_types(&[
+                ("CHARACTER VARYING", "VARCHAR", true),
+                ("NUMERIC", "DECIMAL", true),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: true,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::TryCast,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::DatePart,
+            supports_boolean_coercion: false,
+            supports_ilike: true,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Operator,
+            array_style: ArrayStyle::AsWritten,
+            array_index_style: ArrayIndexStyle::OneBased,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Function,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: true,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: true,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Unsupported,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::FoldLower,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::Dollar,
         }
     }
 
     pub fn sqlite() -> Self {
-        let mut function_transforms: HashMap<String, Arc<dyn FunctionTransform>> =
-            Default::default();
+        let mut function_transforms = FunctionTransforms::default();
         function_transforms.insert("floor".to_string(), Arc::new(SqLiteFloorTransform));
         function_transforms.insert("ceil".to_string(), Arc::new(SqLiteCeilTransform));
         function_transforms.insert("isfinite".to_string(), Arc::new(SqLiteIsFiniteTransform));
         function_transforms.insert("isnan".to_string(), Arc::new(SqLiteIsNanTransform));
+        function_transforms.insert("regexp_replace".to_string(), Arc::new(UnsupportedTransform));
+        function_transforms.insert("date_bin".to_string(), Arc::new(EpochDateBinTransform));
+        function_transforms.insert(
+            "string_agg".to_string(),
+            Arc::new(RenameTransform("group_concat")),
+        );
 
         Self {
+            name: "sqlite",
             quote_style: Some('"'),
             quote_functions: true,
             functions: vec![
@@ -218,55 +1415,3337 @@ impl Dialect {
             .map(|name| name.to_string())
             .collect(),
             function_transforms,
+            data_types: data_types(&[
+                ("CHAR", "TEXT", false),
+                ("CHARACTER VARYING", "TEXT", false),
+                ("NVARCHAR", "TEXT", false),
+                ("STRING", "TEXT", false),
+                ("DOUBLE", "REAL", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: true,
+            supports_aggregate_order_by: true,
+            epoch_style: EpochStyle::Strftime,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::Operator,
+            interval_multiplication: IntervalMultiplicationStyle::Unsupported,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::AsWritten,
+            supports_boolean_coercion: true,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Unsupported,
+            array_style: ArrayStyle::Unsupported,
+            array_index_style: ArrayIndexStyle::Unsupported,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: true,
+            supports_groups_frames: true,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: true,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::OnConflict,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Begin,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::Unsupported,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
         }
     }
-}
-
-#[derive(Clone, Debug)]
-struct SqLiteFloorTransform;
-impl FunctionTransform for SqLiteFloorTransform {
-    fn transform(&self, _name: &str, args: &[String]) -> String {
-        format!("round({} - 0.5)", &args[0])
-    }
-}
-
-#[derive(Clone, Debug)]
-struct SqLiteCeilTransform;
-impl FunctionTransform for SqLiteCeilTransform {
-    fn transform(&self, _name: &str, args: &[String]) -> String {
-        format!("round({} + 0.5)", &args[0])
-    }
-}
-
-#[derive(Clone, Debug)]
-struct SqLiteIsFiniteTransform;
-impl FunctionTransform for SqLiteIsFiniteTransform {
-    fn transform(&self, _name: &str, args: &[String]) -> String {
-        format!("{arg} NOT IN ('NaN', '-Inf', 'Inf')", arg = &args[0])
-    }
-}
 
-#[derive(Clone, Debug)]
-struct SqLiteIsNanTransform;
-impl FunctionTransform for SqLiteIsNanTransform {
-    fn transform(&self, _name: &str, args: &[String]) -> String {
-        format!("{arg} = 'NaN'", arg = &args[0])
-    }
-}
+    pub fn postgres() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert(
+            "date_trunc".to_string(),
+            Arc::new(PostgresDateTruncTransform),
+        );
+        function_transforms.insert("date_diff".to_string(), Arc::new(PostgresDateDiffTransform));
 
-impl Default for Dialect {
-    fn default() -> Self {
         Self {
-            quote_style: None,
+            name: "postgres",
+            quote_style: Some('"'),
             quote_functions: false,
-            functions: Default::default(),
-            function_transforms: Default::default(),
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "cbrt",
+                "ceil",
+                "ceiling",
+                "cos",
+                "cot",
+                "degrees",
+                "div",
+                "exp",
+                "floor",
+                "isfinite",
+                "ln",
+                "log",
+                "log10",
+                "mod",
+                "pi",
+                "power",
+                "radians",
+                "random",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "trunc",
+                "coalesce",
+                "greatest",
+                "least",
+                "nullif",
+                "ascii",
+                "bit_length",
+                "btrim",
+                "char_length",
+                "character_length",
+                "chr",
+                "concat",
+                "concat_ws",
+                "format",
+                "initcap",
+                "left",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "octet_length",
+                "regexp_match",
+                "regexp_replace",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "split_part",
+                "starts_with",
+                "strpos",
+                "substr",
+                "to_hex",
+                "translate",
+                "upper",
+                "age",
+                "clock_timestamp",
+                "date_bin",
+                "date_part",
+                "date_trunc",
+                "make_date",
+                "make_timestamp",
+                "now",
+                "to_char",
+                "to_date",
+                "to_timestamp",
+                // Aggregate functions
+                "array_agg",
+                "avg",
+                "bool_and",
+                "bool_or",
+                "corr",
+                "count",
+                "covar_pop",
+                "covar_samp",
+                "max",
+                "min",
+                "percentile_cont",
+                "percentile_disc",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "string_agg",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("DOUBLE", "DOUBLE PRECISION", false),
+                ("DATETIME", "TIMESTAMP", false),
+                ("STRING", "TEXT", false),
+                ("BINARY", "BYTEA", false),
+                ("VARBINARY", "BYTEA", false),
+                ("BLOB", "BYTEA", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: true,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: false,
+            supports_ilike: true,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Operator,
+            array_style: ArrayStyle::Keyword,
+            array_index_style: ArrayIndexStyle::OneBased,
+            unnest_style: UnnestStyle::Ordinality,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: true,
+            supports_window_frame_exclude: true,
+            supports_groups_frames: true,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: true,
+            supports_table_sample_repeatable: true,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: true,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::OnConflict,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Begin,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::FoldLower,
+            require_derived_alias: true,
+            count_distinct_style: CountDistinctStyle::Tuple,
+            supports_unicode_strings: true,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::Dollar,
+        }
+    }
+
+    pub fn mysql() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert(
+            "string_agg".to_string(),
+            Arc::new(MySqlGroupConcatTransform),
+        );
+        function_transforms.insert("date_bin".to_string(), Arc::new(EpochDateBinTransform));
+        function_transforms.insert(
+            "date_diff".to_string(),
+            Arc::new(DateDiffTransform {
+                function: "TIMESTAMPDIFF",
+                units: &[
+                    "microsecond",
+                    "second",
+                    "minute",
+                    "hour",
+                    "day",
+                    "week",
+                    "month",
+                    "quarter",
+                    "year",
+                ],
+            }),
+        );
+
+        Self {
+            name: "mysql",
+            quote_style: Some('`'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "ceil",
+                "ceiling",
+                "cos",
+                "cot",
+                "degrees",
+                "exp",
+                "floor",
+                "ln",
+                "log",
+                "log10",
+                "log2",
+                "mod",
+                "pi",
+                "pow",
+                "power",
+                "radians",
+                "rand",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "truncate",
+                "coalesce",
+                "greatest",
+                "if",
+                "ifnull",
+                "least",
+                "nullif",
+                "ascii",
+                "char_length",
+                "character_length",
+                "concat",
+                "concat_ws",
+                "instr",
+                "lcase",
+                "left",
+                "length",
+                "locate",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "regexp_like",
+                "regexp_replace",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "sha1",
+                "sha2",
+                "substr",
+                "substring_index",
+                "ucase",
+                "upper",
+                "curdate",
+                "current_date",
+                "current_timestamp",
+                "date",
+                "date_add",
+                "date_format",
+                "date_sub",
+                "datediff",
+                "day",
+                "dayofmonth",
+                "dayofweek",
+                "dayofyear",
+                "from_unixtime",
+                "hour",
+                "minute",
+                "month",
+                "now",
+                "quarter",
+                "second",
+                "str_to_date",
+                "timestampdiff",
+                "unix_timestamp",
+                "week",
+                "year",
+                // Aggregate functions
+                "avg",
+                "bit_and",
+                "bit_or",
+                "count",
+                "group_concat",
+                "max",
+                "min",
+                "std",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: cast_names(
+                data_types(&[
+                    ("CHARACTER VARYING", "CHAR", true),
+                    ("NVARCHAR", "CHAR", true),
+                    ("TEXT", "CHAR", false),
+                    ("STRING", "CHAR", false),
+                    ("NUMERIC", "DECIMAL", true),
+                    ("TIMESTAMP", "DATETIME", false),
+                    ("JSONB", "JSON", false),
+                ]),
+                // MySQL casts to integers only as SIGNED or UNSIGNED, and has
+                // no boolean type to cast to
+                &[
+                    ("TINYINT", "SIGNED"),
+                    ("SMALLINT", "SIGNED"),
+                    ("INT", "SIGNED"),
+                    ("INTEGER", "SIGNED"),
+                    ("BIGINT", "SIGNED"),
+                    ("BOOLEAN", "SIGNED"),
+                ],
+            ),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: true,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::UnixTimestamp,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: false,
+            string_concat: StringConcatStyle::Function,
+            interval_multiplication: IntervalMultiplicationStyle::Quantity,
+            date_add_style: DateAddStyle::DateAddInterval,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: true,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Regexp,
+            array_style: ArrayStyle::Unsupported,
+            array_index_style: ArrayIndexStyle::Unsupported,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: true,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::OnDuplicateKey,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Assignment,
+            transaction_style: TransactionStyle::StartTransaction,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Native,
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    pub fn bigquery() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert(
+            "date_trunc".to_string(),
+            Arc::new(BigQueryTimestampTruncTransform),
+        );
+
+        Self {
+            name: "bigquery",
+            quote_style: Some('`'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "ceil",
+                "ceiling",
+                "cos",
+                "div",
+                "exp",
+                "floor",
+                "greatest",
+                "is_inf",
+                "is_nan",
+                "least",
+                "ln",
+                "log",
+                "log10",
+                "mod",
+                "pow",
+                "power",
+                "rand",
+                "round",
+                "safe_divide",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "trunc",
+                "coalesce",
+                "if",
+                "ifnull",
+                "nullif",
+                "ascii",
+                "byte_length",
+                "char_length",
+                "character_length",
+                "concat",
+                "ends_with",
+                "format",
+                "initcap",
+                "left",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "regexp_contains",
+                "regexp_extract",
+                "regexp_replace",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "sha256",
+                "sha512",
+                "split",
+                "starts_with",
+                "strpos",
+                "substr",
+                "to_hex",
+                "translate",
+                "trim",
+                "upper",
+                "current_date",
+                "current_datetime",
+                "current_timestamp",
+                "date",
+                "date_add",
+                "date_diff",
+                "date_sub",
+                "date_trunc",
+                "datetime",
+                "datetime_trunc",
+                "format_timestamp",
+                "parse_timestamp",
+                "timestamp",
+                "timestamp_add",
+                "timestamp_diff",
+                "timestamp_millis",
+                "timestamp_seconds",
+                "timestamp_sub",
+                "timestamp_trunc",
+                "unix_millis",
+                "unix_seconds",
+                // Aggregate functions
+                "any_value",
+                "approx_count_distinct",
+                "approx_quantiles",
+                "array_agg",
+                "avg",
+                "corr",
+                "count",
+                "countif",
+                "covar_pop",
+                "covar_samp",
+                "logical_and",
+                "logical_or",
+                "max",
+                "min",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "string_agg",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: cast_functions(
+                data_types(&[
+                    ("CHAR", "STRING", false),
+                    ("CHARACTER VARYING", "STRING", true),
+                    ("NVARCHAR", "STRING", true),
+                    ("TEXT", "STRING", false),
+                    ("BINARY", "BYTES", true),
+                    ("VARBINARY", "BYTES", true),
+                    ("BLOB", "BYTES", false),
+                    ("BYTEA", "BYTES", false),
+                    ("TINYINT", "INT64", false),
+                    ("SMALLINT", "INT64", false),
+                    ("INT", "INT64", false),
+                    ("INTEGER", "INT64", false),
+                    ("BIGINT", "INT64", false),
+                    ("REAL", "FLOAT64", false),
+                    ("FLOAT", "FLOAT64", false),
+                    ("DOUBLE", "FLOAT64", false),
+                    ("BOOLEAN", "BOOL", false),
+                    ("JSONB", "JSON", false),
+                ]),
+                &[("JSON", "PARSE_JSON"), ("JSONB", "PARSE_JSON")],
+            ),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: true,
+            supports_aggregate_order_by: true,
+            epoch_style: EpochStyle::UnixSeconds,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::SafeCast,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Quantity,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: false,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::RegexpContains,
+            array_style: ArrayStyle::Brackets,
+            array_index_style: ArrayIndexStyle::Offset,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Keyword,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: true,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::BeginTransaction,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Function,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: false,
+            supports_qualify: true,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: true,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    pub fn spark() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("median".to_string(), Arc::new(SparkMedianTransform));
+        function_transforms.insert(
+            "array_agg".to_string(),
+            Arc::new(RenameTransform("collect_list")),
+        );
+        Self {
+            name: "spark",
+            quote_style: Some('`'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "bround",
+                "cbrt",
+                "ceil",
+                "ceiling",
+                "cos",
+                "degrees",
+                "exp",
+                "floor",
+                "greatest",
+                "isnan",
+                "least",
+                "ln",
+                "log",
+                "log10",
+                "log2",
+                "mod",
+                "pi",
+                "pmod",
+                "pow",
+                "power",
+                "radians",
+                "rand",
+                "round",
+                "sign",
+                "signum",
+                "sin",
+                "sqrt",
+                "tan",
+                "coalesce",
+                "if",
+                "ifnull",
+                "nanvl",
+                "nullif",
+                "nvl",
+                "ascii",
+                "char_length",
+                "character_length",
+                "concat",
+                "concat_ws",
+                "format_string",
+                "initcap",
+                "instr",
+                "lcase",
+                "left",
+                "length",
+                "locate",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "regexp_extract",
+                "regexp_replace",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "sha1",
+                "sha2",
+                "split",
+                "startswith",
+                "substr",
+                "substring",
+                "translate",
+                "trim",
+                "ucase",
+                "upper",
+                "current_date",
+                "current_timestamp",
+                "date_add",
+                "date_format",
+                "date_sub",
+                "date_trunc",
+                "datediff",
+                "dayofmonth",
+                "dayofweek",
+                "dayofyear",
+                "from_unixtime",
+                "hour",
+                "minute",
+                "month",
+                "now",
+                "quarter",
+                "second",
+                "to_date",
+                "to_timestamp",
+                "trunc",
+                "unix_timestamp",
+                "weekofyear",
+                "year",
+                "named_struct",
+                // Aggregate functions
+                "any",
+                "approx_count_distinct",
+                "approx_percentile",
+                "avg",
+                "bool_and",
+                "bool_or",
+                "collect_list",
+                "collect_set",
+                "corr",
+                "count",
+                "count_if",
+                "covar_pop",
+                "covar_samp",
+                "first",
+                "last",
+                "max",
+                "mean",
+                "min",
+                "percentile",
+                "percentile_approx",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("CHARACTER VARYING", "STRING", false),
+                ("NVARCHAR", "STRING", false),
+                ("TEXT", "STRING", false),
+                ("BINARY", "BINARY", false),
+                ("VARBINARY", "BINARY", false),
+                ("BLOB", "BINARY", false),
+                ("BYTEA", "BINARY", false),
+                ("REAL", "FLOAT", false),
+                ("DATETIME", "TIMESTAMP", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: true,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::UnixTimestamp,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::TryCast,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::AsWritten,
+            supports_boolean_coercion: false,
+            supports_ilike: true,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Rlike,
+            array_style: ArrayStyle::Function,
+            array_index_style: ArrayIndexStyle::ZeroBased,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Function,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: true,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Unsupported,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Native,
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    pub fn snowflake() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("string_agg".to_string(), Arc::new(ListAggTransform));
+
+        Self {
+            name: "snowflake",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "acosh",
+                "asin",
+                "asinh",
+                "atan",
+                "atan2",
+                "atanh",
+                "cbrt",
+                "ceil",
+                "cos",
+                "cosh",
+                "degrees",
+                "div0",
+                "exp",
+                "factorial",
+                "floor",
+                "ln",
+                "log",
+                "mod",
+                "pi",
+                "pow",
+                "power",
+                "radians",
+                "round",
+                "sign",
+                "sin",
+                "sinh",
+                "sqrt",
+                "square",
+                "tan",
+                "tanh",
+                "trunc",
+                "truncate",
+                "coalesce",
+                "decode",
+                "equal_null",
+                "greatest",
+                "iff",
+                "ifnull",
+                "least",
+                "nullif",
+                "nullifzero",
+                "nvl",
+                "nvl2",
+                "zeroifnull",
+                "ascii",
+                "base64_encode",
+                "bit_length",
+                "char",
+                "charindex",
+                "concat",
+                "concat_ws",
+                "contains",
+                "editdistance",
+                "endswith",
+                "initcap",
+                "insert",
+                "left",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "position",
+                "regexp_count",
+                "regexp_instr",
+                "regexp_like",
+                "regexp_replace",
+                "regexp_substr",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "sha1",
+                "sha2",
+                "split",
+                "split_part",
+                "startswith",
+                "strtok",
+                "substr",
+                "substring",
+                "translate",
+                "trim",
+                "upper",
+                "add_months",
+                "convert_timezone",
+                "current_date",
+                "current_time",
+                "current_timestamp",
+                "date_from_parts",
+                "date_part",
+                "date_trunc",
+                "dateadd",
+                "datediff",
+                "dayname",
+                "dayofmonth",
+                "dayofweek",
+                "dayofyear",
+                "hour",
+                "last_day",
+                "minute",
+                "month",
+                "monthname",
+                "quarter",
+                "second",
+                "time_slice",
+                "timeadd",
+                "timediff",
+                "timestamp_from_parts",
+                "timestampadd",
+                "timestampdiff",
+                "to_date",
+                "to_time",
+                "to_timestamp",
+                "to_timestamp_ltz",
+                "to_timestamp_ntz",
+                "to_timestamp_tz",
+                "week",
+                "weekofyear",
+                "year",
+                // Aggregate functions
+                "any_value",
+                "approx_count_distinct",
+                "approx_percentile",
+                "array_agg",
+                "avg",
+                "bitand_agg",
+                "bitor_agg",
+                "booland_agg",
+                "boolor_agg",
+                "corr",
+                "count",
+                "count_if",
+                "covar_pop",
+                "covar_samp",
+                "listagg",
+                "max",
+                "median",
+                "min",
+                "mode",
+                "percentile_cont",
+                "percentile_disc",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: cast_functions(
+                data_types(&[
+                    ("BYTEA", "BINARY", false),
+                    ("BLOB", "BINARY", false),
+                    ("CLOB", "STRING", false),
+                    ("DATETIME", "TIMESTAMP_NTZ", false),
+                    ("JSON", "VARIANT", false),
+                    ("JSONB", "VARIANT", false),
+                ]),
+                &[("JSON", "PARSE_JSON"), ("JSONB", "PARSE_JSON")],
+            ),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::TryCast,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Unsupported,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::AsWritten,
+            supports_boolean_coercion: false,
+            supports_ilike: true,
+            supports_like_any: true,
+            regex_match_style: RegexMatchStyle::Rlike,
+            array_style: ArrayStyle::Brackets,
+            array_index_style: ArrayIndexStyle::ZeroBased,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: true,
+            supports_table_sample_repeatable: true,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Begin,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: true,
+            supports_wildcard_rename: true,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::FoldUpper,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Native,
+            supports_unicode_strings: false,
+            supports_qualify: true,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: true,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    pub fn vertica() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert(
+            "approx_distinct".to_string(),
+            Arc::new(RenameTransform("APPROXIMATE_COUNT_DISTINCT")),
+        );
+        function_transforms.insert(
+            "approx_median".to_string(),
+            Arc::new(RenameTransform("APPROXIMATE_MEDIAN")),
+        );
+
+        Self {
+            name: "vertica",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "cbrt",
+                "ceil",
+                "ceiling",
+                "cos",
+                "cot",
+                "degrees",
+                "exp",
+                "floor",
+                "greatest",
+                "least",
+                "ln",
+                "log",
+                "log10",
+                "mod",
+                "pi",
+                "power",
+                "radians",
+                "random",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "trunc",
+                "width_bucket",
+                "coalesce",
+                "decode",
+                "ifnull",
+                "isnull",
+                "nullif",
+                "nullifzero",
+                "nvl",
+                "nvl2",
+                "zeroifnull",
+                "ascii",
+                "btrim",
+                "char_length",
+                "character_length",
+                "chr",
+                "concat",
+                "initcap",
+                "instr",
+                "left",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "octet_length",
+                "overlay",
+                "position",
+                "regexp_count",
+                "regexp_instr",
+                "regexp_like",
+                "regexp_replace",
+                "regexp_substr",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "sha1",
+                "sha256",
+                "sha512",
+                "split_part",
+                "strpos",
+                "substr",
+                "substring",
+                "translate",
+                "trim",
+                "upper",
+                "add_months",
+                "age_in_months",
+                "age_in_years",
+                "current_date",
+                "current_time",
+                "current_timestamp",
+                "date",
+                "date_part",
+                "date_trunc",
+                "dateadd",
+                "datediff",
+                "day",
+                "dayofmonth",
+                "dayofweek",
+                "dayofyear",
+                "extract",
+                "hour",
+                "last_day",
+                "localtimestamp",
+                "minute",
+                "month",
+                "months_between",
+                "now",
+                "quarter",
+                "second",
+                "statement_timestamp",
+                "sysdate",
+                "time_slice",
+                "timestampadd",
+                "timestampdiff",
+                "to_char",
+                "to_date",
+                "to_timestamp",
+                "week",
+                "year",
+                // Aggregate functions
+                "approximate_count_distinct",
+                "approximate_median",
+                "approximate_percentile",
+                "avg",
+                "bit_and",
+                "bit_or",
+                "bool_and",
+                "bool_or",
+                "corr",
+                "count",
+                "covar_pop",
+                "covar_samp",
+                "listagg",
+                "max",
+                "median",
+                "min",
+                "regr_slope",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+                "conditional_change_event",
+                "conditional_true_event",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("DOUBLE", "DOUBLE PRECISION", false),
+                ("TEXT", "LONG VARCHAR", false),
+                ("STRING", "LONG VARCHAR", false),
+                ("BLOB", "LONG VARBINARY", false),
+                ("BYTEA", "VARBINARY", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::AsWritten,
+            supports_boolean_coercion: false,
+            supports_ilike: true,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::RegexpLike,
+            array_style: ArrayStyle::Keyword,
+            array_index_style: ArrayIndexStyle::ZeroBased,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Begin,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    pub fn druid() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("date_trunc".to_string(), Arc::new(DruidDateTruncTransform));
+        function_transforms.insert(
+            "approx_distinct".to_string(),
+            Arc::new(RenameTransform("APPROX_COUNT_DISTINCT")),
+        );
+        function_transforms.insert(
+            "regexp_replace".to_string(),
+            Arc::new(GlobalRegexpReplaceTransform),
+        );
+
+        Self {
+            name: "druid",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "ceil",
+                "cos",
+                "cot",
+                "degrees",
+                "exp",
+                "floor",
+                "ln",
+                "log10",
+                "mod",
+                "power",
+                "radians",
+                "round",
+                "sin",
+                "sqrt",
+                "tan",
+                "truncate",
+                "btrim",
+                "char_length",
+                "character_length",
+                "concat",
+                "contains_string",
+                "icontains_string",
+                "left",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "parse_long",
+                "position",
+                "regexp_extract",
+                "regexp_like",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "strlen",
+                "strpos",
+                "substr",
+                "substring",
+                "textcat",
+                "trim",
+                "upper",
+                "current_date",
+                "current_timestamp",
+                "millis_to_timestamp",
+                "time_ceil",
+                "time_extract",
+                "time_floor",
+                "time_format",
+                "time_parse",
+                "time_shift",
+                "timestamp_to_millis",
+                "timestampadd",
+                "timestampdiff",
+                "coalesce",
+                "nullif",
+                "nvl",
+                // Aggregate functions
+                "any_value",
+                "approx_count_distinct",
+                "approx_quantile",
+                "array_agg",
+                "avg",
+                "count",
+                "earliest",
+                "latest",
+                "max",
+                "min",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "string_agg",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("CHAR", "VARCHAR", false),
+                ("CHARACTER VARYING", "VARCHAR", false),
+                ("NVARCHAR", "VARCHAR", false),
+                ("TEXT", "VARCHAR", false),
+                ("STRING", "VARCHAR", false),
+                ("NUMERIC", "DECIMAL", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: true,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: false,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Unsupported,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: false,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::RegexpLike,
+            array_style: ArrayStyle::Keyword,
+            array_index_style: ArrayIndexStyle::Unsupported,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: false,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Unsupported,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Sensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::Unsupported,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    pub fn redshift() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("isnan".to_string(), Arc::new(SqLiteIsNanTransform));
+        function_transforms.insert("isfinite".to_string(), Arc::new(SqLiteIsFiniteTransform));
+        function_transforms.insert(
+            "approx_distinct".to_string(),
+            Arc::new(ApproximateCountDistinctTransform),
+        );
+        function_transforms.insert("date_bin".to_string(), Arc::new(UnsupportedTransform));
+        function_transforms.insert(
+            "date_diff".to_string(),
+            Arc::new(ElapsedDateDiffTransform {
+                function: "DATEDIFF",
+            }),
+        );
+        function_transforms.insert(
+            "date_trunc".to_string(),
+            Arc::new(PostgresDateTruncTransform),
+        );
+        function_transforms.insert(
+            "regexp_replace".to_string(),
+            Arc::new(GlobalRegexpReplaceTransform),
+        );
+        function_transforms.insert("string_agg".to_string(), Arc::new(ListAggTransform));
+
+        Self {
+            name: "redshift",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "ceil",
+                "ceiling",
+                "cos",
+                "cot",
+                "degrees",
+                "exp",
+                "floor",
+                "ln",
+                "log",
+                "log10",
+                "mod",
+                "pi",
+                "power",
+                "radians",
+                "random",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "trunc",
+                "coalesce",
+                "greatest",
+                "least",
+                "nullif",
+                "ascii",
+                "bit_length",
+                "btrim",
+                "char_length",
+                "character_length",
+                "chr",
+                "concat",
+                "concat_ws",
+                "initcap",
+                "left",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "octet_length",
+                "regexp_replace",
+                "regexp_substr",
+                "repeat",
+                "replace",
+                "reverse",
+                "right",
+                "rpad",
+                "rtrim",
+                "split_part",
+                "strpos",
+                "substr",
+                "to_hex",
+                "translate",
+                "upper",
+                "date_part",
+                "date_trunc",
+                "getdate",
+                "to_char",
+                "to_date",
+                "to_timestamp",
+                // Aggregate functions
+                "avg",
+                "bool_and",
+                "bool_or",
+                "count",
+                "listagg",
+                "max",
+                "median",
+                "min",
+                "percentile_cont",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: cast_functions(
+                data_types(&[
+                    ("DOUBLE", "DOUBLE PRECISION", false),
+                    ("DATETIME", "TIMESTAMP", false),
+                    ("STRING", "VARCHAR", false),
+                    ("BINARY", "VARBYTE", true),
+                    ("VARBINARY", "VARBYTE", true),
+                    ("BLOB", "VARBYTE", false),
+                    ("BYTEA", "VARBYTE", false),
+                    ("JSON", "SUPER", false),
+                    ("JSONB", "SUPER", false),
+                ]),
+                &[("JSON", "JSON_PARSE"), ("JSONB", "JSON_PARSE")],
+            ),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::Extract,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::Operator,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::AsWritten,
+            supports_boolean_coercion: false,
+            supports_ilike: true,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Operator,
+            array_style: ArrayStyle::Unsupported,
+            array_index_style: ArrayIndexStyle::Unsupported,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: false,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Begin,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat {
+                max_length: Some(65535),
+            },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::Dollar,
+        }
+    }
+
+    pub fn trino() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("isnan".to_string(), Arc::new(RenameTransform("is_nan")));
+        function_transforms.insert(
+            "isfinite".to_string(),
+            Arc::new(RenameTransform("is_finite")),
+        );
+        function_transforms.insert("md5".to_string(), Arc::new(TrinoMd5Transform));
+        function_transforms.insert(
+            "regexp_match".to_string(),
+            Arc::new(RenameTransform("regexp_extract")),
+        );
+        function_transforms.insert(
+            "regexp_replace".to_string(),
+            Arc::new(GlobalRegexpReplaceTransform),
+        );
+
+        Self {
+            name: "trino",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "cbrt",
+                "ceil",
+                "ceiling",
+                "cos",
+                "degrees",
+                "exp",
+                "floor",
+                "ln",
+                "log10",
+                "log2",
+                "mod",
+                "pi",
+                "power",
+                "radians",
+                "rand",
+                "random",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "truncate",
+                "coalesce",
+                "greatest",
+                "if",
+                "least",
+                "nullif",
+                "chr",
+                "codepoint",
+                "concat",
+                "concat_ws",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "md5",
+                "regexp_extract",
+                "regexp_extract_all",
+                "regexp_like",
+                "regexp_replace",
+                "replace",
+                "reverse",
+                "rpad",
+                "rtrim",
+                "split",
+                "split_part",
+                "starts_with",
+                "strpos",
+                "substr",
+                "substring",
+                "to_hex",
+                "to_utf8",
+                "trim",
+                "upper",
+                "current_date",
+                "current_timestamp",
+                "date_add",
+                "date_diff",
+                "date_format",
+                "date_parse",
+                "date_trunc",
+                "day",
+                "day_of_week",
+                "day_of_year",
+                "from_unixtime",
+                "hour",
+                "minute",
+                "month",
+                "now",
+                "quarter",
+                "second",
+                "to_unixtime",
+                "week",
+                "year",
+                // Aggregate functions
+                "approx_distinct",
+                "approx_percentile",
+                "arbitrary",
+                "array_agg",
+                "avg",
+                "bool_and",
+                "bool_or",
+                "corr",
+                "count",
+                "count_if",
+                "covar_pop",
+                "covar_samp",
+                "every",
+                "max",
+                "max_by",
+                "min",
+                "min_by",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: cast_functions(
+                data_types(&[
+                    ("CHARACTER VARYING", "VARCHAR", true),
+                    ("NVARCHAR", "VARCHAR", true),
+                    ("TEXT", "VARCHAR", false),
+                    ("STRING", "VARCHAR", false),
+                    ("NUMERIC", "DECIMAL", true),
+                    ("DATETIME", "TIMESTAMP", false),
+                    ("BINARY", "VARBINARY", false),
+                    ("BLOB", "VARBINARY", false),
+                    ("BYTEA", "VARBINARY", false),
+                    ("JSONB", "JSON", false),
+                ]),
+                &[("JSON", "json_parse"), ("JSONB", "json_parse")],
+            ),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: true,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: true,
+            epoch_style: EpochStyle::ToUnixtime,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::TryCast,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::AsWritten,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: false,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::RegexpLike,
+            array_style: ArrayStyle::Keyword,
+            array_index_style: ArrayIndexStyle::OneBased,
+            unnest_style: UnnestStyle::Ordinality,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: true,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: true,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::StartTransaction,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: true,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: true,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+
+    /// Presto shares its SQL dialect with Trino
+    pub fn presto() -> Self {
+        Self {
+            name: "presto",
+            ..Self::trino()
+        }
+    }
+
+    pub fn oracle() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("date_bin".to_string(), Arc::new(UnsupportedTransform));
+        function_transforms.insert(
+            "regexp_replace".to_string(),
+            Arc::new(GlobalRegexpReplaceTransform),
+        );
+        function_transforms.insert("string_agg".to_string(), Arc::new(ListAggTransform));
+
+        Self {
+            name: "oracle",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "ceil",
+                "cos",
+                "cosh",
+                "exp",
+                "floor",
+                "ln",
+                "log",
+                "mod",
+                "power",
+                "remainder",
+                "round",
+                "sign",
+                "sin",
+                "sinh",
+                "sqrt",
+                "tan",
+                "tanh",
+                "trunc",
+                "coalesce",
+                "decode",
+                "greatest",
+                "least",
+                "nullif",
+                "nvl",
+                "nvl2",
+                "ascii",
+                "chr",
+                "concat",
+                "initcap",
+                "instr",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "regexp_count",
+                "regexp_instr",
+                "regexp_like",
+                "regexp_replace",
+                "regexp_substr",
+                "replace",
+                "rpad",
+                "rtrim",
+                "soundex",
+                "substr",
+                "translate",
+                "trim",
+                "upper",
+                "add_months",
+                "current_date",
+                "current_timestamp",
+                "extract",
+                "last_day",
+                "months_between",
+                "next_day",
+                "sysdate",
+                "systimestamp",
+                "to_char",
+                "to_date",
+                "to_number",
+                "to_timestamp",
+                // Aggregate functions
+                "avg",
+                "corr",
+                "count",
+                "covar_pop",
+                "covar_samp",
+                "listagg",
+                "max",
+                "median",
+                "min",
+                "percentile_cont",
+                "percentile_disc",
+                "stddev",
+                "stddev_pop",
+                "stddev_samp",
+                "sum",
+                "var_pop",
+                "var_samp",
+                "variance",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("CHARACTER VARYING", "VARCHAR2", true),
+                ("NVARCHAR", "NVARCHAR2", true),
+                ("TEXT", "CLOB", false),
+                ("STRING", "CLOB", false),
+                ("BOOLEAN", "NUMBER(1)", false),
+                ("SMALLINT", "NUMBER(5)", false),
+                ("INT", "NUMBER(10)", false),
+                ("INTEGER", "NUMBER(10)", false),
+                ("BIGINT", "NUMBER(19)", false),
+                ("NUMERIC", "NUMBER", true),
+                ("REAL", "BINARY_FLOAT", false),
+                ("DOUBLE", "BINARY_DOUBLE", false),
+                ("DATETIME", "TIMESTAMP", false),
+                ("BYTEA", "BLOB", false),
+                ("JSONB", "JSON", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::DateSubtraction,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::Operator,
+            interval_multiplication: IntervalMultiplicationStyle::Operator,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: false,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::RegexpLikeMatchParameter,
+            array_style: ArrayStyle::Unsupported,
+            array_index_style: ArrayIndexStyle::Unsupported,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: Some(ObjectName(vec![Ident::new("DUAL")])),
+            standard_offset_rows: true,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Fetch,
+            limit_allows_expression: false,
+            supports_optimizer_hints: true,
+            boolean_literal_style: BooleanLiteralStyle::Integer,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: false,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Implicit,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Function,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::FoldUpper,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Concat {
+                max_length: Some(4000),
+            },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: true,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::Colon,
+        }
+    }
+
+    pub fn mssql() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("ceil".to_string(), Arc::new(RenameTransform("ceiling")));
+        function_transforms.insert("length".to_string(), Arc::new(RenameTransform("len")));
+        function_transforms.insert("now".to_string(), Arc::new(RenameTransform("getdate")));
+        function_transforms.insert(
+            "approx_distinct".to_string(),
+            Arc::new(RenameTransform("approx_count_distinct")),
+        );
+        function_transforms.insert("date_bin".to_string(), Arc::new(UnsupportedTransform));
+        function_transforms.insert(
+            "date_diff".to_string(),
+            Arc::new(ElapsedDateDiffTransform {
+                function: "DATEDIFF_BIG",
+            }),
+        );
+
+        Self {
+            name: "mssql",
+            quote_style: Some('['),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atn2",
+                "ceiling",
+                "cos",
+                "cot",
+                "degrees",
+                "exp",
+                "floor",
+                "log",
+                "log10",
+                "pi",
+                "power",
+                "radians",
+                "rand",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "square",
+                "tan",
+                "choose",
+                "coalesce",
+                "iif",
+                "isnull",
+                "nullif",
+                "ascii",
+                "char",
+                "charindex",
+                "concat",
+                "concat_ws",
+                "datalength",
+                "format",
+                "left",
+                "len",
+                "lower",
+                "ltrim",
+                "nchar",
+                "patindex",
+                "replace",
+                "replicate",
+                "reverse",
+                "right",
+                "rtrim",
+                "soundex",
+                "space",
+                "str",
+                "stuff",
+                "substring",
+                "translate",
+                "trim",
+                "upper",
+                "current_timestamp",
+                "dateadd",
+                "datediff",
+                "datefromparts",
+                "datename",
+                "datepart",
+                "day",
+                "eomonth",
+                "getdate",
+                "getutcdate",
+                "month",
+                "sysdatetime",
+                "year",
+                // Aggregate functions
+                "approx_count_distinct",
+                "avg",
+                "checksum_agg",
+                "count",
+                "count_big",
+                "grouping",
+                "max",
+                "min",
+                "stdev",
+                "stdevp",
+                "string_agg",
+                "sum",
+                "var",
+                "varp",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lag",
+                "lead",
+                "first_value",
+                "last_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("CHARACTER VARYING", "VARCHAR", true),
+                ("TEXT", "NVARCHAR(MAX)", false),
+                ("STRING", "NVARCHAR(MAX)", false),
+                ("CLOB", "NVARCHAR(MAX)", false),
+                ("NUMERIC", "DECIMAL", true),
+                ("DOUBLE", "FLOAT", false),
+                ("BOOLEAN", "BIT", false),
+                ("DATETIME", "DATETIME2", false),
+                ("TIMESTAMP", "DATETIME2", false),
+                ("UUID", "UNIQUEIDENTIFIER", false),
+                ("BYTEA", "VARBINARY(MAX)", false),
+                ("BLOB", "VARBINARY(MAX)", false),
+                ("JSON", "NVARCHAR(MAX)", false),
+                ("JSONB", "NVARCHAR(MAX)", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: true,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::DateDiffBig,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::TryCast,
+            supports_nulls_ordering: false,
+            string_concat: StringConcatStyle::GuardedFunction,
+            interval_multiplication: IntervalMultiplicationStyle::Unsupported,
+            date_add_style: DateAddStyle::DateAdd,
+            date_part_style: DatePartStyle::AsWritten,
+            supports_boolean_coercion: false,
+            supports_ilike: false,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Unsupported,
+            array_style: ArrayStyle::Unsupported,
+            array_index_style: ArrayIndexStyle::Unsupported,
+            unnest_style: UnnestStyle::Unsupported,
+            lateral_style: LateralStyle::Apply,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: true,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: true,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::OffsetFetch,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Integer,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: true,
+            supports_create_or_replace_table: false,
+            supports_materialized_views: false,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::BeginTransaction,
+            supports_limit_by: false,
+            supports_query_settings: false,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: true,
+            identifier_case: IdentifierCase::Insensitive,
+            require_derived_alias: true,
+            count_distinct_style: CountDistinctStyle::Concat { max_length: None },
+            supports_unicode_strings: false,
+            supports_qualify: false,
+            omit_table_alias_as: false,
+            supports_top: true,
+            view_replace_style: ViewReplaceStyle::OrAlter,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::AtP,
+        }
+    }
+
+    pub fn clickhouse() -> Self {
+        let mut function_transforms = FunctionTransforms::default();
+        function_transforms.insert("date_bin".to_string(), Arc::new(UnsupportedTransform));
+
+        Self {
+            name: "clickhouse",
+            quote_style: Some('"'),
+            quote_functions: false,
+            functions: vec![
+                "abs",
+                "acos",
+                "asin",
+                "atan",
+                "atan2",
+                "ceil",
+                "cos",
+                "exp",
+                "floor",
+                "ln",
+                "log",
+                "log10",
+                "log2",
+                "pow",
+                "power",
+                "round",
+                "sign",
+                "sin",
+                "sqrt",
+                "tan",
+                "coalesce",
+                "greatest",
+                "if",
+                "ifNull",
+                "least",
+                "multiIf",
+                "nullIf",
+                "concat",
+                "length",
+                "lower",
+                "lpad",
+                "ltrim",
+                "match",
+                "position",
+                "replaceAll",
+                "replaceRegexpAll",
+                "reverse",
+                "rpad",
+                "rtrim",
+                "substring",
+                "trim",
+                "upper",
+                "now",
+                "today",
+                "toDate",
+                "toDateTime",
+                "toStartOfDay",
+                "toStartOfHour",
+                "toStartOfMonth",
+                "toStartOfWeek",
+                "toStartOfYear",
+                "toUnixTimestamp",
+                "toYear",
+                "toMonth",
+                "toDayOfMonth",
+                "toHour",
+                "toFloat64",
+                "toInt64",
+                "toString",
+                // Aggregate functions
+                "any",
+                "argMax",
+                "argMin",
+                "avg",
+                "corr",
+                "count",
+                "covarPop",
+                "covarSamp",
+                "groupArray",
+                "max",
+                "median",
+                "min",
+                "quantile",
+                "quantileExact",
+                "stddevPop",
+                "stddevSamp",
+                "sum",
+                "uniq",
+                "uniqExact",
+                "varPop",
+                "varSamp",
+                // Window functions
+                "row_number",
+                "rank",
+                "dense_rank",
+                "percent_rank",
+                "cume_dist",
+                "ntile",
+                "lagInFrame",
+                "leadInFrame",
+                "first_value",
+                "last_value",
+                "nth_value",
+            ]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+            function_transforms,
+            data_types: data_types(&[
+                ("CHARACTER VARYING", "String", false),
+                ("TEXT", "String", false),
+                ("STRING", "String", false),
+                ("TINYINT", "Int8", false),
+                ("SMALLINT", "Int16", false),
+                ("INT", "Int32", false),
+                ("INTEGER", "Int32", false),
+                ("BIGINT", "Int64", false),
+                ("NUMERIC", "Decimal", true),
+                ("REAL", "Float32", false),
+                ("DOUBLE", "Float64", false),
+                ("BOOLEAN", "Bool", false),
+                ("DATE", "Date32", false),
+                ("DATETIME", "DateTime", false),
+                ("TIMESTAMP", "DateTime64(6)", false),
+                ("BYTEA", "String", false),
+            ]),
+            unknown_function_policy: UnknownFunctionPolicy::Error,
+            strict: false,
+            supports_aggregate_filter: false,
+            nulls_first_by_default: false,
+            supports_aggregate_order_by: false,
+            epoch_style: EpochStyle::UnixTimestamp,
+            cast_style: CastStyle::Cast,
+            try_cast_style: TryCastStyle::Unsupported,
+            supports_nulls_ordering: true,
+            string_concat: StringConcatStyle::Operator,
+            interval_multiplication: IntervalMultiplicationStyle::Quantity,
+            date_add_style: DateAddStyle::Operator,
+            date_part_style: DatePartStyle::Extract,
+            supports_boolean_coercion: true,
+            supports_ilike: true,
+            supports_like_any: false,
+            regex_match_style: RegexMatchStyle::Regexp,
+            array_style: ArrayStyle::Brackets,
+            array_index_style: ArrayIndexStyle::OneBased,
+            unnest_style: UnnestStyle::Offset,
+            lateral_style: LateralStyle::Lateral,
+            identifier_hook: None,
+            struct_style: StructStyle::Unsupported,
+            supports_correlated_select_subqueries: false,
+            supports_between_symmetric: false,
+            supports_window_frame_exclude: false,
+            supports_groups_frames: false,
+            dummy_from_table: None,
+            standard_offset_rows: false,
+            supports_table_sample: false,
+            supports_table_sample_repeatable: false,
+            limit_style: LimitStyle::Limit,
+            limit_allows_expression: false,
+            supports_optimizer_hints: false,
+            boolean_literal_style: BooleanLiteralStyle::Keyword,
+            upsert_style: UpsertStyle::Unsupported,
+            disallow_natural_join: true,
+            supports_create_or_replace_table: true,
+            supports_materialized_views: true,
+            explain_style: ExplainStyle::Options,
+            transaction_style: TransactionStyle::Unsupported,
+            supports_limit_by: true,
+            supports_query_settings: true,
+            max_render_depth: Some(DEFAULT_MAX_RENDER_DEPTH),
+            rewrite_null_comparisons: false,
+            rounding_result_type: None,
+            supports_wildcard_ilike: false,
+            supports_wildcard_rename: false,
+            modulo_style: ModuloStyle::Operator,
+            require_order_by_with_offset: false,
+            identifier_case: IdentifierCase::Sensitive,
+            require_derived_alias: false,
+            count_distinct_style: CountDistinctStyle::Native,
+            supports_unicode_strings: false,
+            supports_qualify: true,
+            omit_table_alias_as: false,
+            supports_top: false,
+            view_replace_style: ViewReplaceStyle::OrReplace,
+            supports_replace_materialized_view: false,
+            placeholder_style: PlaceholderStyle::QuestionMark,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SqLiteFloorTransform;
+impl FunctionTransform for SqLiteFloorTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!("round({} - 0.5)", &args[0]))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SqLiteCeilTransform;
+impl FunctionTransform for SqLiteCeilTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!("round({} + 0.5)", &args[0]))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SqLiteIsFiniteTransform;
+impl FunctionTransform for SqLiteIsFiniteTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!(
+            "{arg} NOT IN ('NaN', '-Inf', 'Inf')",
+            arg = &args[0]
+        ))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct SqLiteIsNanTransform;
+impl FunctionTransform for SqLiteIsNanTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!("{arg} = 'NaN'", arg = &args[0]))
+    }
+}
+
+/// Druid spells `date_trunc(unit, ts)` as `TIME_FLOOR(ts, period)`, where the
+/// period is an ISO 8601 duration
+#[derive(Clone, Debug)]
+struct DruidDateTruncTransform;
+impl FunctionTransform for DruidDateTruncTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let (unit, ts, tz) = date_trunc_args(name, args, dialect)?;
+        let period = match unit.as_str() {
+            "second" => "PT1S",
+            "minute" => "PT1M",
+            "hour" => "PT1H",
+            "day" => "P1D",
+            "week" => "P1W",
+            "month" => "P1M",
+            "quarter" => "P3M",
+            "year" => "P1Y",
+            _ => {
+                return Err(SqlGenError::DialectError(format!(
+                    "Unsupported {} unit for dialect: {}",
+                    name, unit
+                )))
+            }
+        };
+        match tz {
+            Some(tz) => Ok(format!("TIME_FLOOR({}, '{}', NULL, {})", ts, period, tz)),
+            None => Ok(format!("TIME_FLOOR({}, '{}')", ts, period)),
+        }
+    }
+}
+
+/// Postgres truncates in a time zone by converting the timestamp to it first,
+/// `date_trunc('day', ts AT TIME ZONE 'UTC')`. Calls without a time zone are
+/// rendered as written.
+#[derive(Clone, Debug)]
+struct PostgresDateTruncTransform;
+impl FunctionTransform for PostgresDateTruncTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let sql = args
+            .iter()
+            .map(|arg| arg.sql(dialect))
+            .collect::<Result<Vec<_>, SqlGenError>>()?;
+        match args {
+            [_, FunctionArg::Unnamed(FunctionArgExpr::Expr(ts)), _] => {
+                let ts = if ts.precedence() == u8::MAX {
+                    sql[1].clone()
+                } else {
+                    format!("({})", sql[1])
+                };
+                Ok(format!(
+                    "{}({}, {} AT TIME ZONE {})",
+                    name, sql[0], ts, sql[2]
+                ))
+            }
+            _ => Ok(format!("{}({})", name, sql.join(", "))),
+        }
+    }
+}
+
+/// BigQuery spells `date_trunc('day', ts, tz)` as `timestamp_trunc(ts, DAY, tz)`
+#[derive(Clone, Debug)]
+struct BigQueryTimestampTruncTransform;
+impl FunctionTransform for BigQueryTimestampTruncTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let (unit, ts, tz) = date_trunc_args(name, args, dialect)?;
+        if ![
+            "microsecond",
+            "millisecond",
+            "second",
+            "minute",
+            "hour",
+            "day",
+            "week",
+            "isoweek",
+            "month",
+            "quarter",
+            "year",
+            "isoyear",
+        ]
+        .contains(&unit.as_str())
+        {
+            return Err(SqlGenError::DialectError(format!(
+                "Unsupported {} unit for dialect: {}",
+                name, unit
+            )));
+        }
+        let unit = unit.to_ascii_uppercase();
+        match tz {
+            Some(tz) => Ok(format!("timestamp_trunc({}, {}, {})", ts, unit, tz)),
+            None => Ok(format!("timestamp_trunc({}, {})", ts, unit)),
+        }
+    }
+}
+
+/// Split the arguments of `date_trunc(unit, ts [, tz])` into the lowercase
+/// unit and the rendered timestamp and time zone. The timestamp is
+/// parenthesized unless it's delimited, so that it can be an operand.
+fn date_trunc_args(
+    name: &str,
+    args: &[FunctionArg],
+    dialect: &Dialect,
+) -> Result<(String, String, Option<String>), SqlGenError> {
+    let sql = sql_args(name, args, if args.len() == 3 { 3 } else { 2 }, dialect)?;
+    let unit = string_arg(&args[0]).ok_or_else(|| {
+        SqlGenError::DialectError(format!(
+            "The first argument to {} must be a string literal",
+            name
+        ))
+    })?;
+    let ts = match &args[1] {
+        FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) if expr.precedence() != u8::MAX => {
+            format!("({})", sql[1])
+        }
+        _ => sql[1].clone(),
+    };
+    let tz = match args.get(2) {
+        Some(arg) if string_arg(arg).is_none() => {
+            return Err(SqlGenError::DialectError(format!(
+                "The time zone argument to {} must be a string literal",
+                name
+            )))
+        }
+        Some(_) => Some(sql[2].clone()),
+        None => None,
+    };
+    Ok((unit.to_ascii_lowercase(), ts, tz))
+}
+
+/// Emulate `date_bin(stride, source [, origin])` for dialects without it by
+/// binning seconds since the epoch,
+/// `origin + floor((source - origin) / stride) * stride`. The stride must be an
+/// interval of a fixed length, and the origin defaults to the epoch.
+#[derive(Clone, Debug)]
+struct EpochDateBinTransform;
+impl FunctionTransform for EpochDateBinTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let n = if args.len() == 3 { 3 } else { 2 };
+        sql_args(name, args, n, dialect)?;
+        let exprs = args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) => Ok(expr.clone()),
+                _ => Err(SqlGenError::DialectError(format!(
+                    "Function {} expects unnamed arguments",
+                    name
+                ))),
+            })
+            .collect::<Result<Vec<_>, SqlGenError>>()?;
+        let stride = match &exprs[0] {
+            Expr::Value(interval @ Value::Interval { .. }) => interval_seconds(interval),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            SqlGenError::DialectError(format!(
+                "The stride of {} must be a positive interval of seconds, minutes, hours, days or weeks",
+                name
+            ))
+        })?;
+        let stride = Expr::Value(Value::Number(
+            stride.parse().map_err(|_| {
+                SqlGenError::DialectError(format!("Invalid stride for {}: {}", name, stride))
+            })?,
+            false,
+        ));
+        let epoch = |expr: &Expr| Expr::Extract {
+            field: DateTimeField::Epoch,
+            expr: Box::new(expr.clone()),
+        };
+        let source = epoch(&exprs[1]);
+        let origin = exprs.get(2).map(epoch);
+
+        let offset = match &origin {
+            Some(origin) => Expr::Nested(Box::new(Expr::BinaryOp {
+                left: Box::new(source),
+                op: BinaryOperator::Minus,
+                right: Box::new(origin.clone()),
+            })),
+            None => source,
+        };
+        let floor = Expr::Function(Function {
+            name: ObjectName(vec![Ident::new("floor")]),
+            args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(
+                Expr::BinaryOp {
+                    left: Box::new(offset),
+                    op: BinaryOperator::Divide,
+                    right: Box::new(stride.clone()),
+                },
+            ))],
+            over: None,
+            distinct: false,
+            order_by: vec![],
+            filter: None,
+            within_group: vec![],
+        });
+        let binned = Expr::BinaryOp {
+            left: Box::new(floor),
+            op: BinaryOperator::Multiply,
+            right: Box::new(stride),
+        };
+        let binned = match origin {
+            Some(origin) => Expr::BinaryOp {
+                left: Box::new(origin),
+                op: BinaryOperator::Plus,
+                right: Box::new(binned),
+            },
+            None => binned,
+        };
+
+        let binned = binned.sql(dialect)?;
+        Ok(match dialect.epoch_style {
+            EpochStyle::Extract => format!("to_timestamp({})", binned),
+            EpochStyle::UnixTimestamp => format!("FROM_UNIXTIME({})", binned),
+            EpochStyle::Strftime => format!("datetime({}, 'unixepoch')", binned),
+            EpochStyle::UnixSeconds => format!("TIMESTAMP_SECONDS({})", binned),
+            EpochStyle::ToUnixtime => format!("from_unixtime({})", binned),
+            EpochStyle::DateSubtraction => {
+                format!(
+                    "(DATE '1970-01-01' + NUMTODSINTERVAL({}, 'SECOND'))",
+                    binned
+                )
+            }
+            // DATEADD takes an int, which overflows in 2038
+            EpochStyle::DateDiffBig => {
+                return Err(dialect.unsupported(format!("Function {}", name)))
+            }
+        })
+    }
+}
+
+/// The length in seconds of a positive interval with a fixed length, as an
+/// exact decimal
+fn interval_seconds(interval: &Value) -> Option<String> {
+    let (quantity, unit) = interval_quantity(interval)?;
+    let unit_seconds: u128 = match unit {
+        DateTimeField::Second => 1,
+        DateTimeField::Minute => 60,
+        DateTimeField::Hour => 60 * 60,
+        DateTimeField::Day => 24 * 60 * 60,
+        DateTimeField::Week => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    // The quantity is a decimal without a sign or exponent, so it's scaled to
+    // an integer, multiplied, and scaled back
+    let quantity = quantity.strip_prefix('+').unwrap_or(&quantity);
+    let (whole, fraction) = quantity.split_once('.').unwrap_or((quantity, ""));
+    let scaled: u128 = format!("{}{}", whole, fraction).parse().ok()?;
+    let seconds = scaled.checked_mul(unit_seconds)?;
+    if seconds == 0 {
+        return None;
+    }
+    let digits = format!("{:0>width$}", seconds, width = fraction.len() + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - fraction.len());
+    let fraction = fraction.trim_end_matches('0');
+    Some(if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    })
+}
+
+/// The unit, start and end of `date_diff('unit', start, end)`, checking that
+/// the unit is one of `units`
+fn date_diff_args(
+    name: &str,
+    args: &[FunctionArg],
+    units: &[&str],
+    dialect: &Dialect,
+) -> Result<(String, String, String), SqlGenError> {
+    let sql = sql_args(name, args, 3, dialect)?;
+    let unit = string_arg(&args[0])
+        .ok_or_else(|| {
+            SqlGenError::DialectError(format!(
+                "The first argument to {} must be a string literal",
+                name
+            ))
+        })?
+        .to_ascii_lowercase();
+    if !units.contains(&unit.as_str()) {
+        return Err(SqlGenError::DialectError(format!(
+            "Unsupported {} unit for dialect: {}",
+            name, unit
+        )));
+    }
+    Ok((unit, sql[1].clone(), sql[2].clone()))
+}
+
+/// `date_diff('unit', start, end)` spelled `function(UNIT, start, end)`, as in
+/// MySQL's `TIMESTAMPDIFF`
+#[derive(Clone, Debug)]
+struct DateDiffTransform {
+    function: &'static str,
+    units: &'static [&'static str],
+}
+impl FunctionTransform for DateDiffTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let (unit, start, end) = date_diff_args(name, args, self.units, dialect)?;
+        Ok(format!(
+            "{}({}, {}, {})",
+            self.function,
+            unit.to_ascii_uppercase(),
+            start,
+            end
+        ))
+    }
+}
+
+/// `date_diff('unit', start, end)` as the whole units in the microseconds
+/// between `start` and `end`, for SQL Server's `DATEDIFF_BIG` and Redshift's
+/// `DATEDIFF`. Both count the unit boundaries crossed, so a day from 23:59 to
+/// 00:01 would be 1 rather than 0. Units of varying length, such as months,
+/// are not supported.
+#[derive(Clone, Debug)]
+struct ElapsedDateDiffTransform {
+    function: &'static str,
+}
+impl FunctionTransform for ElapsedDateDiffTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let units = [
+            "microsecond",
+            "millisecond",
+            "second",
+            "minute",
+            "hour",
+            "day",
+            "week",
+        ];
+        let (unit, start, end) = date_diff_args(name, args, &units, dialect)?;
+        let microseconds = format!("{}(MICROSECOND, {}, {})", self.function, start, end);
+        Ok(match unit.as_str() {
+            "microsecond" => microseconds,
+            "millisecond" => format!("({} / 1000)", microseconds),
+            "second" => format!("({} / 1000000)", microseconds),
+            "minute" => format!("({} / 60000000)", microseconds),
+            "hour" => format!("({} / 3600000000)", microseconds),
+            "day" => format!("({} / 86400000000)", microseconds),
+            _ => format!("({} / 604800000000)", microseconds),
+        })
+    }
+}
+
+/// Postgres subtracts timestamps to an interval, so `date_diff('unit', start,
+/// end)` is the whole number of units in the seconds of `end - start`. Units
+/// of varying length, such as months, are not supported.
+#[derive(Clone, Debug)]
+struct PostgresDateDiffTransform;
+impl FunctionTransform for PostgresDateDiffTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let units = ["second", "minute", "hour", "day", "week"];
+        let (unit, start, end) = date_diff_args(name, args, &units, dialect)?;
+        let start = match &args[1] {
+            FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) if expr.precedence() != u8::MAX => {
+                format!("({})", start)
+            }
+            _ => start,
+        };
+        let seconds = format!("EXTRACT(EPOCH FROM ({} - {}))", end, start);
+        Ok(match unit.as_str() {
+            "second" => format!("trunc({})", seconds),
+            "minute" => format!("trunc({} / 60)", seconds),
+            "hour" => format!("trunc({} / 3600)", seconds),
+            "day" => format!("trunc({} / 86400)", seconds),
+            _ => format!("trunc({} / 604800)", seconds),
+        })
+    }
+}
+
+/// MySQL spells `string_agg(x, sep [ORDER BY ...])` as
+/// `GROUP_CONCAT(x [ORDER BY ...] SEPARATOR sep)`, since extra arguments to
+/// `GROUP_CONCAT` are concatenated with the first
+#[derive(Clone, Debug)]
+struct MySqlGroupConcatTransform;
+impl FunctionTransform for MySqlGroupConcatTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let sql = sql_args(name, args, 2, dialect)?;
+        Ok(format!("GROUP_CONCAT({} SEPARATOR {})", sql[0], sql[1]))
+    }
+
+    fn transform_function(
+        &self,
+        name: &str,
+        function: &Function,
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let sql = sql_args(name, &function.args, 2, dialect)?;
+        let order_by = function
+            .order_by
+            .iter()
+            .map(|order_by| order_by.sql(dialect))
+            .collect::<Result<Vec<_>, SqlGenError>>()?;
+        Ok(format!(
+            "GROUP_CONCAT({}{}{} SEPARATOR {})",
+            if function.distinct { "DISTINCT " } else { "" },
+            sql[0],
+            if order_by.is_empty() {
+                String::new()
+            } else {
+                format!(" ORDER BY {}", order_by.join(", "))
+            },
+            sql[1]
+        ))
+    }
+}
+
+/// `string_agg(x, sep [ORDER BY ...])` spelled
+/// `listagg(x, sep) WITHIN GROUP (ORDER BY ...)`, as in Oracle, Redshift and
+/// Snowflake
+#[derive(Clone, Debug)]
+struct ListAggTransform;
+impl FunctionTransform for ListAggTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let sql = sql_args(name, args, 2, dialect)?;
+        Ok(format!("listagg({}, {})", sql[0], sql[1]))
+    }
+
+    fn transform_function(
+        &self,
+        name: &str,
+        function: &Function,
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        if !function.order_by.is_empty() && !function.within_group.is_empty() {
+            return Err(dialect.unsupported(format!(
+                "Function {} with both ORDER BY and WITHIN GROUP",
+                name
+            )));
+        }
+        let sql = sql_args(name, &function.args, 2, dialect)?;
+        let order_by = function
+            .order_by
+            .iter()
+            .map(|order_by| order_by.sql(dialect))
+            .collect::<Result<Vec<_>, SqlGenError>>()?;
+        Ok(format!(
+            "listagg({}{}, {}){}",
+            if function.distinct { "DISTINCT " } else { "" },
+            sql[0],
+            sql[1],
+            if order_by.is_empty() {
+                String::new()
+            } else {
+                format!(" WITHIN GROUP (ORDER BY {})", order_by.join(", "))
+            }
+        ))
+    }
+}
+
+/// Rename a function, passing its arguments through unchanged
+#[derive(Clone, Debug)]
+struct RenameTransform(&'static str);
+impl FunctionTransform for RenameTransform {
+    fn transform(
+        &self,
+        _name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = args
+            .iter()
+            .map(|arg| arg.sql(dialect))
+            .collect::<Result<Vec<_>, SqlGenError>>()?;
+        Ok(format!("{}({})", self.0, args.join(", ")))
+    }
+}
+
+/// `md5(s)` as a lowercase hex string, since Trino's `md5` hashes `varbinary`
+/// to `varbinary`
+#[derive(Clone, Debug)]
+struct TrinoMd5Transform;
+impl FunctionTransform for TrinoMd5Transform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!("lower(to_hex(md5(to_utf8({}))))", &args[0]))
+    }
+}
+
+/// `median(x)` as the approximate median `percentile_approx(x, 0.5)`, as in
+/// Spark
+#[derive(Clone, Debug)]
+struct SparkMedianTransform;
+impl FunctionTransform for SparkMedianTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!("percentile_approx({}, 0.5)", &args[0]))
+    }
+}
+
+/// `approx_distinct(x)` spelled `APPROXIMATE COUNT(DISTINCT x)`, as in Redshift
+#[derive(Clone, Debug)]
+struct ApproximateCountDistinctTransform;
+impl FunctionTransform for ApproximateCountDistinctTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        let args = sql_args(name, args, 1, dialect)?;
+        Ok(format!("APPROXIMATE COUNT(DISTINCT {})", &args[0]))
+    }
+}
+
+/// Reject calls to a function that a dialect has no equivalent for, regardless
+/// of `unknown_function_policy`
+#[derive(Clone, Debug)]
+struct UnsupportedTransform;
+impl FunctionTransform for UnsupportedTransform {
+    fn transform(
+        &self,
+        name: &str,
+        _args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        Err(dialect.unsupported(format!("Function {}", name)))
+    }
+
+    fn is_supported(&self) -> bool {
+        false
+    }
+}
+
+/// `regexp_replace(s, pattern, replacement[, flags])` for dialects whose
+/// `regexp_replace` takes no flags and always replaces every match. Only the
+/// global flag `'g'` can be expressed, by dropping it.
+#[derive(Clone, Debug)]
+struct GlobalRegexpReplaceTransform;
+impl FunctionTransform for GlobalRegexpReplaceTransform {
+    fn transform(
+        &self,
+        name: &str,
+        args: &[FunctionArg],
+        dialect: &Dialect,
+    ) -> Result<String, SqlGenError> {
+        if args.len() == 3 {
+            // Without the global flag only the first match is replaced
+            return Err(dialect.unsupported(format!("Function {} without the 'g' flag", name)));
+        }
+        let sql = sql_args(name, args, 4, dialect)?;
+        match string_arg(&args[3]) {
+            Some("g") => {}
+            Some(flags) => {
+                return Err(SqlGenError::DialectError(format!(
+                    "Unsupported {} flags for dialect: {}",
+                    name, flags
+                )))
+            }
+            None => {
+                return Err(SqlGenError::DialectError(format!(
+                    "The flags argument to {} must be a string literal",
+                    name
+                )))
+            }
         }
+        Ok(format!(
+            "REGEXP_REPLACE({}, {}, {})",
+            sql[0], sql[1], sql[2]
+        ))
     }
 }
 
 pub trait DialectDisplay {
-    fn fmt(&self, f: &mut (dyn fmt::Write), dialect: &Dialect) -> Result<(), SqlGenError>;
+    fn fmt(&self, f: &mut dyn fmt::Write, dialect: &Dialect) -> Result<(), SqlGenError>;
 
     fn sql(&self, dialect: &Dialect) -> Result<String, SqlGenError>
     where
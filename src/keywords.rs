@@ -12,17 +12,17 @@
 
 //! This module defines
 //! 1) a list of constants for every keyword that
-//! can appear in [Word::keyword]:
+//!    can appear in [Word::keyword]:
 //!    pub const KEYWORD = "KEYWORD"
 //! 2) an `ALL_KEYWORDS` array with every keyword in it
-//!     This is not a list of *reserved* keywords: some of these can be
-//!     parsed as identifiers if the parser decides so. This means that
-//!     new keywords can be added here without affecting the parse result.
+//!    This is not a list of *reserved* keywords: some of these can be
+//!    parsed as identifiers if the parser decides so. This means that
+//!    new keywords can be added here without affecting the parse result.
 //!
-//!     As a matter of fact, most of these keywords are not used at all
-//!     and could be removed.
+//!    As a matter of fact, most of these keywords are not used at all
+//!    and could be removed.
 //! 3) a `RESERVED_FOR_TABLE_ALIAS` array with keywords reserved in a
-//! "table alias" context.
+//!    "table alias" context.
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -98,6 +98,7 @@ define_keywords!(
     BEGIN,
     BEGIN_FRAME,
     BEGIN_PARTITION,
+    BERNOULLI,
     BETWEEN,
     BIGINT,
     BINARY,
@@ -216,6 +217,7 @@ define_keywords!(
     EVENT,
     EVERY,
     EXCEPT,
+    EXCLUDE,
     EXEC,
     EXECUTE,
     EXISTS,
@@ -286,6 +288,8 @@ define_keywords!(
     ISOYEAR,
     JAR,
     JOIN,
+    JSON,
+    JSONB,
     JSONFILE,
     JULIAN,
     KEY,
@@ -362,6 +366,8 @@ define_keywords!(
     OR,
     ORC,
     ORDER,
+    ORDINALITY,
+    OTHERS,
     OUT,
     OUTER,
     OUTPUTFORMAT,
@@ -439,6 +445,7 @@ define_keywords!(
     ROWID,
     ROWS,
     ROW_NUMBER,
+    SAFE_CAST,
     SAVEPOINT,
     SCHEMA,
     SCOPE,
@@ -602,6 +609,7 @@ pub const RESERVED_FOR_TABLE_ALIAS: &[Keyword] = &[
     Keyword::OUTER,
     Keyword::SET,
     Keyword::QUALIFY,
+    Keyword::TABLESAMPLE,
 ];
 
 /// Can't be used as a column alias, so that `SELECT <expr> alias`
@@ -18,7 +18,7 @@
 //! into an Abstract Syntax Tree (AST).
 //!
 //! ```
-//! use sqlparser::parser::Parser;
+//! use sqlgen::parser::Parser;
 //!
 //! let sql = "SELECT a, b, 123, myfunc(b) \
 //!            FROM table_1 \
@@ -35,6 +35,21 @@ pub enum SqlGenError {
     ParserError(String),
     FormatError(fmt::Error),
     DialectError(String),
+    RewriteError(String),
+    /// Rendering nested queries, set operations and expressions deeper than
+    /// the dialect's `max_render_depth`
+    DepthExceeded(usize),
+    /// A feature that the dialect, named by `dialect`, can't express
+    UnsupportedFeature {
+        feature: String,
+        dialect: String,
+    },
+    /// An identifier that can't be rendered, such as an empty one
+    InvalidIdentifier(String),
+    /// `VALUES` without any rows
+    EmptyValues,
+    /// `SELECT` without any items in its projection
+    EmptyProjection,
 }
 
 impl From<fmt::Error> for SqlGenError {
@@ -107,6 +122,17 @@ impl fmt::Display for SqlGenError {
                 SqlGenError::ParserError(s) => s.clone(),
                 SqlGenError::FormatError(err) => err.to_string(),
                 SqlGenError::DialectError(s) => s.clone(),
+                SqlGenError::RewriteError(s) => s.clone(),
+                SqlGenError::DepthExceeded(depth) =>
+                    format!("Rendering exceeded the maximum depth of {}", depth),
+                SqlGenError::UnsupportedFeature { feature, dialect } if dialect.is_empty() =>
+                    format!("{} is not supported by dialect", feature),
+                SqlGenError::UnsupportedFeature { feature, dialect } =>
+                    format!("{} is not supported by dialect {}", feature, dialect),
+                SqlGenError::InvalidIdentifier(ident) => format!("Invalid identifier {:?}", ident),
+                SqlGenError::EmptyValues => "VALUES must have at least one row".to_string(),
+                SqlGenError::EmptyProjection =>
+                    "SELECT must have at least one item in its projection".to_string(),
             }
         )
     }
@@ -245,7 +271,7 @@ impl Parser {
                 }
                 Keyword::CASE => self.parse_case_expr(),
                 Keyword::CAST => self.parse_cast_expr(),
-                Keyword::TRY_CAST => self.parse_try_cast_expr(),
+                Keyword::TRY_CAST | Keyword::SAFE_CAST => self.parse_try_cast_expr(),
                 Keyword::EXISTS => self.parse_exists_expr(false),
                 Keyword::EXTRACT => self.parse_extract_expr(),
                 Keyword::POSITION => self.parse_position_expr(),
@@ -358,7 +384,38 @@ impl Parser {
     pub fn parse_function(&mut self, name: ObjectName) -> Result<Expr, SqlGenError> {
         self.expect_token(&Token::LParen)?;
         let distinct = self.parse_all_or_distinct()?;
-        let args = self.parse_optional_args()?;
+        let (args, order_by) = if self.consume_token(&Token::RParen) {
+            (vec![], vec![])
+        } else {
+            let args = self.parse_comma_separated(Parser::parse_function_args)?;
+            let order_by = if self.parse_keywords(&[Keyword::ORDER, Keyword::BY]) {
+                self.parse_comma_separated(Parser::parse_order_by_expr)?
+            } else {
+                vec![]
+            };
+            self.expect_token(&Token::RParen)?;
+            (args, order_by)
+        };
+        let within_group = if self.parse_keywords(&[Keyword::WITHIN, Keyword::GROUP]) {
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[Keyword::ORDER, Keyword::BY])?;
+            let order_by_expr = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+            order_by_expr
+        } else {
+            vec![]
+        };
+        let filter = match (self.peek_token(), self.peek_nth_token(1)) {
+            (Token::Word(w), Token::LParen) if w.keyword == Keyword::FILTER => {
+                self.next_token();
+                self.expect_token(&Token::LParen)?;
+                self.expect_keyword(Keyword::WHERE)?;
+                let filter = self.parse_expr()?;
+                self.expect_token(&Token::RParen)?;
+                Some(Box::new(filter))
+            }
+            _ => None,
+        };
         let over = if self.parse_keyword(Keyword::OVER) {
             // TBD: support window names (`OVER mywin`) in place of inline specification
             self.expect_token(&Token::LParen)?;
@@ -395,6 +452,9 @@ impl Parser {
             args,
             over,
             distinct,
+            order_by,
+            filter,
+            within_group,
         }))
     }
 
@@ -409,6 +469,9 @@ impl Parser {
             args,
             over: None,
             distinct: false,
+            order_by: vec![],
+            filter: None,
+            within_group: vec![],
         }))
     }
 
@@ -434,13 +497,35 @@ impl Parser {
         } else {
             (self.parse_window_frame_bound()?, None)
         };
+        let exclude = if self.parse_keyword(Keyword::EXCLUDE) {
+            Some(self.parse_window_frame_exclude()?)
+        } else {
+            None
+        };
         Ok(WindowFrame {
             units,
             start_bound,
             end_bound,
+            exclude,
         })
     }
 
+    /// Parse what follows `EXCLUDE` in a window frame: `CURRENT ROW`, `GROUP`,
+    /// `TIES` or `NO OTHERS`
+    pub fn parse_window_frame_exclude(&mut self) -> Result<WindowFrameExclude, SqlGenError> {
+        if self.parse_keywords(&[Keyword::CURRENT, Keyword::ROW]) {
+            Ok(WindowFrameExclude::CurrentRow)
+        } else if self.parse_keyword(Keyword::GROUP) {
+            Ok(WindowFrameExclude::Group)
+        } else if self.parse_keyword(Keyword::TIES) {
+            Ok(WindowFrameExclude::Ties)
+        } else if self.parse_keywords(&[Keyword::NO, Keyword::OTHERS]) {
+            Ok(WindowFrameExclude::NoOthers)
+        } else {
+            self.expected("CURRENT ROW, GROUP, TIES or NO OTHERS", self.peek_token())
+        }
+    }
+
     /// Parse `CURRENT ROW` or `{ <positive number> | UNBOUNDED } { PRECEDING | FOLLOWING }`
     pub fn parse_window_frame_bound(&mut self) -> Result<WindowFrameBound, SqlGenError> {
         if self.parse_keywords(&[Keyword::CURRENT, Keyword::ROW]) {
@@ -561,7 +646,8 @@ impl Parser {
         })
     }
 
-    /// Parse a SQL TRY_CAST function e.g. `TRY_CAST(expr AS FLOAT)`
+    /// Parse a SQL TRY_CAST function e.g. `TRY_CAST(expr AS FLOAT)`, or its
+    /// BigQuery spelling `SAFE_CAST(expr AS FLOAT)`
     pub fn parse_try_cast_expr(&mut self) -> Result<Expr, SqlGenError> {
         self.expect_token(&Token::LParen)?;
         let expr = self.parse_expr()?;
@@ -611,7 +697,7 @@ impl Parser {
                 r#in: Box::new(from),
             })
         } else {
-            return parser_err!("Position function must include IN keyword".to_string());
+            parser_err!("Position function must include IN keyword".to_string())
         }
     }
 
@@ -643,10 +729,7 @@ impl Parser {
         self.expect_token(&Token::LParen)?;
         let mut where_expr = None;
         if let Token::Word(word) = self.peek_token() {
-            if [Keyword::BOTH, Keyword::LEADING, Keyword::TRAILING]
-                .iter()
-                .any(|d| word.keyword == *d)
-            {
+            if [Keyword::BOTH, Keyword::LEADING, Keyword::TRAILING].contains(&word.keyword) {
                 let trim_where = self.parse_trim_where()?;
                 let sub_expr = self.parse_expr()?;
                 self.expect_keyword(Keyword::FROM)?;
@@ -827,8 +910,13 @@ impl Parser {
         // inside the value string.)
 
         // The first token in an interval is a string literal which specifies
-        // the duration of the interval.
-        let value = self.parse_expr()?;
+        // the duration of the interval. MySQL and BigQuery also allow an
+        // expression, but a string literal ends the value so that operators
+        // after it apply to the interval, as in `INTERVAL '1 hour' * n`.
+        let value = match self.peek_token() {
+            Token::SingleQuotedString(_) => self.parse_prefix()?,
+            _ => self.parse_expr()?,
+        };
 
         // Following the string literal is a qualifier which indicates the units
         // of the duration specified in the string literal.
@@ -861,8 +949,7 @@ impl Parser {
                     Keyword::TIMEZONE_HOUR,
                     Keyword::TIMEZONE_MINUTE,
                 ]
-                .iter()
-                .any(|d| kw.keyword == *d) =>
+                .contains(&kw.keyword) =>
             {
                 Some(self.parse_date_time_field()?)
             }
@@ -952,6 +1039,26 @@ impl Parser {
         if let Some(op) = regular_binary_operator {
             if let Some(keyword) = self.parse_one_of_keywords(&[Keyword::ANY, Keyword::ALL]) {
                 self.expect_token(&Token::LParen)?;
+                if keyword == Keyword::ANY
+                    && matches!(op, BinaryOperator::Like | BinaryOperator::ILike)
+                {
+                    // `LIKE ANY` takes a list of patterns, as in Snowflake
+                    let mut patterns = self.parse_comma_separated(Parser::parse_expr)?;
+                    self.expect_token(&Token::RParen)?;
+                    return Ok(if patterns.len() == 1 {
+                        Expr::BinaryOp {
+                            left: Box::new(expr),
+                            op,
+                            right: Box::new(Expr::AnyOp(Box::new(patterns.remove(0)))),
+                        }
+                    } else {
+                        Expr::LikeAny {
+                            expr: Box::new(expr),
+                            case_insensitive: op == BinaryOperator::ILike,
+                            patterns,
+                        }
+                    });
+                }
                 let right = self.parse_subexpr(precedence)?;
                 self.expect_token(&Token::RParen)?;
 
@@ -1120,6 +1227,8 @@ impl Parser {
 
     /// Parses `BETWEEN <low> AND <high>`, assuming the `BETWEEN` keyword was already consumed
     pub fn parse_between(&mut self, expr: Expr, negated: bool) -> Result<Expr, SqlGenError> {
+        let symmetric = self.parse_one_of_keywords(&[Keyword::SYMMETRIC, Keyword::ASYMMETRIC])
+            == Some(Keyword::SYMMETRIC);
         // Stop parsing subexpressions for <low> and <high> on tokens with
         // precedence lower than that of `BETWEEN`, such as `AND`, `IS`, etc.
         let low = self.parse_subexpr(Self::BETWEEN_PREC)?;
@@ -1130,6 +1239,7 @@ impl Parser {
             negated,
             low: Box::new(low),
             high: Box::new(high),
+            symmetric,
         })
     }
 
@@ -1408,7 +1518,7 @@ impl Parser {
         let all = self.parse_keyword(Keyword::ALL);
         let distinct = self.parse_keyword(Keyword::DISTINCT);
         if all && distinct {
-            return parser_err!("Cannot specify both ALL and DISTINCT".to_string());
+            parser_err!("Cannot specify both ALL and DISTINCT".to_string())
         } else {
             Ok(distinct)
         }
@@ -1540,7 +1650,11 @@ impl Parser {
     /// Parse a literal string
     pub fn parse_literal_string(&mut self) -> Result<String, SqlGenError> {
         match self.next_token() {
-            Token::Word(Word { value, keyword, .. }) if keyword == Keyword::NoKeyword => Ok(value),
+            Token::Word(Word {
+                value,
+                keyword: Keyword::NoKeyword,
+                ..
+            }) => Ok(value),
             Token::SingleQuotedString(s) => Ok(s),
             Token::DoubleQuotedString(s) => Ok(s),
             Token::EscapedStringLiteral(s) => Ok(s),
@@ -1651,6 +1765,8 @@ impl Parser {
                 Keyword::STRING => Ok(DataType::String),
                 Keyword::TEXT => Ok(DataType::Text),
                 Keyword::BYTEA => Ok(DataType::Bytea),
+                Keyword::JSON => Ok(DataType::Json),
+                Keyword::JSONB => Ok(DataType::Jsonb),
                 Keyword::NUMERIC | Keyword::DECIMAL | Keyword::DEC => {
                     let (precision, scale) = self.parse_optional_precision_scale()?;
                     Ok(DataType::Decimal(precision, scale))
@@ -1883,12 +1999,24 @@ impl Parser {
             vec![]
         };
 
+        let mut limit_by = None;
         let mut limit = None;
         let mut offset = None;
 
         for _x in 0..2 {
             if limit.is_none() && self.parse_keyword(Keyword::LIMIT) {
-                limit = self.parse_limit()?
+                limit = self.parse_limit()?;
+                if limit_by.is_none() && self.parse_keyword(Keyword::BY) {
+                    // ClickHouse `LIMIT n BY expr`, which may be followed by
+                    // the query's own limit
+                    limit_by = Some(LimitBy {
+                        limit: limit.take().ok_or_else(|| {
+                            SqlGenError::ParserError("Expected a limit before BY".to_string())
+                        })?,
+                        by: self.parse_comma_separated(Parser::parse_expr)?,
+                    });
+                    continue;
+                }
             }
 
             if offset.is_none() && self.parse_keyword(Keyword::OFFSET) {
@@ -1917,10 +2045,12 @@ impl Parser {
             with,
             body,
             order_by,
+            limit_by,
             limit,
             offset,
             fetch,
             lock,
+            settings: vec![],
         })
     }
 
@@ -2106,23 +2236,17 @@ impl Parser {
             vec![]
         };
 
-        let cluster_by = if self.parse_keywords(&[Keyword::CLUSTER, Keyword::BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
-        } else {
-            vec![]
-        };
-
-        let distribute_by = if self.parse_keywords(&[Keyword::DISTRIBUTE, Keyword::BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
-        } else {
-            vec![]
-        };
-
-        let sort_by = if self.parse_keywords(&[Keyword::SORT, Keyword::BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
-        } else {
-            vec![]
-        };
+        // Hive's CLUSTER BY, DISTRIBUTE BY and SORT BY have no place in the
+        // AST, and dropping them would change the meaning of the query
+        for (keywords, clause) in [
+            (&[Keyword::CLUSTER, Keyword::BY], "CLUSTER BY"),
+            (&[Keyword::DISTRIBUTE, Keyword::BY], "DISTRIBUTE BY"),
+            (&[Keyword::SORT, Keyword::BY], "SORT BY"),
+        ] {
+            if self.parse_keywords(keywords) {
+                return parser_err!(format!("{} is not supported", clause));
+            }
+        }
 
         let having = if self.parse_keyword(Keyword::HAVING) {
             Some(self.parse_expr()?)
@@ -2137,6 +2261,7 @@ impl Parser {
         };
 
         Ok(Select {
+            hints: vec![],
             distinct,
             top,
             projection,
@@ -2146,6 +2271,7 @@ impl Parser {
             selection,
             group_by,
             having,
+            qualify,
         })
     }
 
@@ -2317,9 +2443,12 @@ impl Parser {
             }
         } else if self.parse_keyword(Keyword::UNNEST) {
             self.expect_token(&Token::LParen)?;
-            let expr = self.parse_expr()?;
+            let array_exprs = self.parse_comma_separated(Parser::parse_expr)?;
             self.expect_token(&Token::RParen)?;
 
+            // PostgreSQL's `WITH ORDINALITY` comes before the alias
+            let with_ordinality = self.parse_keywords(&[Keyword::WITH, Keyword::ORDINALITY]);
+
             let alias = match self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS) {
                 Ok(Some(alias)) => Some(alias),
                 Ok(None) => None,
@@ -2340,9 +2469,10 @@ impl Parser {
 
             Ok(TableFactor::UNNEST {
                 alias,
-                array_expr: Box::new(expr),
+                array_exprs,
                 with_offset,
                 with_offset_alias,
+                with_ordinality,
             })
         } else {
             let name = self.parse_object_name()?;
@@ -2353,6 +2483,11 @@ impl Parser {
                 None
             };
             let alias = self.parse_optional_table_alias(keywords::RESERVED_FOR_TABLE_ALIAS)?;
+            let sample = if self.parse_keyword(Keyword::TABLESAMPLE) {
+                Some(Box::new(self.parse_table_sample()?))
+            } else {
+                None
+            };
             // MSSQL-specific table hints:
             let mut with_hints = vec![];
             if self.parse_keyword(Keyword::WITH) {
@@ -2369,10 +2504,37 @@ impl Parser {
                 alias,
                 args,
                 with_hints,
+                sample,
             })
         }
     }
 
+    /// Parse what follows `TABLESAMPLE`: `{ BERNOULLI | SYSTEM } (<percentage>)
+    /// [ REPEATABLE (<seed>) ]`
+    pub fn parse_table_sample(&mut self) -> Result<TableSample, SqlGenError> {
+        let method = match self.parse_one_of_keywords(&[Keyword::BERNOULLI, Keyword::SYSTEM]) {
+            Some(Keyword::BERNOULLI) => TableSampleMethod::Bernoulli,
+            Some(Keyword::SYSTEM) => TableSampleMethod::System,
+            _ => return self.expected("BERNOULLI or SYSTEM", self.peek_token()),
+        };
+        self.expect_token(&Token::LParen)?;
+        let percentage = self.parse_expr()?;
+        self.expect_token(&Token::RParen)?;
+        let repeatable = if self.parse_keyword(Keyword::REPEATABLE) {
+            self.expect_token(&Token::LParen)?;
+            let seed = self.parse_expr()?;
+            self.expect_token(&Token::RParen)?;
+            Some(seed)
+        } else {
+            None
+        };
+        Ok(TableSample {
+            method,
+            percentage,
+            repeatable,
+        })
+    }
+
     pub fn parse_derived_table_factor(
         &mut self,
         lateral: IsLateral,
@@ -2445,11 +2607,43 @@ impl Parser {
                     Some(alias) => SelectItem::ExprWithAlias { expr, alias },
                     None => SelectItem::UnnamedExpr(expr),
                 }),
-            WildcardExpr::QualifiedWildcard(prefix) => Ok(SelectItem::QualifiedWildcard(prefix)),
-            WildcardExpr::Wildcard => Ok(SelectItem::Wildcard),
+            WildcardExpr::QualifiedWildcard(prefix) => Ok(SelectItem::QualifiedWildcard(
+                prefix,
+                self.parse_wildcard_options()?,
+            )),
+            WildcardExpr::Wildcard => Ok(SelectItem::Wildcard(self.parse_wildcard_options()?)),
         }
     }
 
+    /// Parse the modifiers that may follow a wildcard in a projection
+    pub fn parse_wildcard_options(&mut self) -> Result<WildcardOptions, SqlGenError> {
+        let ilike = if self.parse_keyword(Keyword::ILIKE) {
+            match self.next_token() {
+                Token::SingleQuotedString(pattern) => Some(pattern),
+                unexpected => return self.expected("a pattern after ILIKE", unexpected),
+            }
+        } else {
+            None
+        };
+        let rename = if self.parse_keyword(Keyword::RENAME) {
+            let parse_rename = |parser: &mut Parser| {
+                let column = parser.parse_identifier()?;
+                parser.expect_keyword(Keyword::AS)?;
+                Ok((column, parser.parse_identifier()?))
+            };
+            if self.consume_token(&Token::LParen) {
+                let rename = self.parse_comma_separated(parse_rename)?;
+                self.expect_token(&Token::RParen)?;
+                rename
+            } else {
+                vec![parse_rename(self)?]
+            }
+        } else {
+            vec![]
+        };
+        Ok(WildcardOptions { ilike, rename })
+    }
+
     /// Parse an expression, optionally followed by ASC or DESC (used in ORDER BY)
     pub fn parse_order_by_expr(&mut self) -> Result<OrderByExpr, SqlGenError> {
         let expr = self.parse_expr()?;
@@ -2504,7 +2698,7 @@ impl Parser {
         if self.parse_keyword(Keyword::ALL) {
             Ok(None)
         } else {
-            Ok(Some(Expr::Value(self.parse_number_value()?)))
+            Ok(Some(self.parse_expr()?))
         }
     }
 
@@ -2651,6 +2845,7 @@ impl Word {
         Ident {
             value: self.value.clone(),
             quote_style: self.quote_style,
+            force_quote: false,
         }
     }
 }
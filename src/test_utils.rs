@@ -118,6 +118,7 @@ pub fn table(name: impl Into<String>) -> TableFactor {
         alias: None,
         args: None,
         with_hints: vec![],
+        sample: None,
     }
 }
 
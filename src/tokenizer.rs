@@ -32,24 +32,17 @@ use core::str::Chars;
 use serde::{Deserialize, Serialize};
 
 use crate::keywords::{Keyword, ALL_KEYWORDS, ALL_KEYWORDS_INDEX};
-use crate::parser::SqlGenError;
 
 fn is_delimited_identifier_start(ch: char) -> bool {
     ch == '`' || ch == '"'
 }
 
 fn is_identifier_start(ch: char) -> bool {
-    ('a'..='z').contains(&ch) || ('A'..='Z').contains(&ch) || ch == '_' || ch == '#' || ch == '@'
+    ch.is_ascii_alphabetic() || ch == '_' || ch == '#' || ch == '@'
 }
 
 fn is_identifier_part(ch: char) -> bool {
-    ('a'..='z').contains(&ch)
-        || ('A'..='Z').contains(&ch)
-        || ('0'..='9').contains(&ch)
-        || ch == '@'
-        || ch == '$'
-        || ch == '#'
-        || ch == '_'
+    ch.is_ascii_alphanumeric() || ch == '@' || ch == '$' || ch == '#' || ch == '_'
 }
 
 /// SQL Token enumeration
@@ -246,7 +239,7 @@ impl Token {
         Token::Word(Word {
             value: word.to_string(),
             quote_style,
-            keyword: if quote_style == None {
+            keyword: if quote_style.is_none() {
                 let keyword = ALL_KEYWORDS.binary_search(&word_uppercase.as_str());
                 keyword.map_or(Keyword::NoKeyword, |x| ALL_KEYWORDS_INDEX[x])
             } else {
@@ -369,10 +362,10 @@ impl<'a> Tokenizer<'a> {
                 }
 
                 Token::Whitespace(Whitespace::Tab) => self.col += 4,
-                Token::Word(w) if w.quote_style == None => {
+                Token::Word(w) if w.quote_style.is_none() => {
                     self.col += w.value.chars().count() as u64
                 }
-                Token::Word(w) if w.quote_style != None => {
+                Token::Word(w) if w.quote_style.is_some() => {
                     self.col += w.value.chars().count() as u64 + 2
                 }
                 Token::Number(s, _) => self.col += s.chars().count() as u64,
@@ -454,7 +447,7 @@ impl<'a> Tokenizer<'a> {
                     chars.next(); // consume the first char
                     let s = self.tokenize_word(ch, chars);
 
-                    if s.chars().all(|x| ('0'..='9').contains(&x) || x == '.') {
+                    if s.chars().all(|x: char| x.is_ascii_digit() || x == '.') {
                         let mut s = peeking_take_while(&mut s.chars().peekable(), |ch| {
                             matches!(ch, '0'..='9' | '.')
                         });
@@ -493,15 +486,12 @@ impl<'a> Tokenizer<'a> {
                 }
                 // numbers and period
                 '0'..='9' | '.' => {
-                    let mut s = peeking_take_while(chars, |ch| matches!(ch, '0'..='9'));
+                    let mut s = peeking_take_while(chars, |ch: char| ch.is_ascii_digit());
 
                     // match binary literal that starts with 0x
                     if s == "0" && chars.peek() == Some(&'x') {
                         chars.next();
-                        let s2 = peeking_take_while(
-                            chars,
-                            |ch| matches!(ch, '0'..='9' | 'A'..='F' | 'a'..='f'),
-                        );
+                        let s2 = peeking_take_while(chars, |ch: char| ch.is_ascii_hexdigit());
                         return Ok(Some(Token::HexStringLiteral(s2)));
                     }
 
@@ -510,7 +500,7 @@ impl<'a> Tokenizer<'a> {
                         s.push('.');
                         chars.next();
                     }
-                    s += &peeking_take_while(chars, |ch| matches!(ch, '0'..='9'));
+                    s += &peeking_take_while(chars, |ch: char| ch.is_ascii_digit());
 
                     // No number -> Token::Period
                     if s == "." {
@@ -763,6 +753,25 @@ impl<'a> Tokenizer<'a> {
                 'r' => escape_control_character!('\r'),
                 'n' => escape_control_character!('\n'),
                 't' => escape_control_character!('\t'),
+                'b' => escape_control_character!('\u{8}'),
+                'f' => escape_control_character!('\u{c}'),
+                'u' if is_escaped => {
+                    chars.next(); // consume
+                    let mut hex = String::new();
+                    while hex.len() < 4 {
+                        match chars.next_if(char::is_ascii_hexdigit) {
+                            Some(digit) => hex.push(digit),
+                            None => break,
+                        }
+                    }
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(c) if hex.len() == 4 => s.push(c),
+                        _ => {
+                            return self.tokenizer_error("Invalid unicode escape in string literal")
+                        }
+                    }
+                    is_escaped = false;
+                }
                 _ => {
                     is_escaped = false;
                     chars.next(); // consume
@@ -23,13 +23,17 @@ mod test_utils;
 
 use matches::assert_matches;
 use sqlgen::ast::*;
-use sqlgen::dialect::{Dialect, DialectDisplay};
+use sqlgen::dialect::{
+    CastStyle, DataTypeName, Dialect, DialectDisplay, FunctionSet, FunctionTransform,
+    SharedDialect, StructStyle, TryCastStyle, UnknownFunctionPolicy, DEFAULT_MAX_RENDER_DEPTH,
+};
 use sqlgen::keywords::ALL_KEYWORDS;
 use sqlgen::parser::{Parser, SqlGenError};
 use sqlgen::test_utils::{
     parse_sql_query, query_parses_to, run_parser_method, verified_expr, verified_only_select,
     verified_query,
 };
+use std::sync::Arc;
 
 use test_utils::{expr_from_projection, join, number, only, table, table_alias};
 
@@ -169,22 +173,28 @@ fn parse_select_into() {
 fn parse_select_wildcard() {
     let sql = "SELECT * FROM foo";
     let select = verified_only_select(sql);
-    assert_eq!(&SelectItem::Wildcard, only(&select.projection));
+    assert_eq!(
+        &SelectItem::Wildcard(WildcardOptions::default()),
+        only(&select.projection)
+    );
 
     let sql = "SELECT foo.* FROM foo";
     let select = verified_only_select(sql);
     assert_eq!(
-        &SelectItem::QualifiedWildcard(ObjectName(vec![Ident::new("foo")])),
+        &SelectItem::QualifiedWildcard(
+            ObjectName(vec![Ident::new("foo")]),
+            WildcardOptions::default()
+        ),
         only(&select.projection)
     );
 
     let sql = "SELECT myschema.mytable.* FROM myschema.mytable";
     let select = verified_only_select(sql);
     assert_eq!(
-        &SelectItem::QualifiedWildcard(ObjectName(vec![
-            Ident::new("myschema"),
-            Ident::new("mytable"),
-        ])),
+        &SelectItem::QualifiedWildcard(
+            ObjectName(vec![Ident::new("myschema"), Ident::new("mytable"),]),
+            WildcardOptions::default()
+        ),
         only(&select.projection)
     );
 
@@ -261,6 +271,9 @@ fn parse_select_count_wildcard() {
             args: vec![FunctionArg::Unnamed(FunctionArgExpr::Wildcard)],
             over: None,
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -279,6 +292,9 @@ fn parse_select_count_distinct() {
             }))],
             over: None,
             distinct: true,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -362,7 +378,8 @@ fn parse_select_with_date_column_name() {
     assert_eq!(
         &Expr::Identifier(Ident {
             value: "date".into(),
-            quote_style: None
+            quote_style: None,
+            force_quote: false,
         }),
         expr_from_projection(only(&select.projection)),
     );
@@ -537,6 +554,7 @@ fn parse_not_precedence() {
                 low: Box::new(Expr::Value(number("1"))),
                 high: Box::new(Expr::Value(number("2"))),
                 negated: true,
+                symmetric: false,
             }),
         },
     );
@@ -844,6 +862,7 @@ fn parse_between() {
                 low: Box::new(Expr::Value(number("25"))),
                 high: Box::new(Expr::Value(number("32"))),
                 negated,
+                symmetric: false,
             },
             select.selection.unwrap()
         );
@@ -871,6 +890,7 @@ fn parse_between_with_expr() {
                 right: Box::new(Expr::Value(number("4"))),
             }),
             negated: false,
+            symmetric: false,
         })),
         select.selection.unwrap()
     );
@@ -894,6 +914,7 @@ fn parse_between_with_expr() {
                 low: Box::new(Expr::Value(number("1"))),
                 high: Box::new(Expr::Value(number("2"))),
                 negated: false,
+                symmetric: false,
             }),
         },
         select.selection.unwrap(),
@@ -1095,6 +1116,9 @@ fn parse_select_having() {
                 args: vec![FunctionArg::Unnamed(FunctionArgExpr::Wildcard)],
                 over: None,
                 distinct: false,
+                filter: None,
+                within_group: vec![],
+                order_by: vec![],
             })),
             op: BinaryOperator::Gt,
             right: Box::new(Expr::Value(number("1")))
@@ -1126,7 +1150,10 @@ fn parse_select_qualify() {
                     }],
                     window_frame: None
                 }),
-                distinct: false
+                distinct: false,
+                filter: None,
+                within_group: vec![],
+                order_by: vec![],
             })),
             op: BinaryOperator::Eq,
             right: Box::new(Expr::Value(number("1")))
@@ -1309,6 +1336,7 @@ fn parse_listagg() {
             expr: Expr::Identifier(Ident {
                 value: "id".to_string(),
                 quote_style: None,
+                force_quote: false,
             }),
             asc: None,
             nulls_first: None,
@@ -1317,6 +1345,7 @@ fn parse_listagg() {
             expr: Expr::Identifier(Ident {
                 value: "username".to_string(),
                 quote_style: None,
+                force_quote: false,
             }),
             asc: None,
             nulls_first: None,
@@ -1352,6 +1381,9 @@ fn parse_scalar_function_in_projection() {
                 ))],
                 over: None,
                 distinct: false,
+                filter: None,
+                within_group: vec![],
+                order_by: vec![],
             }),
             expr_from_projection(only(&select.projection))
         );
@@ -1382,6 +1414,9 @@ fn parse_named_argument_function() {
             ],
             over: None,
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(only(&select.projection))
     );
@@ -1415,6 +1450,9 @@ fn parse_window_functions() {
                 window_frame: None,
             }),
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -1674,11 +1712,15 @@ fn parse_at_timezone() {
             timestamp: Box::new(Expr::Function(Function {
                 name: ObjectName(vec![Ident {
                     value: "FROM_UNIXTIME".to_string(),
-                    quote_style: None
+                    quote_style: None,
+                    force_quote: false,
                 }]),
                 args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(zero.clone()))],
                 over: None,
-                distinct: false
+                distinct: false,
+                filter: None,
+                within_group: vec![],
+                order_by: vec![],
             })),
             time_zone: "UTC-06:00".to_string()
         },
@@ -1693,6 +1735,7 @@ fn parse_at_timezone() {
                 name: ObjectName(vec![Ident {
                     value: "DATE_FORMAT".to_string(),
                     quote_style: None,
+                    force_quote: false,
                 },],),
                 args: vec![
                     FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::AtTimeZone {
@@ -1700,10 +1743,14 @@ fn parse_at_timezone() {
                             name: ObjectName(vec![Ident {
                                 value: "FROM_UNIXTIME".to_string(),
                                 quote_style: None,
+                                force_quote: false,
                             },],),
                             args: vec![FunctionArg::Unnamed(FunctionArgExpr::Expr(zero,),),],
                             over: None,
                             distinct: false,
+                            filter: None,
+                            within_group: vec![],
+                            order_by: vec![],
                         },)),
                         time_zone: "UTC-06:00".to_string(),
                     },),),
@@ -1713,10 +1760,14 @@ fn parse_at_timezone() {
                 ],
                 over: None,
                 distinct: false,
+                filter: None,
+                within_group: vec![],
+                order_by: vec![],
             },),
             alias: Ident {
                 value: "hour".to_string(),
                 quote_style: Some('"',),
+                force_quote: false,
             },
         },
         only(&select.projection),
@@ -1748,6 +1799,9 @@ fn parse_table_function() {
                 )))],
                 over: None,
                 distinct: false,
+                filter: None,
+                within_group: vec![],
+                order_by: vec![],
             });
             assert_eq!(expr, expected_expr);
             assert_eq!(alias, table_alias("a"))
@@ -1796,9 +1850,10 @@ fn parse_unnest() {
                     name: Ident::new("numbers"),
                     columns: vec![],
                 }),
-                array_expr: Box::new(Expr::Identifier(Ident::new("expr"))),
+                array_exprs: vec![Expr::Identifier(Ident::new("expr"))],
                 with_offset: true,
                 with_offset_alias: None,
+                with_ordinality: false,
             },
             joins: vec![],
         }],
@@ -1811,9 +1866,10 @@ fn parse_unnest() {
         vec![TableWithJoins {
             relation: TableFactor::UNNEST {
                 alias: None,
-                array_expr: Box::new(Expr::Identifier(Ident::new("expr"))),
+                array_exprs: vec![Expr::Identifier(Ident::new("expr"))],
                 with_offset: false,
                 with_offset_alias: None,
+                with_ordinality: false,
             },
             joins: vec![],
         }],
@@ -1826,9 +1882,10 @@ fn parse_unnest() {
         vec![TableWithJoins {
             relation: TableFactor::UNNEST {
                 alias: None,
-                array_expr: Box::new(Expr::Identifier(Ident::new("expr"))),
+                array_exprs: vec![Expr::Identifier(Ident::new("expr"))],
                 with_offset: true,
                 with_offset_alias: None,
+                with_ordinality: false,
             },
             joins: vec![],
         }],
@@ -1844,9 +1901,10 @@ fn parse_unnest() {
                     name: Ident::new("numbers"),
                     columns: vec![],
                 }),
-                array_expr: Box::new(Expr::Identifier(Ident::new("expr"))),
+                array_exprs: vec![Expr::Identifier(Ident::new("expr"))],
                 with_offset: false,
                 with_offset_alias: None,
+                with_ordinality: false,
             },
             joins: vec![],
         }],
@@ -1862,9 +1920,10 @@ fn parse_unnest() {
                     name: Ident::new("numbers"),
                     columns: vec![],
                 }),
-                array_expr: Box::new(Expr::Identifier(Ident::new("expr"))),
+                array_exprs: vec![Expr::Identifier(Ident::new("expr"))],
                 with_offset: false,
                 with_offset_alias: Some(Ident::new("with_offset_alias")),
+                with_ordinality: false,
             },
             joins: vec![],
         }],
@@ -1884,11 +1943,13 @@ fn parse_delimited_identifiers() {
             alias,
             args,
             with_hints,
+            sample,
         } => {
             assert_eq!(vec![Ident::with_quote('"', "a table")], name.0);
             assert_eq!(Ident::with_quote('"', "alias"), alias.unwrap().name);
             assert!(args.is_none());
             assert!(with_hints.is_empty());
+            assert!(sample.is_none());
         }
         _ => panic!("Expecting TableFactor::Table"),
     }
@@ -1907,6 +1968,9 @@ fn parse_delimited_identifiers() {
             args: vec![],
             over: None,
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[1]),
     );
@@ -2020,6 +2084,7 @@ fn parse_implicit_join() {
                     alias: None,
                     args: None,
                     with_hints: vec![],
+                    sample: None,
                 },
                 joins: vec![],
             },
@@ -2029,6 +2094,7 @@ fn parse_implicit_join() {
                     alias: None,
                     args: None,
                     with_hints: vec![],
+                    sample: None,
                 },
                 joins: vec![],
             }
@@ -2046,6 +2112,7 @@ fn parse_implicit_join() {
                     alias: None,
                     args: None,
                     with_hints: vec![],
+                    sample: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
@@ -2053,6 +2120,7 @@ fn parse_implicit_join() {
                         alias: None,
                         args: None,
                         with_hints: vec![],
+                        sample: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2063,6 +2131,7 @@ fn parse_implicit_join() {
                     alias: None,
                     args: None,
                     with_hints: vec![],
+                    sample: None,
                 },
                 joins: vec![Join {
                     relation: TableFactor::Table {
@@ -2070,6 +2139,7 @@ fn parse_implicit_join() {
                         alias: None,
                         args: None,
                         with_hints: vec![],
+                        sample: None,
                     },
                     join_operator: JoinOperator::Inner(JoinConstraint::Natural),
                 }]
@@ -2090,6 +2160,7 @@ fn parse_cross_join() {
                 alias: None,
                 args: None,
                 with_hints: vec![],
+                sample: None,
             },
             join_operator: JoinOperator::CrossJoin
         },
@@ -2110,6 +2181,7 @@ fn parse_joins_on() {
                 alias,
                 args: None,
                 with_hints: vec![],
+                sample: None,
             },
             join_operator: f(JoinConstraint::On(Expr::BinaryOp {
                 left: Box::new(Expr::Identifier("c1".into())),
@@ -2163,6 +2235,7 @@ fn parse_joins_using() {
                 alias,
                 args: None,
                 with_hints: vec![],
+                sample: None,
             },
             join_operator: f(JoinConstraint::Using(vec!["c1".into()])),
         }
@@ -2208,6 +2281,7 @@ fn parse_natural_join() {
                 alias: None,
                 args: None,
                 with_hints: vec![],
+                sample: None,
             },
             join_operator: f(JoinConstraint::Natural),
         }
@@ -2389,10 +2463,12 @@ fn parse_recursive_cte() {
             name: Ident {
                 value: "nums".to_string(),
                 quote_style: None,
+                force_quote: false,
             },
             columns: vec![Ident {
                 value: "val".to_string(),
                 quote_style: None,
+                force_quote: false,
             }],
         },
         query: cte_query,
@@ -2441,6 +2517,7 @@ fn parse_derived_tables() {
                     alias: None,
                     args: None,
                     with_hints: vec![],
+                    sample: None,
                 },
                 join_operator: JoinOperator::Inner(JoinConstraint::Natural),
             }],
@@ -2775,9 +2852,14 @@ fn lateral_derived() {
         {
             assert_eq!(lateral_in, lateral);
             assert_eq!(Ident::new("order"), alias.name);
+            // ORDER is a keyword, so it must be quoted
+            assert_matches!(
+                subquery.sql(&Default::default()),
+                Err(SqlGenError::InvalidIdentifier(ident)) if ident == "order"
+            );
             assert_eq!(
-                subquery.sql(&Default::default()).unwrap(),
-                "SELECT * FROM order WHERE order.customer = customer.id LIMIT 3"
+                subquery.sql(&Dialect::postgres()).unwrap(),
+                r#"SELECT * FROM "order" WHERE "order"."customer" = "customer"."id" LIMIT 3"#
             );
         } else {
             unreachable!()
@@ -2893,6 +2975,9 @@ fn parse_time_functions() {
             args: vec![],
             over: None,
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -2908,6 +2993,9 @@ fn parse_time_functions() {
             args: vec![],
             over: None,
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -2923,6 +3011,9 @@ fn parse_time_functions() {
             args: vec![],
             over: None,
             distinct: false,
+            filter: None,
+            within_group: vec![],
+            order_by: vec![],
         }),
         expr_from_projection(&select.projection[0])
     );
@@ -3005,3 +3096,3448 @@ fn test_function_transform() {
         r#"SELECT round(1.5 - 0.5) AS "a", round(2.5 + 0.5) FROM "tbl""#
     )
 }
+
+#[test]
+fn test_function_lookup_is_case_insensitive() {
+    let dialect = Dialect::sqlite();
+    for name in ["FLOOR", "Floor", "floor"] {
+        let res = parse_sql_query(&format!("select {}(x) from tbl", name)).unwrap();
+        assert_eq!(
+            res.sql(&dialect).unwrap(),
+            r#"SELECT round("x" - 0.5) FROM "tbl""#
+        );
+    }
+
+    // Supported functions keep the spelling used in the query
+    for name in ["ROUND", "Round", "round"] {
+        let res = parse_sql_query(&format!("select {}(x) from tbl", name)).unwrap();
+        assert_eq!(
+            res.sql(&dialect).unwrap(),
+            format!(r#"SELECT "{}"("x") FROM "tbl""#, name)
+        );
+    }
+}
+
+#[test]
+fn test_function_transform_key_is_case_insensitive() {
+    #[derive(Debug)]
+    struct RoundTransform;
+    impl FunctionTransform for RoundTransform {
+        fn transform(
+            &self,
+            _name: &str,
+            args: &[FunctionArg],
+            dialect: &Dialect,
+        ) -> Result<String, SqlGenError> {
+            Ok(format!("round({}, 0)", args[0].sql(dialect)?))
+        }
+    }
+
+    let mut dialect = Dialect::sqlite();
+    dialect
+        .function_transforms
+        .insert("Round".to_string(), Arc::new(RoundTransform));
+    for name in ["ROUND", "Round", "round"] {
+        let res = parse_sql_query(&format!("select {}(x) from tbl", name)).unwrap();
+        assert_eq!(
+            res.sql(&dialect).unwrap(),
+            r#"SELECT round("x", 0) FROM "tbl""#
+        );
+    }
+}
+
+#[test]
+fn test_cast_type_parameters() {
+    let res = parse_sql_query("select cast(a as varchar(255)), cast(b as decimal(10,2)) from tbl")
+        .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::default()).unwrap(),
+        "SELECT CAST(a AS CHARACTER VARYING(255)), CAST(b AS NUMERIC(10,2)) FROM tbl"
+    );
+    assert_eq!(
+        res.sql(&Dialect::datafusion()).unwrap(),
+        r#"SELECT CAST("a" AS VARCHAR(255)), CAST("b" AS DECIMAL(10,2)) FROM "tbl""#
+    );
+
+    // SQLite strings are TEXT, which doesn't take a length
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT CAST("a" AS TEXT), CAST("b" AS NUMERIC(10,2)) FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::druid()).unwrap(),
+        r#"SELECT CAST("a" AS VARCHAR), CAST("b" AS DECIMAL) FROM "tbl""#
+    );
+
+    let res =
+        parse_sql_query("select cast(a as char(3)), cast(b as decimal(10)) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::datafusion()).unwrap(),
+        r#"SELECT CAST("a" AS CHAR(3)), CAST("b" AS DECIMAL(10)) FROM "tbl""#
+    );
+}
+
+#[test]
+fn test_unknown_function_policy() {
+    let res = parse_sql_query("select frobnicate(x) from tbl").unwrap();
+
+    let mut dialect = Dialect::datafusion();
+    assert_eq!(
+        dialect.unknown_function_policy,
+        UnknownFunctionPolicy::Error
+    );
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        "sqlgen error: Function frobnicate is not supported by dialect datafusion"
+    );
+
+    dialect.unknown_function_policy = UnknownFunctionPolicy::PassThrough;
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT frobnicate("x") FROM "tbl""#
+    );
+
+    dialect.unknown_function_policy = UnknownFunctionPolicy::Quote;
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "frobnicate"("x") FROM "tbl""#
+    );
+
+    // Supported functions are unaffected by the policy
+    let res = parse_sql_query("select abs(x) from tbl").unwrap();
+    assert_eq!(res.sql(&dialect).unwrap(), r#"SELECT abs("x") FROM "tbl""#);
+}
+
+fn test_schema() -> Schema {
+    let mut schema = Schema::new();
+    schema.insert(
+        "orders".to_string(),
+        vec![
+            Ident::new("id"),
+            Ident::new("customer_id"),
+            Ident::new("total"),
+        ],
+    );
+    schema.insert(
+        "customers".to_string(),
+        vec![Ident::new("id"), Ident::new("name")],
+    );
+    schema
+}
+
+#[test]
+fn test_pin_output_columns() {
+    let dialect = Dialect::datafusion();
+    let schema = test_schema();
+
+    let mut query = parse_sql_query("select * from orders").unwrap();
+    query
+        .pin_output_columns(&[Ident::new("total"), Ident::new("id")], &schema)
+        .unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"SELECT "total", "id" FROM "orders""#
+    );
+
+    // Explicit items keep their expressions and aliases
+    let mut query =
+        parse_sql_query("select c.*, o.total * 2 as double_total from orders o join customers c on o.customer_id = c.id").unwrap();
+    query
+        .pin_output_columns(
+            &[
+                Ident::new("double_total"),
+                Ident::new("name"),
+                Ident::new("id"),
+            ],
+            &schema,
+        )
+        .unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"SELECT "o"."total" * 2 AS "double_total", "c"."name", "c"."id" FROM "orders" AS "o" JOIN "customers" AS "c" ON "o"."customer_id" = "c"."id""#
+    );
+
+    // Wildcards over CTEs expand to the CTE's columns
+    let mut query =
+        parse_sql_query("with t as (select id, total from orders) select * from t").unwrap();
+    query
+        .pin_output_columns(&[Ident::new("total"), Ident::new("id")], &schema)
+        .unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"WITH "t" AS (SELECT "id", "total" FROM "orders") SELECT "total", "id" FROM "t""#
+    );
+}
+
+#[test]
+fn test_expand_wildcards_merged_join_columns() {
+    let dialect = Dialect::datafusion();
+    let mut schema = test_schema();
+    schema.insert(
+        "addresses".to_string(),
+        vec![Ident::new("id"), Ident::new("city")],
+    );
+
+    // Columns merged by USING and NATURAL are selected once, first
+    let mut query = parse_sql_query("select * from customers join addresses using (id)").unwrap();
+    query.expand_wildcards(&schema).unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"SELECT "id", "customers"."name", "addresses"."city" FROM "customers" JOIN "addresses" USING("id")"#
+    );
+    let mut query = parse_sql_query("select * from customers natural join addresses").unwrap();
+    query
+        .pin_output_columns(&[Ident::new("city"), Ident::new("id")], &schema)
+        .unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"SELECT "addresses"."city", "id" FROM "customers" NATURAL JOIN "addresses""#
+    );
+
+    // A qualified wildcard still selects all the columns of its table
+    let mut query =
+        parse_sql_query("select addresses.* from customers join addresses using (id)").unwrap();
+    query.expand_wildcards(&schema).unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"SELECT "addresses"."id", "addresses"."city" FROM "customers" JOIN "addresses" USING("id")"#
+    );
+
+    // Table names and qualifiers are compared ignoring case
+    let mut query = parse_sql_query("select Orders.* from ORDERS").unwrap();
+    query.expand_wildcards(&schema).unwrap();
+    assert_eq!(
+        query.sql(&dialect).unwrap(),
+        r#"SELECT "ORDERS"."id", "ORDERS"."customer_id", "ORDERS"."total" FROM "ORDERS""#
+    );
+}
+
+#[test]
+fn test_pin_output_columns_missing_column() {
+    let schema = test_schema();
+
+    let mut query = parse_sql_query("select * from orders").unwrap();
+    let err = query
+        .pin_output_columns(&[Ident::new("total"), Ident::new("name")], &schema)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "sqlgen error: Column name is not available"
+    );
+
+    let mut query = parse_sql_query("select * from products").unwrap();
+    let err = query
+        .pin_output_columns(&[Ident::new("id")], &schema)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "sqlgen error: Table products not found in schema"
+    );
+
+    // Duplicate output names can't be pinned by name
+    for sql in [
+        "select o.id, c.id from orders o join customers c on o.customer_id = c.id",
+        "select id, id from orders union all select id, total from orders",
+    ] {
+        let mut query = parse_sql_query(sql).unwrap();
+        let err = query
+            .pin_output_columns(&[Ident::new("id")], &schema)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "sqlgen error: Column id is ambiguous");
+    }
+}
+
+#[test]
+fn test_dialect_builder() {
+    #[derive(Debug)]
+    struct NegateTransform;
+    impl FunctionTransform for NegateTransform {
+        fn transform(
+            &self,
+            _name: &str,
+            args: &[FunctionArg],
+            dialect: &Dialect,
+        ) -> Result<String, SqlGenError> {
+            Ok(format!("-({})", args[0].sql(dialect)?))
+        }
+    }
+
+    let dialect = Dialect::postgres()
+        .with_functions(["my_udf", "other_udf"])
+        .with_function_transform("negate", Arc::new(NegateTransform));
+    let res = parse_sql_query("select my_udf(a), other_udf(b), negate(c) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT my_udf("a"), other_udf("b"), -("c") FROM "tbl""#
+    );
+    assert!(res.sql(&Dialect::postgres()).is_err());
+}
+
+#[test]
+fn test_strict_aggregate_arguments() {
+    let mut dialect = Dialect::datafusion();
+    let res = parse_sql_query("select count() from tbl").unwrap();
+    assert_eq!(res.sql(&dialect).unwrap(), r#"SELECT count() FROM "tbl""#);
+
+    dialect.strict = true;
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        "sqlgen error: Function count requires at least one argument"
+    );
+
+    let res = parse_sql_query("select count(*), sum(x) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT count(*), sum("x") FROM "tbl""#
+    );
+
+    let res = parse_sql_query("select sum(*) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        "sqlgen error: Function sum does not accept a wildcard argument"
+    );
+}
+
+#[test]
+fn test_aggregate_filter() {
+    let sql = "SELECT count(*) FILTER (WHERE x > 0), sum(y) FILTER (WHERE x > 0) OVER (PARTITION BY z) FROM tbl";
+    verified_query(sql);
+
+    let res = parse_sql_query(sql).unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT count(*) FILTER (WHERE "x" > 0), sum("y") FILTER (WHERE "x" > 0) OVER (PARTITION BY "z") FROM "tbl""#
+    );
+
+    let mut dialect = Dialect::postgres();
+    dialect.supports_aggregate_filter = false;
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT count(CASE WHEN "x" > 0 THEN 1 END), sum(CASE WHEN "x" > 0 THEN "y" END) OVER (PARTITION BY "z") FROM "tbl""#
+    );
+
+    let res = parse_sql_query("select corr(a, b) filter (where x > 0) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        "sqlgen error: FILTER on function corr cannot be rewritten for dialect"
+    );
+
+    // FILTER is still usable as an alias
+    verified_query("SELECT count(*) AS filter FROM tbl");
+}
+
+#[test]
+fn test_strict_translate_arguments() {
+    let mut dialect = Dialect::datafusion();
+    dialect.strict = true;
+
+    let res = parse_sql_query("select translate(s, 'abc', 'xyz') from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT translate("s", 'abc', 'xyz') FROM "tbl""#
+    );
+
+    let res = parse_sql_query("select translate(s, 'abc', 'xy') from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        "sqlgen error: Function translate expects from and to strings of equal length, found 'abc' and 'xy'"
+    );
+
+    // Non-literal arguments can't be checked
+    let res = parse_sql_query("select translate(s, a, 'xy') from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT translate("s", "a", 'xy') FROM "tbl""#
+    );
+}
+
+#[test]
+fn test_within_group() {
+    let dialect = Dialect::postgres();
+    let res =
+        parse_sql_query("select percentile_cont(0.5) within group (order by x) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY "x") FROM "tbl""#
+    );
+
+    let res = parse_sql_query(
+        "select percentile_disc(0.9) within group (order by x desc, y) filter (where z > 0) from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT percentile_disc(0.9) WITHIN GROUP (ORDER BY "x" DESC, "y") FILTER (WHERE "z" > 0) FROM "tbl""#
+    );
+}
+
+#[test]
+fn test_regexp_replace_flags() {
+    let res = parse_sql_query("select regexp_replace(s, 'a+', 'b', 'g') from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT regexp_replace("s", 'a+', 'b', 'g') FROM "tbl""#
+    );
+
+    // Druid always replaces every match
+    assert_eq!(
+        res.sql(&Dialect::druid()).unwrap(),
+        r#"SELECT REGEXP_REPLACE("s", 'a+', 'b') FROM "tbl""#
+    );
+    let first_only = parse_sql_query("select regexp_replace(s, 'a+', 'b') from tbl").unwrap();
+    assert!(first_only.sql(&Dialect::druid()).is_err());
+
+    // SQLite has no regexp_replace, whatever the unknown function policy
+    let mut dialect = Dialect::sqlite();
+    dialect.unknown_function_policy = UnknownFunctionPolicy::PassThrough;
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        "sqlgen error: Function regexp_replace is not supported by dialect sqlite"
+    );
+}
+
+#[test]
+fn test_aggregate_distinct() {
+    let res = parse_sql_query("select count(distinct a), sum(distinct b) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT count(DISTINCT "a"), sum(DISTINCT "b") FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT "count"(DISTINCT "a"), "sum"(DISTINCT "b") FROM "tbl""#
+    );
+
+    // DISTINCT applies to the rewritten FILTER argument
+    let res = parse_sql_query("select count(distinct a) filter (where b > 0) from tbl").unwrap();
+    let mut dialect = Dialect::postgres();
+    dialect.supports_aggregate_filter = false;
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT count(DISTINCT CASE WHEN "b" > 0 THEN "a" END) FROM "tbl""#
+    );
+
+    // Transformed functions can't carry DISTINCT
+    let res = parse_sql_query("select approx_distinct(distinct a) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::druid()).unwrap_err().to_string(),
+        "sqlgen error: DISTINCT in function approx_distinct is not supported by dialect druid"
+    );
+}
+
+#[test]
+fn test_canonical_sql() {
+    let dialect = Dialect::postgres();
+    let canonical = |sql: &str| {
+        parse_sql_query(sql)
+            .unwrap()
+            .canonical_sql(&dialect)
+            .unwrap()
+    };
+
+    let expected = r#"SELECT ("a" + "b") * "c", "a" - "b" - "c", "a" - ("b" - "c"), count("x") FROM "t" WHERE "a" > 1 AND NOT ("b" < 2 OR "c" IS NULL) ORDER BY "a" ASC NULLS LAST, "b" DESC NULLS FIRST"#;
+    assert_eq!(
+        canonical(
+            "SELECT (a + b) * c, (a - b) - c, a - (b - c), COUNT(((x))) FROM t \
+             WHERE (a > 1) AND NOT ((b < 2) OR (c IS NULL)) ORDER BY a, (b) DESC"
+        ),
+        expected
+    );
+    assert_eq!(
+        canonical(
+            "select (a+b)*c, a-b-c, a-(b-c), count(x) from t \
+             where a > 1 and not (b < 2 or c is null) order by a asc nulls last, b desc nulls first"
+        ),
+        expected
+    );
+
+    // Explicit NULL ordering follows the dialect's default
+    let query = parse_sql_query("select a from t order by a").unwrap();
+    assert_eq!(
+        query.canonical_sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT "a" FROM "t" ORDER BY "a" ASC NULLS FIRST"#
+    );
+    assert_eq!(
+        query.canonical_sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM `t` ORDER BY `a` ASC"
+    );
+}
+
+#[test]
+fn test_canonical_sql_string_concat() {
+    let dialect = Dialect::postgres();
+    let canonical = |sql: &str| {
+        parse_sql_query(sql)
+            .unwrap()
+            .canonical_sql(&dialect)
+            .unwrap()
+    };
+
+    // Concatenation binds tighter than comparison in every dialect
+    assert_eq!(
+        canonical("select a from t where (a || b) = c and c <> (a || b)"),
+        r#"SELECT "a" FROM "t" WHERE "a" || "b" = "c" AND "c" <> "a" || "b""#
+    );
+    assert_eq!(
+        canonical("select (a || b) || c, a || (b || c) from t"),
+        r#"SELECT "a" || "b" || "c", "a" || ("b" || "c") FROM "t""#
+    );
+
+    // ...but its precedence relative to arithmetic differs, so those
+    // parentheses are kept
+    assert_eq!(
+        canonical("select (a || b) + c, a || (b * c), (a + b) || c from t"),
+        r#"SELECT ("a" || "b") + "c", "a" || ("b" * "c"), ("a" + "b") || "c" FROM "t""#
+    );
+
+    // Parentheses are rendered as given outside of canonicalization
+    assert_eq!(
+        parse_sql_query("select a from t where (a || b) = c")
+            .unwrap()
+            .sql(&dialect)
+            .unwrap(),
+        r#"SELECT "a" FROM "t" WHERE ("a" || "b") = "c""#
+    );
+}
+
+#[test]
+fn test_aggregate_order_by() {
+    let res = parse_sql_query("select array_agg(x order by y desc, z) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT array_agg("x" ORDER BY "y" DESC, "z") FROM "tbl""#
+    );
+
+    let res = parse_sql_query("select array_agg(distinct a order by a) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT array_agg(DISTINCT "a" ORDER BY "a") FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::druid()).unwrap_err().to_string(),
+        "sqlgen error: ORDER BY in function array_agg is not supported by dialect druid"
+    );
+
+    let res = parse_sql_query("select string_agg(x, ',' order by y) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT string_agg("x", ',' ORDER BY "y") FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT GROUP_CONCAT(`x` ORDER BY `y` SEPARATOR ',') FROM `tbl`"
+    );
+}
+
+#[test]
+fn test_extract_epoch() {
+    let res = parse_sql_query("select extract(epoch from ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT EXTRACT(EPOCH FROM "ts") FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT UNIX_TIMESTAMP(`ts`) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT strftime('%s', "ts") FROM "tbl""#
+    );
+
+    // Other fields are unaffected
+    let res = parse_sql_query("select extract(year from ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT EXTRACT(YEAR FROM `ts`) FROM `tbl`"
+    );
+}
+
+#[test]
+fn test_cast_type_names() {
+    let res = parse_sql_query(
+        "select cast(a as double), cast(b as varchar), cast(c as boolean), cast(d as bigint) from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT CAST("a" AS DOUBLE PRECISION), CAST("b" AS CHARACTER VARYING), CAST("c" AS BOOLEAN), CAST("d" AS BIGINT) FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT CAST(`a` AS FLOAT64), CAST(`b` AS STRING), CAST(`c` AS BOOL), CAST(`d` AS INT64) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::spark()).unwrap(),
+        "SELECT CAST(`a` AS DOUBLE), CAST(`b` AS STRING), CAST(`c` AS BOOLEAN), CAST(`d` AS BIGINT) FROM `tbl`"
+    );
+}
+
+#[test]
+fn test_mysql_cast_integers() {
+    let res = parse_sql_query(
+        "select cast(a as int), cast(b as integer), cast(c as boolean), cast(d as bigint), cast(e as int unsigned), cast(f as varchar(10)) from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT CAST(`a` AS SIGNED), CAST(`b` AS SIGNED), CAST(`c` AS SIGNED), CAST(`d` AS SIGNED), CAST(`e` AS UNSIGNED), CAST(`f` AS CHAR(10)) FROM `tbl`"
+    );
+
+    // Column types keep their own names
+    assert_eq!(
+        DataType::Int(Some(11)).sql(&Dialect::mysql()).unwrap(),
+        "INT(11)"
+    );
+}
+
+#[test]
+fn test_try_cast_dialects() {
+    let res = parse_sql_query("select try_cast(a as double) from tbl").unwrap();
+    assert_eq!(
+        res,
+        parse_sql_query("select safe_cast(a as double) from tbl").unwrap()
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT SAFE_CAST(`a` AS FLOAT64) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::snowflake()).unwrap(),
+        r#"SELECT TRY_CAST("a" AS DOUBLE) FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::spark()).unwrap(),
+        "SELECT TRY_CAST(`a` AS DOUBLE) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        "sqlgen error: TRY_CAST is not supported by dialect postgres"
+    );
+
+    let mut dialect = Dialect::postgres();
+    dialect.try_cast_style = TryCastStyle::Cast;
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT CAST("a" AS DOUBLE PRECISION) FROM "tbl""#
+    );
+}
+
+#[test]
+fn test_nulls_ordering() {
+    let res =
+        parse_sql_query("select a from tbl order by a nulls first, b desc nulls last").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::vertica()).unwrap(),
+        r#"SELECT "a" FROM "tbl" ORDER BY "a" NULLS FIRST, "b" DESC NULLS LAST"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap_err().to_string(),
+        "sqlgen error: NULLS FIRST/LAST is not supported by dialect mysql"
+    );
+    assert_matches!(
+        res.sql(&Dialect::mysql()),
+        Err(SqlGenError::UnsupportedFeature { feature, dialect })
+            if feature == "NULLS FIRST/LAST" && dialect == "mysql"
+    );
+
+    let res = parse_sql_query("select a from tbl order by a desc").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM `tbl` ORDER BY `a` DESC"
+    );
+}
+
+#[test]
+fn test_vertica_functions() {
+    let res = parse_sql_query(
+        "select approx_distinct(a), approx_median(b), time_slice(ts, 1, 'HOUR') from tbl group by 3",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::vertica()).unwrap(),
+        r#"SELECT APPROXIMATE_COUNT_DISTINCT("a"), APPROXIMATE_MEDIAN("b"), time_slice("ts", 1, 'HOUR') FROM "tbl" GROUP BY 3"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        "sqlgen error: Function approx_distinct is not supported by dialect postgres"
+    );
+}
+
+#[test]
+fn test_string_concat() {
+    let operator = parse_sql_query("select a || b || c from tbl").unwrap();
+    let function = parse_sql_query("select concat(a, b, c) from tbl").unwrap();
+
+    // MySQL only has concat(...) since || is a logical OR
+    assert_eq!(
+        operator.sql(&Dialect::mysql()).unwrap(),
+        "SELECT concat(`a`, `b`, `c`) FROM `tbl`"
+    );
+    assert_eq!(
+        function.sql(&Dialect::mysql()).unwrap(),
+        "SELECT concat(`a`, `b`, `c`) FROM `tbl`"
+    );
+
+    // SQLite only has ||
+    assert_eq!(
+        operator.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT "a" || "b" || "c" FROM "tbl""#
+    );
+    // concat skips NULLs where || propagates them
+    assert_eq!(
+        function.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT (coalesce("a", '') || coalesce("b", '') || coalesce("c", '')) FROM "tbl""#
+    );
+
+    // Postgres has both
+    assert_eq!(
+        operator.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" || "b" || "c" FROM "tbl""#
+    );
+    assert_eq!(
+        function.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT concat("a", "b", "c") FROM "tbl""#
+    );
+
+    // Parenthesized chains are flattened, other operands are kept intact
+    let res = parse_sql_query("select a || (b || c), concat(a, b + 1, lower(c)) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT concat(`a`, `b`, `c`), concat(`a`, `b` + 1, lower(`c`)) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT "a" || ("b" || "c"), (coalesce("a", '') || coalesce("b" + 1, '') || coalesce("lower"("c"), '')) FROM "tbl""#
+    );
+
+    // The chain is parenthesized so that COLLATE applies to all of it
+    let res = parse_sql_query(r#"select concat(a, b) collate "C" from tbl"#).unwrap();
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT (coalesce("a", '') || coalesce("b", '')) COLLATE "C" FROM "tbl""#
+    );
+}
+
+#[test]
+fn test_interval_multiplication() {
+    let res = parse_sql_query(
+        "select ts + interval '1 hour' * qty, ts - 2 * interval '3' day, interval '1' minute * (a + b) from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "ts" + INTERVAL '1 hour' * "qty", "ts" - 2 * INTERVAL '3' DAY, INTERVAL '1' MINUTE * ("a" + "b") FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT `ts` + INTERVAL `qty` HOUR, `ts` - INTERVAL (3 * 2) DAY, INTERVAL (`a` + `b`) MINUTE FROM `tbl`"
+    );
+
+    let res = parse_sql_query("select interval '1:30' hour to minute * qty from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap_err().to_string(),
+        "sqlgen error: Multiplying INTERVAL '1:30' HOUR TO MINUTE is not supported by dialect mysql"
+    );
+
+    // Only finite decimal quantities are spliced into the rendered SQL
+    for quantity in ["inf", "NaN", "1e3", "."] {
+        let res = parse_sql_query(&format!(
+            "select interval '{} hours' * qty from tbl",
+            quantity
+        ))
+        .unwrap();
+        assert_eq!(
+            res.sql(&Dialect::mysql()).unwrap_err().to_string(),
+            format!(
+                "sqlgen error: Multiplying INTERVAL '{} hours' is not supported by dialect mysql",
+                quantity
+            )
+        );
+    }
+    let res = parse_sql_query("select interval '1 hour' * qty from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::druid()).unwrap_err().to_string(),
+        "sqlgen error: Multiplying INTERVAL '1 hour' is not supported by dialect druid"
+    );
+}
+
+#[test]
+fn test_date_part_style() {
+    let extract = parse_sql_query("select extract(year from ts) from tbl").unwrap();
+    let date_part = parse_sql_query("select date_part('year', ts) from tbl").unwrap();
+    for query in [&extract, &date_part] {
+        assert_eq!(
+            query.sql(&Dialect::datafusion()).unwrap(),
+            r#"SELECT date_part('year', "ts") FROM "tbl""#
+        );
+        assert_eq!(
+            query.sql(&Dialect::postgres()).unwrap(),
+            r#"SELECT EXTRACT(YEAR FROM "ts") FROM "tbl""#
+        );
+        assert_eq!(
+            query.sql(&Dialect::bigquery()).unwrap(),
+            "SELECT EXTRACT(YEAR FROM `ts`) FROM `tbl`"
+        );
+    }
+
+    // Extracting the epoch still follows the dialect's epoch style
+    let res = parse_sql_query("select date_part('epoch', ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::datafusion()).unwrap(),
+        r#"SELECT date_part('epoch', "ts") FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT UNIX_TIMESTAMP(`ts`) FROM `tbl`"
+    );
+
+    let res = parse_sql_query("select date_part(unit, ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        "sqlgen error: date_part without a literal field cannot be rendered as EXTRACT by dialect"
+    );
+}
+
+#[test]
+fn test_boolean_coercion() {
+    let res = parse_sql_query("select sum(x > 0), avg(y is null), sum(x) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT sum(`x` > 0), avg(`y` IS NULL), sum(`x`) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT sum(CASE WHEN "x" > 0 THEN 1 ELSE 0 END), avg(CASE WHEN "y" IS NULL THEN 1 ELSE 0 END), sum("x") FROM "tbl""#
+    );
+
+    // Coercion happens before a FILTER is rewritten into the argument
+    let res = parse_sql_query("select sum(x > 0) filter (where y = 1) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT sum(CASE WHEN `y` = 1 THEN CASE WHEN `x` > 0 THEN 1 ELSE 0 END END) FROM `tbl`"
+    );
+}
+
+#[test]
+fn test_date_trunc_time_zone() {
+    let res =
+        parse_sql_query("select date_trunc('day', ts), date_trunc('Hour', ts, 'UTC') from tbl")
+            .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT date_trunc('day', "ts"), date_trunc('Hour', "ts" AT TIME ZONE 'UTC') FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT timestamp_trunc(`ts`, DAY), timestamp_trunc(`ts`, HOUR, 'UTC') FROM `tbl`"
+    );
+
+    let res = parse_sql_query("select date_trunc('week', ts + delta, 'America/New_York') from tbl")
+        .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT date_trunc('week', ("ts" + "delta") AT TIME ZONE 'America/New_York') FROM "tbl""#
+    );
+
+    // Units are rendered as literals, or as written when they aren't one
+    let res = parse_sql_query(
+        "select date_trunc('day'')--', ts, 'UTC'), date_trunc(unit, ts), date_trunc(unit, ts, tz) from tbl",
+    )
+    .unwrap();
+    for dialect in [Dialect::postgres(), Dialect::redshift()] {
+        assert_eq!(
+            res.sql(&dialect).unwrap(),
+            r#"SELECT date_trunc('day'')--', "ts" AT TIME ZONE 'UTC'), date_trunc("unit", "ts"), date_trunc("unit", "ts" AT TIME ZONE "tz") FROM "tbl""#
+        );
+    }
+
+    let res = parse_sql_query("select date_trunc('day', ts, tz) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap_err().to_string(),
+        "sqlgen error: The time zone argument to date_trunc must be a string literal"
+    );
+    let res = parse_sql_query("select date_trunc('fortnight', ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap_err().to_string(),
+        "sqlgen error: Unsupported date_trunc unit for dialect: fortnight"
+    );
+}
+
+#[test]
+fn test_schema_probe() {
+    let probe = |sql: &str| {
+        parse_sql_query(sql)
+            .unwrap()
+            .schema_probe()
+            .sql(&Dialect::postgres())
+            .unwrap()
+    };
+    assert_eq!(
+        probe("select a, b from tbl where a > 1"),
+        r#"SELECT "a", "b" FROM "tbl" WHERE "a" > 1 LIMIT 0"#
+    );
+    assert_eq!(
+        probe("with t as (select a from tbl) select a from t order by a limit 10 offset 5"),
+        r#"WITH "t" AS (SELECT "a" FROM "tbl") SELECT "a" FROM "t" LIMIT 0"#
+    );
+    assert_eq!(
+        probe("select a from t1 union all select a from t2 order by a limit 3"),
+        r#"SELECT * FROM (SELECT "a" FROM "t1" UNION ALL SELECT "a" FROM "t2") AS "probe" LIMIT 0"#
+    );
+    assert_eq!(
+        probe("values (1, 'a'), (2, 'b')"),
+        r#"SELECT * FROM (VALUES (1, 'a'), (2, 'b')) AS "probe" LIMIT 0"#
+    );
+}
+
+#[test]
+fn test_date_bin_emulation() {
+    let res = parse_sql_query(
+        "select date_bin(interval '15 minutes', ts, '2020-01-01 00:00:00'), date_bin(interval '1' day, ts) from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT datetime(strftime('%s', '2020-01-01 00:00:00') + round((strftime('%s', "ts") - strftime('%s', '2020-01-01 00:00:00')) / 900 - 0.5) * 900, 'unixepoch'), datetime(round(strftime('%s', "ts") / 86400 - 0.5) * 86400, 'unixepoch') FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT FROM_UNIXTIME(UNIX_TIMESTAMP('2020-01-01 00:00:00') + floor((UNIX_TIMESTAMP(`ts`) - UNIX_TIMESTAMP('2020-01-01 00:00:00')) / 900) * 900), FROM_UNIXTIME(floor(UNIX_TIMESTAMP(`ts`) / 86400) * 86400) FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::datafusion()).unwrap(),
+        r#"SELECT date_bin(INTERVAL '15 minutes', "ts", '2020-01-01 00:00:00'), date_bin(INTERVAL '1' DAY, "ts") FROM "tbl""#
+    );
+
+    // Fractional strides are exact
+    let res = parse_sql_query("select date_bin(interval '1.1 hours', ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT FROM_UNIXTIME(floor(UNIX_TIMESTAMP(`ts`) / 3960) * 3960) FROM `tbl`"
+    );
+    let res = parse_sql_query("select date_bin(interval '0.25 seconds', ts) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT FROM_UNIXTIME(floor(UNIX_TIMESTAMP(`ts`) / 0.25) * 0.25) FROM `tbl`"
+    );
+
+    for stride in ["1 month", "0 hours", "-1 hours", "inf hours", "NaN seconds"] {
+        let res = parse_sql_query(&format!(
+            "select date_bin(interval '{}', ts) from tbl",
+            stride
+        ))
+        .unwrap();
+        assert_eq!(
+            res.sql(&Dialect::sqlite()).unwrap_err().to_string(),
+            "sqlgen error: The stride of date_bin must be a positive interval of seconds, minutes, hours, days or weeks"
+        );
+    }
+}
+
+#[test]
+fn test_lint_distinct_order_by() {
+    let lint = |sql: &str| parse_sql_query(sql).unwrap().lint().unwrap();
+    assert_eq!(
+        lint("select distinct a, b + 1 as c from t order by a, c, b + 1, 1"),
+        vec![]
+    );
+    assert_eq!(lint("select a from t order by b"), vec![]);
+    assert_eq!(lint("select distinct * from t order by b"), vec![]);
+    assert_eq!(
+        lint("select x from (select distinct a from t order by b desc, lower(a)) as s"),
+        vec![
+            Lint {
+                kind: LintKind::DistinctOrderBy,
+                severity: Severity::Warning,
+                message: r#"ORDER BY expression "b" is not in the select list of SELECT DISTINCT"#
+                    .to_string(),
+            },
+            Lint {
+                kind: LintKind::DistinctOrderBy,
+                severity: Severity::Warning,
+                message:
+                    r#"ORDER BY expression lower("a") is not in the select list of SELECT DISTINCT"#
+                        .to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_select_distinct_dialects() {
+    // DISTINCT collapses NULLs in every dialect, so it renders unchanged
+    let res = parse_sql_query("select distinct a, b from tbl").unwrap();
+    for dialect in [Dialect::postgres(), Dialect::sqlite(), Dialect::druid()] {
+        assert_eq!(
+            res.sql(&dialect).unwrap(),
+            r#"SELECT DISTINCT "a", "b" FROM "tbl""#
+        );
+    }
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT DISTINCT `a`, `b` FROM `tbl`"
+    );
+}
+
+#[test]
+fn test_ilike_fallback() {
+    let res = parse_sql_query("select a from tbl where a ilike 'x%' and b not ilike c").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "tbl" WHERE "a" ILIKE 'x%' AND "b" NOT ILIKE "c""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM `tbl` WHERE lower(`a`) LIKE lower('x%') AND lower(`b`) NOT LIKE lower(`c`)"
+    );
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT "a" FROM "tbl" WHERE lower("a") LIKE lower('x%') AND lower("b") NOT LIKE lower("c")"#
+    );
+}
+
+#[test]
+fn test_values_cast_first_row() {
+    let values = |sql: &str| match *parse_sql_query(sql).unwrap().body {
+        SetExpr::Values(values) => values,
+        _ => panic!("Expected VALUES"),
+    };
+
+    let mut typed = values("values (null, 'a'), (1, 'b')");
+    typed
+        .cast_first_row(&[Some(DataType::Integer(None)), None])
+        .unwrap();
+    assert_eq!(
+        typed.sql(&Dialect::postgres()).unwrap(),
+        "VALUES (CAST(NULL AS INTEGER), 'a'), (1, 'b')"
+    );
+    assert_eq!(
+        typed.sql(&Dialect::bigquery()).unwrap(),
+        "VALUES (CAST(NULL AS INT64), 'a'), (1, 'b')"
+    );
+
+    let untyped = values("values (null, 'a'), (1, 'b')");
+    assert_eq!(
+        untyped.sql(&Dialect::postgres()).unwrap(),
+        "VALUES (NULL, 'a'), (1, 'b')"
+    );
+
+    let mut wrong_width = untyped.clone();
+    assert_eq!(
+        wrong_width
+            .cast_first_row(&[Some(DataType::Integer(None))])
+            .unwrap_err()
+            .to_string(),
+        "sqlgen error: Expected 2 column types for VALUES, found 1"
+    );
+}
+
+#[test]
+fn test_regex_match_dialects() {
+    let res = parse_sql_query("select a from tbl where a ~ 'p' and b !~ 'q'").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "tbl" WHERE "a" ~ 'p' AND "b" !~ 'q'"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM `tbl` WHERE `a` REGEXP 'p' AND `b` NOT REGEXP 'q'"
+    );
+    assert_eq!(
+        res.sql(&Dialect::spark()).unwrap(),
+        "SELECT `a` FROM `tbl` WHERE `a` RLIKE 'p' AND `b` NOT RLIKE 'q'"
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT `a` FROM `tbl` WHERE REGEXP_CONTAINS(`a`, 'p') AND NOT REGEXP_CONTAINS(`b`, 'q')"
+    );
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap_err().to_string(),
+        "sqlgen error: Regular expression operator ~ is not supported by dialect sqlite"
+    );
+
+    let res = parse_sql_query("select a from tbl where a ~* 'p'").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::druid()).unwrap(),
+        r#"SELECT "a" FROM "tbl" WHERE REGEXP_LIKE("a", '(?i)p')"#
+    );
+    let res = parse_sql_query("select a from tbl where a ~* b").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap_err().to_string(),
+        "sqlgen error: Regular expression operator ~* requires a literal pattern for dialect"
+    );
+}
+
+#[test]
+fn test_strict_distinct_order_by_arguments() {
+    let mut dialect = Dialect::postgres();
+    dialect.strict = true;
+
+    let res = parse_sql_query("select array_agg(distinct x order by x desc) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT array_agg(DISTINCT "x" ORDER BY "x" DESC) FROM "tbl""#
+    );
+
+    let res = parse_sql_query("select array_agg(distinct x order by y) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap_err().to_string(),
+        r#"sqlgen error: ORDER BY expression "y" of function array_agg with DISTINCT must be one of its arguments"#
+    );
+
+    // Without DISTINCT any ordering is allowed
+    let res = parse_sql_query("select array_agg(x order by y) from tbl").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT array_agg("x" ORDER BY "y") FROM "tbl""#
+    );
+}
+
+#[test]
+fn test_array_dialects() {
+    let res =
+        parse_sql_query("select array[1, 2, 3], [a, b], arr[1], arr[i + 1][2] from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT ARRAY[1, 2, 3], ARRAY["a", "b"], "arr"[1], "arr"["i" + 1][2] FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT [1, 2, 3], [`a`, `b`], `arr`[OFFSET(0)], `arr`[OFFSET(`i` + 1 - 1)][OFFSET(1)] FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::spark()).unwrap(),
+        "SELECT array(1, 2, 3), array(`a`, `b`), `arr`[0], `arr`[`i` + 1 - 1][1] FROM `tbl`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::datafusion()).unwrap(),
+        r#"SELECT ARRAY[1, 2, 3], ["a", "b"], "arr"[1], "arr"["i" + 1][2] FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap_err().to_string(),
+        "sqlgen error: Array is not supported by dialect mysql"
+    );
+}
+
+#[test]
+fn test_identifier_hook() {
+    let res = parse_sql_query("select t.a as b, count(c) from t join u using (id)").unwrap();
+    let dialect =
+        Dialect::postgres().with_identifier_hook(true, |ident| format!("ns_{}", ident.value));
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "ns_t"."ns_a" AS "ns_b", count("ns_c") FROM "ns_t" JOIN "ns_u" USING("ns_id")"#
+    );
+
+    // Without quoting the hook's text is written as is
+    let dialect =
+        Dialect::mysql().with_identifier_hook(false, |ident| format!("db.`{}`", ident.value));
+    let res = parse_sql_query("select a from t").unwrap();
+    assert_eq!(res.sql(&dialect).unwrap(), "SELECT db.`a` FROM db.`t`");
+}
+
+#[test]
+fn test_struct_literal() {
+    let res = parse_sql_query("select named_struct('a', 1, 'b', x) from t").unwrap();
+    let duckdb = Dialect {
+        struct_style: StructStyle::Braces,
+        ..Dialect::postgres()
+    };
+    assert_eq!(
+        res.sql(&duckdb).unwrap(),
+        r#"SELECT {'a': 1, 'b': "x"} FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT STRUCT(1 AS `a`, `x` AS `b`) FROM `t`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::spark()).unwrap(),
+        "SELECT named_struct('a', 1, 'b', `x`) FROM `t`"
+    );
+    assert!(res.sql(&Dialect::postgres()).is_err());
+
+    let res = parse_sql_query("select named_struct(a, 1) from t").unwrap();
+    assert!(res.sql(&Dialect::bigquery()).is_err());
+}
+
+#[test]
+fn test_json_cast() {
+    let res = parse_sql_query("select cast(a as json), b::jsonb from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT CAST("a" AS JSON), CAST("b" AS JSONB) FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT CAST(`a` AS JSON), CAST(`b` AS JSON) FROM `t`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::snowflake()).unwrap(),
+        r#"SELECT PARSE_JSON("a"), PARSE_JSON("b") FROM "t""#
+    );
+
+    let res = parse_sql_query("select try_cast(a as json) from t").unwrap();
+    assert!(res.sql(&Dialect::snowflake()).is_err());
+}
+
+#[test]
+fn test_in_rendering() {
+    let res = parse_sql_query("select * from t where x in (1, 2, 3) and y not in (1)").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t" WHERE "x" IN (1, 2, 3) AND "y" NOT IN (1)"#
+    );
+
+    let res = parse_sql_query("select * from t where x in (select y from u)").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t" WHERE "x" IN (SELECT "y" FROM "u")"#
+    );
+
+    // Operands binding more loosely than IN are parenthesized
+    let expr = Expr::InList {
+        expr: Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("a"))),
+            op: BinaryOperator::Or,
+            right: Box::new(Expr::Identifier(Ident::new("b"))),
+        }),
+        list: vec![Expr::Value(Value::Boolean(true))],
+        negated: true,
+    };
+    assert_eq!(
+        expr.sql(&Dialect::postgres()).unwrap(),
+        r#"("a" OR "b") NOT IN (true)"#
+    );
+
+    let expr = Expr::InList {
+        expr: Box::new(Expr::Identifier(Ident::new("x"))),
+        list: vec![],
+        negated: false,
+    };
+    assert_matches!(
+        expr.sql(&Dialect::postgres()),
+        Err(SqlGenError::DialectError(_))
+    );
+}
+
+#[test]
+fn test_correlated_subqueries() {
+    let correlated =
+        parse_sql_query("select t.a, (select max(u.b) from u where u.id = t.id) as m from t")
+            .unwrap();
+    let found = correlated.correlated_subqueries().unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(
+        found[0].sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT max("u"."b") FROM "u" WHERE "u"."id" = "t"."id""#
+    );
+
+    let uncorrelated = parse_sql_query("select t.a, (select max(u.b) from u) as m from t").unwrap();
+    assert!(uncorrelated.correlated_subqueries().unwrap().is_empty());
+
+    // A subquery's own tables shadow the enclosing query's
+    let shadowed =
+        parse_sql_query("select a from t where exists (select 1 from t where t.a > 0)").unwrap();
+    assert!(shadowed.correlated_subqueries().unwrap().is_empty());
+
+    let dialect = Dialect {
+        strict: true,
+        ..Dialect::druid()
+    };
+    assert!(correlated.sql(&dialect).is_err());
+    assert!(uncorrelated.sql(&dialect).is_ok());
+    assert!(correlated.sql(&Dialect::druid()).is_ok());
+}
+
+#[test]
+fn test_between_rendering() {
+    let res = parse_sql_query("select * from t where x between 1 and 10 or y not between a and b")
+        .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT * FROM `t` WHERE `x` BETWEEN 1 AND 10 OR `y` NOT BETWEEN `a` AND `b`"
+    );
+
+    let res = parse_sql_query("select * from t where x between symmetric 10 and 1").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t" WHERE "x" BETWEEN SYMMETRIC 10 AND 1"#
+    );
+    assert!(res.sql(&Dialect::mysql()).is_err());
+
+    // Bounds that bind as loosely as BETWEEN are parenthesized
+    let expr = Expr::Between {
+        expr: Box::new(Expr::Identifier(Ident::new("x"))),
+        negated: false,
+        low: Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("a"))),
+            op: BinaryOperator::Eq,
+            right: Box::new(Expr::Identifier(Ident::new("b"))),
+        }),
+        high: Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("c"))),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::Value(number("1"))),
+        }),
+        symmetric: false,
+    };
+    assert_eq!(
+        expr.sql(&Dialect::postgres()).unwrap(),
+        r#""x" BETWEEN ("a" = "b") AND "c" + 1"#
+    );
+}
+
+#[test]
+fn test_window_frame_exclude() {
+    let res = parse_sql_query(
+        "select sum(x) over (order by y rows between 1 preceding and 1 following exclude current row) from t",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT sum("x") OVER (ORDER BY "y" ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING EXCLUDE CURRENT ROW) FROM "t""#
+    );
+    assert!(res.sql(&Dialect::sqlite()).is_ok());
+    assert!(res.sql(&Dialect::mysql()).is_err());
+
+    let res =
+        parse_sql_query("select sum(x) over (order by y groups 1 preceding exclude ties) from t")
+            .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT sum("x") OVER (ORDER BY "y" GROUPS 1 PRECEDING EXCLUDE TIES) FROM "t""#
+    );
+}
+
+#[test]
+fn test_case_rendering() {
+    let res = parse_sql_query(
+        "select case x when 1 then 'one' when 2 then 'two' end, \
+         case when a > 0 then case when b > 0 then 1 else 2 end else 3 end from t",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT CASE "x" WHEN 1 THEN 'one' WHEN 2 THEN 'two' END, CASE WHEN "a" > 0 THEN CASE WHEN "b" > 0 THEN 1 ELSE 2 END ELSE 3 END FROM "t""#
+    );
+
+    let expr = Expr::Case {
+        operand: None,
+        conditions: vec![],
+        results: vec![],
+        else_result: Some(Box::new(Expr::Value(number("1")))),
+    };
+    assert!(expr.sql(&Dialect::postgres()).is_err());
+
+    let expr = Expr::Case {
+        operand: None,
+        conditions: vec![Expr::Identifier(Ident::new("a"))],
+        results: vec![],
+        else_result: None,
+    };
+    assert!(expr.sql(&Dialect::postgres()).is_err());
+}
+
+#[test]
+fn test_groups_window_frame() {
+    let res = parse_sql_query(
+        "select sum(x) over (order by y groups between 1 preceding and 1 following) from t",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT sum("x") OVER (ORDER BY "y" GROUPS BETWEEN 1 PRECEDING AND 1 FOLLOWING) FROM "t""#
+    );
+    assert!(res.sql(&Dialect::sqlite()).is_ok());
+    assert_matches!(
+        res.sql(&Dialect::bigquery()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_collate() {
+    let res =
+        parse_sql_query(r#"select * from t where a collate "C" < b order by c collate "en_US""#)
+            .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t" WHERE "a" COLLATE "C" < "b" ORDER BY "c" COLLATE "en_US""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT * FROM `t` WHERE `a` COLLATE `C` < `b` ORDER BY `c` COLLATE `en_US`"
+    );
+
+    // Operands binding more loosely than COLLATE are parenthesized
+    let expr = Expr::Collate {
+        expr: Box::new(Expr::BinaryOp {
+            left: Box::new(Expr::Identifier(Ident::new("a"))),
+            op: BinaryOperator::StringConcat,
+            right: Box::new(Expr::Identifier(Ident::new("b"))),
+        }),
+        collation: ObjectName(vec![Ident::new("C")]),
+    };
+    let dialect =
+        Dialect::postgres().with_identifier_hook(true, |ident| ident.value.to_lowercase());
+    assert_eq!(expr.sql(&dialect).unwrap(), r#"("a" || "b") COLLATE "C""#);
+}
+
+#[test]
+fn test_generic_dialect() {
+    let res = parse_sql_query(
+        "select a, concat(b, 'x') as c, count(*) from t where d ilike 'y%' \
+         group by a, b order by a desc nulls last limit 10 offset 5",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::generic()).unwrap(),
+        r#"SELECT "a", (coalesce("b", '') || 'x') AS "c", count(*) FROM "t" WHERE lower("d") LIKE lower('y%') GROUP BY "a", "b" ORDER BY "a" DESC NULLS LAST LIMIT 10 OFFSET 5 ROWS"#
+    );
+}
+
+#[test]
+fn test_query_to_dot() {
+    let res = parse_sql_query(
+        "select * from t join (select b from u) as v on t.a = v.b left join w using (a)",
+    )
+    .unwrap();
+    let dot = res.to_dot();
+    assert!(dot.starts_with("digraph query {"));
+    assert!(dot.ends_with("}\n"));
+    for label in [
+        r#"[label="Query"]"#,
+        r#"[label="Select"]"#,
+        r#"[label="Table t"]"#,
+        r#"[label="INNER JOIN"]"#,
+        r#"[label="Derived AS v"]"#,
+        r#"[label="Table u"]"#,
+        r#"[label="LEFT JOIN"]"#,
+        r#"[label="Table w"]"#,
+    ] {
+        assert!(dot.contains(label), "{} not in {}", label, dot);
+    }
+    // Two queries, a select each, four tables and two joins
+    assert_eq!(dot.matches("[label=").count(), 10);
+    assert_eq!(dot.matches(" -> ").count(), 9);
+}
+
+#[test]
+fn test_dummy_from_table() {
+    let res = parse_sql_query("select 1 as a, 'x' as b union all select a, b from t").unwrap();
+    let oracle = Dialect {
+        dummy_from_table: Some(ObjectName(vec![Ident::new("DUAL")])),
+        ..Dialect::generic()
+    };
+    assert_eq!(
+        res.sql(&oracle).unwrap(),
+        r#"SELECT 1 AS "a", 'x' AS "b" FROM "DUAL" UNION ALL SELECT "a", "b" FROM "t""#
+    );
+
+    let hana = Dialect {
+        dummy_from_table: Some(ObjectName(vec![Ident::new("sys"), Ident::new("dummy")])),
+        ..Dialect::generic()
+    };
+    assert_eq!(
+        res.sql(&hana).unwrap(),
+        r#"SELECT 1 AS "a", 'x' AS "b" FROM "sys"."dummy" UNION ALL SELECT "a", "b" FROM "t""#
+    );
+
+    assert_eq!(
+        res.sql(&Dialect::generic()).unwrap(),
+        r#"SELECT 1 AS "a", 'x' AS "b" UNION ALL SELECT "a", "b" FROM "t""#
+    );
+}
+
+#[test]
+fn test_redshift_dialect() {
+    let dialect = Dialect::redshift();
+    let res = parse_sql_query(
+        "select approx_distinct(a), isnan(b), concat(c, d, 'x'), md5(c) from t group by e",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT APPROXIMATE COUNT(DISTINCT "a"), "b" = 'NaN', (coalesce("c", '') || coalesce("d", '') || 'x'), md5("c") FROM "t" GROUP BY "e""#
+    );
+
+    let res = parse_sql_query("select listagg(a, ',') within group (order by b) from t").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT LISTAGG("a", ',') WITHIN GROUP (ORDER BY "b") FROM "t""#
+    );
+
+    for sql in [
+        "select date_bin(interval '1 hour', ts) from t",
+        "select regexp_match(a, 'x') from t",
+    ] {
+        let res = parse_sql_query(sql).unwrap();
+        assert!(res.sql(&dialect).is_err(), "{}", sql);
+    }
+}
+
+#[test]
+fn test_standard_offset_rows() {
+    let dialect = Dialect::generic();
+    let res = parse_sql_query("select a from t offset 1").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "a" FROM "t" OFFSET 1 ROW"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" OFFSET 1"#
+    );
+
+    let res = parse_sql_query("select a from t offset 2").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "a" FROM "t" OFFSET 2 ROWS"#
+    );
+
+    // Explicit keywords are kept
+    let res = parse_sql_query("select a from t offset 2 row").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "a" FROM "t" OFFSET 2 ROW"#
+    );
+}
+
+#[test]
+fn test_trino_dialect() {
+    let res = parse_sql_query(
+        "select md5(a), regexp_match(a, '[0-9]+'), isnan(b), approx_distinct(c), arbitrary(d) from t",
+    )
+    .unwrap();
+    let expected = r#"SELECT lower(to_hex(md5(to_utf8("a")))), regexp_extract("a", '[0-9]+'), is_nan("b"), approx_distinct("c"), arbitrary("d") FROM "t""#;
+    assert_eq!(res.sql(&Dialect::trino()).unwrap(), expected);
+    assert_eq!(res.sql(&Dialect::presto()).unwrap(), expected);
+
+    let res = parse_sql_query("select extract(epoch from ts) from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::trino()).unwrap(),
+        r#"SELECT to_unixtime("ts") FROM "t""#
+    );
+}
+
+#[test]
+fn test_table_sample() {
+    let res =
+        parse_sql_query("select a from t as s tablesample bernoulli(10) repeatable(42)").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" AS "s" TABLESAMPLE BERNOULLI(10) REPEATABLE(42)"#
+    );
+    assert_matches!(
+        res.sql(&Dialect::trino()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+
+    let res = parse_sql_query("select a from t tablesample system(5) where a > 0").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" TABLESAMPLE SYSTEM(5) WHERE "a" > 0"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::trino()).unwrap(),
+        r#"SELECT "a" FROM "t" TABLESAMPLE SYSTEM(5) WHERE "a" > 0"#
+    );
+    assert!(res.sql(&Dialect::mysql()).is_err());
+}
+
+#[test]
+fn test_spark_transforms() {
+    let res = parse_sql_query("select median(a), array_agg(b), percentile_approx(a, 0.9) from t")
+        .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::spark()).unwrap(),
+        "SELECT percentile_approx(`a`, 0.5), collect_list(`b`), percentile_approx(`a`, 0.9) FROM `t`"
+    );
+    assert_matches!(
+        parse_sql_query("select median(a, b) from t")
+            .unwrap()
+            .sql(&Dialect::spark()),
+        Err(SqlGenError::DialectError(_))
+    );
+}
+
+#[test]
+fn test_union_branches_to_ctes() {
+    let mut res = parse_sql_query(
+        "with branch_1 as (select a from t1) select a from branch_1 union all select a from branch_2 union all select a from t3 order by a",
+    )
+    .unwrap();
+    res.union_branches_to_ctes().unwrap();
+    let with = res.with.as_ref().unwrap();
+    let names: Vec<_> = with
+        .cte_tables
+        .iter()
+        .map(|cte| cte.alias.name.value.as_str())
+        .collect();
+    assert_eq!(names, vec!["branch_1", "branch_3", "branch_4", "branch_5"]);
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        concat!(
+            r#"WITH "branch_1" AS (SELECT "a" FROM "t1"), "#,
+            r#""branch_3" AS (SELECT "a" FROM "branch_1"), "#,
+            r#""branch_4" AS (SELECT "a" FROM "branch_2"), "#,
+            r#""branch_5" AS (SELECT "a" FROM "t3") "#,
+            r#"SELECT * FROM "branch_3" UNION ALL SELECT * FROM "branch_4" UNION ALL SELECT * FROM "branch_5" ORDER BY "a""#,
+        )
+    );
+
+    // A UNION under a UNION ALL stays in a single branch
+    let mut res =
+        parse_sql_query("select a from t1 union select a from t2 union all select a from t3")
+            .unwrap();
+    res.union_branches_to_ctes().unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"WITH "branch_1" AS (SELECT "a" FROM "t1" UNION SELECT "a" FROM "t2"), "branch_2" AS (SELECT "a" FROM "t3") SELECT * FROM "branch_1" UNION ALL SELECT * FROM "branch_2""#
+    );
+
+    let mut res = parse_sql_query("select a from t1 except select a from t2").unwrap();
+    assert_matches!(
+        res.union_branches_to_ctes(),
+        Err(SqlGenError::RewriteError(_))
+    );
+}
+
+#[test]
+fn test_oracle_dialect() {
+    let res = parse_sql_query(
+        "select nvl(a, 0), to_char(b), trunc(c) from t where d = true or e = false order by a limit 10 offset 5",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT nvl("a", 0), to_char("b"), trunc("c") FROM "t" WHERE "d" = 1 OR "e" = 0 ORDER BY "a" OFFSET 5 ROWS FETCH FIRST 10 ROWS ONLY"#
+    );
+
+    let res = parse_sql_query("select 1 limit 1").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT 1 FROM "DUAL" FETCH FIRST 1 ROWS ONLY"#
+    );
+
+    // Boolean literals are values, or comparisons where a predicate is needed
+    let res = parse_sql_query(
+        "select true as a, case when false then 1 end as b from (select * from t where true) as s join u on true where b and not false",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT 1 AS "a", CASE WHEN 1 = 0 THEN 1 END AS "b" FROM (SELECT * FROM "t" WHERE 1 = 1) "s" JOIN "u" ON 1 = 1 WHERE "b" AND NOT 1 = 0"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT 1 AS [a], CASE WHEN 1 = 0 THEN 1 END AS [b] FROM (SELECT * FROM [t] WHERE 1 = 1) AS [s] JOIN [u] ON 1 = 1 WHERE [b] AND NOT 1 = 0"
+    );
+
+    // Predicates in the select list are selected as 1 or 0
+    let res = parse_sql_query("select a = 1, a > b as c, true as d from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT CASE WHEN [a] = 1 THEN 1 WHEN NOT ([a] = 1) THEN 0 END, CASE WHEN [a] > [b] THEN 1 WHEN NOT ([a] > [b]) THEN 0 END AS [c], 1 AS [d] FROM [t]"
+    );
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT CASE WHEN "a" = 1 THEN 1 WHEN NOT ("a" = 1) THEN 0 END, CASE WHEN "a" > "b" THEN 1 WHEN NOT ("a" > "b") THEN 0 END AS "c", 1 AS "d" FROM "t""#
+    );
+
+    let res = parse_sql_query("select 1 / extract(epoch from ts) from t where a ~* b and a !~ 'p'")
+        .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT 1 / ((CAST("ts" AS DATE) - DATE '1970-01-01') * 86400) FROM "t" WHERE REGEXP_LIKE("a", "b", 'i') AND NOT REGEXP_LIKE("a", 'p')"#
+    );
+
+    let res = parse_sql_query("select a from t limit 10 fetch first 5 rows only").unwrap();
+    assert_matches!(
+        res.sql(&Dialect::oracle()),
+        Err(SqlGenError::DialectError(_))
+    );
+    assert_matches!(
+        parse_sql_query("select array_agg(a) from t")
+            .unwrap()
+            .sql(&Dialect::oracle()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_like_any() {
+    let res = parse_sql_query("select a from t where a ilike any ('x%', 'y%') and b").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::snowflake()).unwrap(),
+        r#"SELECT "a" FROM "t" WHERE "a" ILIKE ANY ('x%', 'y%') AND "b""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" WHERE ("a" ILIKE 'x%' OR "a" ILIKE 'y%') AND "b""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM `t` WHERE (lower(`a`) LIKE lower('x%') OR lower(`a`) LIKE lower('y%')) AND `b`"
+    );
+
+    let res = parse_sql_query("select a like any ('x%', 'y%', 'z%') from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT ("a" LIKE 'x%' OR "a" LIKE 'y%' OR "a" LIKE 'z%') FROM "t""#
+    );
+
+    // A single operand is a quantified comparison with an array
+    let res = parse_sql_query("select a from t where a like any (b)").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" WHERE "a" LIKE ANY("b")"#
+    );
+
+    let expr = Expr::LikeAny {
+        expr: Box::new(Expr::Identifier(Ident::new("a"))),
+        case_insensitive: true,
+        patterns: vec![],
+    };
+    for dialect in [Dialect::snowflake(), Dialect::postgres()] {
+        assert_matches!(
+            expr.sql(&dialect),
+            Err(SqlGenError::DialectError(msg)) if msg == "LIKE ANY must have a pattern"
+        );
+    }
+}
+
+#[test]
+fn test_table_function_args() {
+    let res = parse_sql_query("select * from generate(start => 1, stop => n) as g").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "generate"("start" => 1, "stop" => "n") AS "g""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT * FROM `generate`(`start` => 1, `stop` => `n`) AS `g`"
+    );
+
+    // Argument names aren't passed to the identifier hook
+    let dialect =
+        Dialect::postgres().with_identifier_hook(true, |ident| format!("ns_{}", ident.value));
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT * FROM "ns_generate"("start" => 1, "stop" => "ns_n") AS "ns_g""#
+    );
+
+    let res = parse_sql_query("select * from now_table() as t").unwrap();
+    assert_matches!(
+        &res.body.as_ref(),
+        SetExpr::Select(select) if matches!(
+            &select.from[0].relation,
+            TableFactor::Table { args: Some(args), .. } if args.is_empty()
+        )
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "now_table"() AS "t""#
+    );
+}
+
+#[test]
+fn test_cast_as_convert() {
+    let mut mssql = Dialect {
+        quote_style: Some('['),
+        cast_style: CastStyle::Convert,
+        ..Dialect::generic()
+    };
+    mssql.data_types.insert(
+        "TIMESTAMP".to_string(),
+        DataTypeName::new("DATETIME2", false).with_convert_style(126),
+    );
+    let res =
+        parse_sql_query("select cast(a as varchar(10)), cast(b as timestamp) from t").unwrap();
+    assert_eq!(
+        res.sql(&mssql).unwrap(),
+        "SELECT CONVERT(CHARACTER VARYING(10), [a]), CONVERT(DATETIME2, [b], 126) FROM [t]"
+    );
+    assert_eq!(
+        res.sql(&Dialect::generic()).unwrap(),
+        r#"SELECT CAST("a" AS CHARACTER VARYING(10)), CAST("b" AS TIMESTAMP) FROM "t""#
+    );
+}
+
+#[test]
+fn test_unnest_ordinality() {
+    let res = parse_sql_query("select * from unnest(a, b) with ordinality as t (x, y, n)").unwrap();
+    assert_matches!(
+        &res.body.as_ref(),
+        SetExpr::Select(select) if matches!(
+            &select.from[0].relation,
+            TableFactor::UNNEST { array_exprs, with_ordinality: true, .. } if array_exprs.len() == 2
+        )
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM UNNEST("a", "b") WITH ORDINALITY AS "t" ("x", "y", "n")"#
+    );
+    assert_matches!(
+        res.sql(&Dialect::bigquery()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+
+    let res = parse_sql_query("select * from unnest(a) as x with offset as o").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT * FROM UNNEST(`a`) AS `x` WITH OFFSET AS `o`"
+    );
+
+    // Offsets count from 0 and ordinalities from 1
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err(),
+        SqlGenError::UnsupportedFeature {
+            feature: "WITH OFFSET".to_string(),
+            dialect: "postgres".to_string(),
+        }
+    );
+    let res = parse_sql_query("select * from unnest(a) with ordinality as x").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap_err(),
+        SqlGenError::UnsupportedFeature {
+            feature: "WITH ORDINALITY".to_string(),
+            dialect: "bigquery".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_lint_set_operation_wildcard() {
+    let lint = |sql: &str| parse_sql_query(sql).unwrap().lint().unwrap();
+    assert_eq!(
+        lint("select a, b from t1 union all select a, b from t2"),
+        vec![]
+    );
+    assert_eq!(
+        lint("select * from (select a from t1 union select a from t2) as s"),
+        vec![]
+    );
+    let wildcard = |op: &str| Lint {
+        kind: LintKind::SetOperationWildcard,
+        severity: Severity::Warning,
+        message: format!(
+            "A branch of {} selects a wildcard, so its columns change with the schema",
+            op
+        ),
+    };
+    assert_eq!(
+        lint("select a from t1 union all select * from t2 union all (select t3.* from t3)"),
+        vec![wildcard("UNION"), wildcard("UNION")]
+    );
+    assert_eq!(
+        lint("select a from t1 except (select * from t2 intersect select a from t3)"),
+        vec![wildcard("INTERSECT")]
+    );
+}
+
+#[test]
+fn test_lateral_apply() {
+    let res = parse_sql_query(
+        "select * from t join lateral (select b from u where u.a = t.a) as x on true left join lateral (select c from v where v.a = t.a) as y on true",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t" JOIN LATERAL (SELECT "b" FROM "u" WHERE "u"."a" = "t"."a") AS "x" ON true LEFT JOIN LATERAL (SELECT "c" FROM "v" WHERE "v"."a" = "t"."a") AS "y" ON true"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT * FROM [t] CROSS APPLY (SELECT [b] FROM [u] WHERE [u].[a] = [t].[a]) AS [x] OUTER APPLY (SELECT [c] FROM [v] WHERE [v].[a] = [t].[a]) AS [y]"
+    );
+
+    let res = parse_sql_query("select * from t, lateral (select t.a) as x").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t", LATERAL (SELECT "t"."a") AS "x""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT * FROM [t] CROSS APPLY (SELECT [t].[a]) AS [x]"
+    );
+
+    let res = parse_sql_query("select * from t join lateral (select b from u) as x on x.b = t.a")
+        .unwrap();
+    assert_matches!(
+        res.sql(&Dialect::mssql()),
+        Err(SqlGenError::DialectError(_))
+    );
+}
+
+#[test]
+fn test_mssql_limit() {
+    let res = parse_sql_query("select a from t order by a limit 10").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT [a] FROM [t] ORDER BY [a] OFFSET 0 ROWS FETCH FIRST 10 ROWS ONLY"
+    );
+    let res = parse_sql_query("select a from t order by a limit 10 offset 20").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT [a] FROM [t] ORDER BY [a] OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY"
+    );
+
+    // Without an ORDER BY, the limit is a TOP
+    let res = parse_sql_query("select distinct a from t where b limit 1").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT DISTINCT TOP (1) [a] FROM [t] WHERE [b]"
+    );
+}
+
+#[test]
+fn test_mssql_dialect() {
+    let res = parse_sql_query("select extract(epoch from ts) from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT DATEDIFF_BIG(SECOND, '1970-01-01', [ts]) FROM [t]"
+    );
+
+    let res = parse_sql_query("select * from unnest(a) as x").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap_err(),
+        SqlGenError::UnsupportedFeature {
+            feature: "UNNEST".to_string(),
+            dialect: "mssql".to_string(),
+        }
+    );
+
+    // SQL Server's concat skips NULLs, unlike ||
+    let res = parse_sql_query("select a || '-' || b, concat(a, b), 'x' || 'y' from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT CASE WHEN [a] IS NULL OR [b] IS NULL THEN NULL ELSE concat([a], '-', [b]) END, concat([a], [b]), concat('x', 'y') FROM [t]"
+    );
+    let res = parse_sql_query("select a || null from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT CASE WHEN [a] IS NULL OR NULL IS NULL THEN NULL ELSE concat([a], NULL) END FROM [t]"
+    );
+}
+
+#[test]
+fn test_values_booleans() {
+    let res =
+        parse_sql_query("select * from (values (1, true), (2, false), (3, a > 0)) as v (x, y)")
+            .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM (VALUES (1, true), (2, false), (3, "a" > 0)) AS "v" ("x", "y")"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT * FROM (VALUES (1, 1), (2, 0), (3, CASE WHEN [a] > 0 THEN 1 WHEN NOT ([a] > 0) THEN 0 END)) AS [v] ([x], [y])"
+    );
+}
+
+#[test]
+fn test_commented() {
+    let mut select = verified_only_select("SELECT a + 1 FROM t WHERE b OR c");
+    select.projection = vec![SelectItem::UnnamedExpr(Expr::Commented(Box::new(
+        Commented::new(Expr::Identifier(Ident::new("a"))).with_trailing_comment("node 2"),
+    )))];
+    select.selection = Some(Expr::BinaryOp {
+        left: Box::new(Expr::Nested(Box::new(Expr::Commented(Box::new(
+            Commented::new(select.selection.take().unwrap()).with_leading_comment("filter"),
+        ))))),
+        op: BinaryOperator::And,
+        right: Box::new(Expr::Identifier(Ident::new("d"))),
+    });
+    let commented = Commented::new(select).with_leading_comment("node 1 */ DROP TABLE t; /*");
+    assert_eq!(
+        commented.sql(&Dialect::postgres()).unwrap(),
+        r#"/* node 1 * / DROP TABLE t; / * */ SELECT "a" /* node 2 */ FROM "t" WHERE (/* filter */ "b" OR "c") AND "d""#
+    );
+}
+
+#[test]
+fn test_limit_expression() {
+    let res = parse_sql_query("select a from t order by a limit 10 + n").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" ORDER BY "a" LIMIT 10 + "n""#
+    );
+    assert_matches!(
+        res.sql(&Dialect::mysql()),
+        Err(SqlGenError::DialectError(_))
+    );
+    let oracle = Dialect {
+        limit_allows_expression: true,
+        ..Dialect::oracle()
+    };
+    assert_eq!(
+        res.sql(&oracle).unwrap(),
+        r#"SELECT "a" FROM "t" ORDER BY "a" FETCH FIRST (10 + "n") ROWS ONLY"#
+    );
+
+    let res = parse_sql_query("select a from t limit ?").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM `t` LIMIT ?"
+    );
+}
+
+#[test]
+fn test_optimizer_hints() {
+    let mut select = verified_only_select("SELECT DISTINCT a FROM t");
+    select.hints = vec!["INDEX(t idx_a)".to_string(), "NO_ICP(t) */".to_string()];
+    assert_eq!(
+        select.sql(&Dialect::mysql()).unwrap(),
+        "SELECT /*+ INDEX(t idx_a) NO_ICP(t) * / */ DISTINCT `a` FROM `t`"
+    );
+    assert_eq!(
+        select.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT DISTINCT "a" FROM "t""#
+    );
+}
+
+#[test]
+fn test_paginate() {
+    let order_by = || {
+        vec![OrderByExpr {
+            expr: Expr::Identifier(Ident::new("id")),
+            asc: None,
+            nulls_first: None,
+        }]
+    };
+    let page = |sql: &str, page: u64| {
+        parse_sql_query(sql)
+            .unwrap()
+            .paginate(page, 20, order_by())
+            .unwrap()
+            .sql(&Dialect::postgres())
+            .unwrap()
+    };
+    assert_eq!(
+        page("select id, a from t order by a", 0),
+        r#"SELECT "id", "a" FROM "t" ORDER BY "a", "id" LIMIT 20"#
+    );
+    assert_eq!(
+        page("select id, a from t order by id desc", 0),
+        r#"SELECT "id", "a" FROM "t" ORDER BY "id" DESC LIMIT 20"#
+    );
+    assert_eq!(
+        page("select id, a from t", 1),
+        r#"SELECT "id", "a" FROM "t" ORDER BY "id" LIMIT 20 OFFSET 20"#
+    );
+    assert_eq!(
+        page("select id, a from t", 3),
+        r#"SELECT "id", "a" FROM "t" ORDER BY "id" LIMIT 20 OFFSET 60"#
+    );
+    assert_eq!(
+        page("select id from t order by a desc limit 100", 2),
+        r#"SELECT * FROM (SELECT "id" FROM "t" ORDER BY "a" DESC LIMIT 100) AS "page" ORDER BY "id" LIMIT 20 OFFSET 40"#
+    );
+
+    let query = parse_sql_query("select id from t").unwrap();
+    assert_matches!(
+        query.clone().paginate(1, 20, vec![]),
+        Err(SqlGenError::RewriteError(_))
+    );
+    assert_matches!(
+        query.paginate(u64::MAX, 20, order_by()),
+        Err(SqlGenError::RewriteError(_))
+    );
+}
+
+#[test]
+fn test_insert() {
+    let excluded = |column: &str| Assignment {
+        id: vec![Ident::new(column)],
+        value: Expr::CompoundIdentifier(vec![Ident::new("excluded"), Ident::new(column)]),
+    };
+    let insert = |source: &str, on_conflict: Option<OnConflict>| Insert {
+        table_name: ObjectName(vec![Ident::new("t")]),
+        columns: vec![Ident::new("id"), Ident::new("a")],
+        source: Box::new(parse_sql_query(source).unwrap()),
+        on_conflict,
+    };
+
+    let values = insert("values (1, 'x'), (2, 'y')", None);
+    assert_eq!(
+        values.sql(&Dialect::postgres()).unwrap(),
+        r#"INSERT INTO "t" ("id", "a") VALUES (1, 'x'), (2, 'y')"#
+    );
+    let select = insert("select id, a from s where a is not null", None);
+    assert_eq!(
+        select.sql(&Dialect::postgres()).unwrap(),
+        r#"INSERT INTO "t" ("id", "a") SELECT "id", "a" FROM "s" WHERE "a" IS NOT NULL"#
+    );
+
+    let do_update = OnConflict::DoUpdate {
+        conflict_target: vec![Ident::new("id")],
+        assignments: vec![excluded("a")],
+    };
+    let upsert = insert("values (1, 'x')", Some(do_update));
+    assert_eq!(
+        upsert.sql(&Dialect::postgres()).unwrap(),
+        r#"INSERT INTO "t" ("id", "a") VALUES (1, 'x') ON CONFLICT ("id") DO UPDATE SET "a" = "excluded"."a""#
+    );
+    assert_eq!(
+        upsert.sql(&Dialect::mysql()).unwrap(),
+        "INSERT INTO `t` (`id`, `a`) VALUES (1, 'x') AS `excluded` ON DUPLICATE KEY UPDATE `a` = `excluded`.`a`"
+    );
+    assert_matches!(
+        upsert.sql(&Dialect::bigquery()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+    let upsert = insert(
+        "select id, a from s",
+        Some(OnConflict::DoUpdate {
+            conflict_target: vec![Ident::new("id")],
+            assignments: vec![excluded("a")],
+        }),
+    );
+    assert_eq!(
+        upsert.sql(&Dialect::mysql()).unwrap_err(),
+        SqlGenError::UnsupportedFeature {
+            feature: "ON CONFLICT DO UPDATE from a SELECT".to_string(),
+            dialect: "mysql".to_string(),
+        }
+    );
+
+    let do_nothing = OnConflict::DoNothing {
+        conflict_target: vec![],
+    };
+    let ignore = insert("values (1, 'x')", Some(do_nothing));
+    assert_eq!(
+        ignore.sql(&Dialect::sqlite()).unwrap(),
+        r#"INSERT INTO "t" ("id", "a") VALUES (1, 'x') ON CONFLICT DO NOTHING"#
+    );
+    assert_matches!(
+        ignore.sql(&Dialect::mysql()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_natural_join() {
+    let res = parse_sql_query("select * from t1 natural join t2 natural left join t3").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT * FROM "t1" NATURAL JOIN "t2" NATURAL LEFT JOIN "t3""#
+    );
+    assert_matches!(
+        res.sql(&Dialect::mssql()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+    let mut dialect = Dialect::postgres();
+    dialect.disallow_natural_join = true;
+    assert_matches!(
+        res.sql(&dialect),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+
+    let natural = |table: &str| {
+        Lint {
+        kind: LintKind::NaturalJoin,
+        severity: Severity::Warning,
+        message: format!(
+            "NATURAL join with {} joins on the columns the tables share, which change with the schema",
+            table
+        ),
+    }
+    };
+    assert_eq!(
+        res.lint().unwrap(),
+        vec![natural(r#""t2""#), natural(r#""t3""#)]
+    );
+    let res = parse_sql_query(
+        "select a from t1 join t2 using (a) where a in (select a from (u1 natural join u2))",
+    )
+    .unwrap();
+    assert_eq!(res.lint().unwrap(), vec![natural(r#""u2""#)]);
+}
+
+#[test]
+fn test_create_table_as() {
+    let create = |or_replace: bool, temporary: bool| CreateTableAs {
+        or_replace,
+        temporary,
+        name: ObjectName(vec![Ident::new("s"), Ident::new("t")]),
+        query: Box::new(parse_sql_query("select a, count(*) as n from u group by a").unwrap()),
+    };
+    assert_eq!(
+        create(false, false).sql(&Dialect::postgres()).unwrap(),
+        r#"CREATE TABLE "s"."t" AS SELECT "a", count(*) AS "n" FROM "u" GROUP BY "a""#
+    );
+    assert_eq!(
+        create(false, true).sql(&Dialect::postgres()).unwrap(),
+        r#"CREATE TEMPORARY TABLE "s"."t" AS SELECT "a", count(*) AS "n" FROM "u" GROUP BY "a""#
+    );
+    assert_eq!(
+        create(true, true).sql(&Dialect::snowflake()).unwrap(),
+        r#"CREATE OR REPLACE TEMPORARY TABLE "s"."t" AS SELECT "a", count(*) AS "n" FROM "u" GROUP BY "a""#
+    );
+    assert_matches!(
+        create(true, false).sql(&Dialect::postgres()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_lint_qualified_using_column() {
+    let lint = |sql: &str| parse_sql_query(sql).unwrap().lint().unwrap();
+    let qualified = |column: &str| Lint {
+        kind: LintKind::QualifiedUsingColumn,
+        severity: Severity::Warning,
+        message: format!(
+            "Column {} is merged by a USING join and shouldn't be qualified",
+            column
+        ),
+    };
+    assert_eq!(
+        lint("select id, t.a, u.b from t join u using (id) where id > 1"),
+        vec![]
+    );
+    assert_eq!(
+        lint("select t.id, v.id from t join u using (id) join v on v.x = u.b"),
+        vec![qualified(r#""t"."id""#)]
+    );
+    assert_eq!(
+        lint("select a from t as x left join (u join w using (k)) using (id) where w.k = 1 and x.id = 2"),
+        vec![qualified(r#""w"."k""#), qualified(r#""x"."id""#)]
+    );
+    // The reference resolves to the innermost table named `t`
+    assert_eq!(
+        lint("select id from t join u using (id) where exists (select 1 from t where t.id = 1 and u.id = 2)"),
+        vec![qualified(r#""u"."id""#)]
+    );
+}
+
+#[test]
+fn test_create_view() {
+    let view = |or_replace: bool, materialized: bool, columns: &[&str]| CreateView {
+        or_replace,
+        materialized,
+        name: ObjectName(vec![Ident::new("v")]),
+        columns: columns.iter().map(|column| Ident::new(*column)).collect(),
+        query: Box::new(parse_sql_query("select a, b from t where a > 0").unwrap()),
+    };
+    assert_eq!(
+        view(false, false, &[]).sql(&Dialect::postgres()).unwrap(),
+        r#"CREATE VIEW "v" AS SELECT "a", "b" FROM "t" WHERE "a" > 0"#
+    );
+    assert_eq!(
+        view(false, false, &["x", "y"])
+            .sql(&Dialect::mysql())
+            .unwrap(),
+        "CREATE VIEW `v` (`x`, `y`) AS SELECT `a`, `b` FROM `t` WHERE `a` > 0"
+    );
+    assert_eq!(
+        view(true, false, &[]).sql(&Dialect::postgres()).unwrap(),
+        r#"CREATE OR REPLACE VIEW "v" AS SELECT "a", "b" FROM "t" WHERE "a" > 0"#
+    );
+    assert_eq!(
+        view(false, true, &[]).sql(&Dialect::postgres()).unwrap(),
+        r#"CREATE MATERIALIZED VIEW "v" AS SELECT "a", "b" FROM "t" WHERE "a" > 0"#
+    );
+    assert_matches!(
+        view(false, true, &[]).sql(&Dialect::mysql()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+
+    // Replacing a view is spelled differently, or not at all
+    assert_eq!(
+        view(true, false, &[]).sql(&Dialect::mssql()).unwrap(),
+        "CREATE OR ALTER VIEW [v] AS SELECT [a], [b] FROM [t] WHERE [a] > 0"
+    );
+    assert_matches!(
+        view(true, false, &[]).sql(&Dialect::sqlite()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+    assert_eq!(
+        view(true, true, &[]).sql(&Dialect::postgres()).unwrap_err(),
+        SqlGenError::UnsupportedFeature {
+            feature: "CREATE OR REPLACE MATERIALIZED VIEW".to_string(),
+            dialect: "postgres".to_string(),
+        }
+    );
+    assert_eq!(
+        view(true, true, &[]).sql(&Dialect::snowflake()).unwrap(),
+        r#"CREATE OR REPLACE MATERIALIZED VIEW "v" AS SELECT "a", "b" FROM "t" WHERE "a" > 0"#
+    );
+}
+
+#[test]
+fn test_date_add_style() {
+    let res = parse_sql_query(
+        "select ts + interval '2' day, ts - interval '3 hours', interval '1 month' + d from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "ts" + INTERVAL '2' DAY, "ts" - INTERVAL '3 hours', INTERVAL '1 month' + "d" FROM "tbl""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT DATEADD(DAY, 2, [ts]), DATEADD(HOUR, -3, [ts]), DATEADD(MONTH, 1, [d]) FROM [tbl]"
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT DATE_ADD(`ts`, INTERVAL 2 DAY), DATE_SUB(`ts`, INTERVAL 3 HOUR), DATE_ADD(`d`, INTERVAL 1 MONTH) FROM `tbl`"
+    );
+
+    let res = parse_sql_query("select ts + interval '1:30' hour to minute from tbl").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap_err().to_string(),
+        "sqlgen error: Adding INTERVAL '1:30' HOUR TO MINUTE to a date is not supported by dialect mssql"
+    );
+
+    // Fractional quantities are converted to a smaller unit, since DATEADD
+    // and DATE_ADD truncate them
+    let res = parse_sql_query(
+        "select ts + interval '1.5 hours', ts - interval '0.25' day, ts + interval '2.5' second from tbl",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap_err().to_string(),
+        "sqlgen error: Adding INTERVAL '2.5' SECOND to a date is not supported by dialect mssql"
+    );
+    let res =
+        parse_sql_query("select ts + interval '1.5 hours', ts - interval '0.25' day from tbl")
+            .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT DATEADD(MINUTE, 90, [ts]), DATEADD(HOUR, -6, [ts]) FROM [tbl]"
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT DATE_ADD(`ts`, INTERVAL 90 MINUTE), DATE_SUB(`ts`, INTERVAL 6 HOUR) FROM `tbl`"
+    );
+    let res = parse_sql_query("select ts + interval '1.5 months' from tbl").unwrap();
+    assert_matches!(
+        res.sql(&Dialect::mysql()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_explain() {
+    let explain = |analyze: bool, verbose: bool, format: Option<ExplainFormat>| Explain {
+        analyze,
+        verbose,
+        format,
+        query: Box::new(parse_sql_query("select a from t").unwrap()),
+    };
+    assert_eq!(
+        explain(false, false, None)
+            .sql(&Dialect::postgres())
+            .unwrap(),
+        r#"EXPLAIN SELECT "a" FROM "t""#
+    );
+    assert_eq!(
+        explain(true, true, None).sql(&Dialect::postgres()).unwrap(),
+        r#"EXPLAIN ANALYZE VERBOSE SELECT "a" FROM "t""#
+    );
+    assert_eq!(
+        explain(false, false, None).sql(&Dialect::mysql()).unwrap(),
+        "EXPLAIN SELECT `a` FROM `t`"
+    );
+
+    let json = Some(ExplainFormat::Json);
+    assert_eq!(
+        explain(false, false, json)
+            .sql(&Dialect::postgres())
+            .unwrap(),
+        r#"EXPLAIN (FORMAT JSON) SELECT "a" FROM "t""#
+    );
+    assert_eq!(
+        explain(true, false, json)
+            .sql(&Dialect::postgres())
+            .unwrap(),
+        r#"EXPLAIN (ANALYZE, FORMAT JSON) SELECT "a" FROM "t""#
+    );
+    assert_eq!(
+        explain(false, false, json).sql(&Dialect::mysql()).unwrap(),
+        "EXPLAIN FORMAT=JSON SELECT `a` FROM `t`"
+    );
+    assert_matches!(
+        explain(false, true, json).sql(&Dialect::mysql()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_date_diff() {
+    let days = parse_sql_query("select date_diff('day', a, b) from t").unwrap();
+    let seconds = parse_sql_query("select date_diff('second', a - c, b + c) from t").unwrap();
+    assert_eq!(
+        days.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT trunc(EXTRACT(EPOCH FROM ("b" - "a")) / 86400) FROM "t""#
+    );
+    assert_eq!(
+        seconds.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT trunc(EXTRACT(EPOCH FROM ("b" + "c" - ("a" - "c")))) FROM "t""#
+    );
+    // DATEDIFF counts the boundaries crossed, so whole units are derived from
+    // the microseconds elapsed
+    assert_eq!(
+        days.sql(&Dialect::mssql()).unwrap(),
+        "SELECT (DATEDIFF_BIG(MICROSECOND, [a], [b]) / 86400000000) FROM [t]"
+    );
+    assert_eq!(
+        seconds.sql(&Dialect::mssql()).unwrap(),
+        "SELECT (DATEDIFF_BIG(MICROSECOND, [a] - [c], [b] + [c]) / 1000000) FROM [t]"
+    );
+    assert_eq!(
+        days.sql(&Dialect::redshift()).unwrap(),
+        r#"SELECT (DATEDIFF(MICROSECOND, "a", "b") / 86400000000) FROM "t""#
+    );
+    assert_eq!(
+        days.sql(&Dialect::mysql()).unwrap(),
+        "SELECT TIMESTAMPDIFF(DAY, `a`, `b`) FROM `t`"
+    );
+    assert_eq!(
+        seconds.sql(&Dialect::mysql()).unwrap(),
+        "SELECT TIMESTAMPDIFF(SECOND, `a` - `c`, `b` + `c`) FROM `t`"
+    );
+
+    let months = parse_sql_query("select date_diff('month', a, b) from t").unwrap();
+    assert_eq!(
+        months.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        "sqlgen error: Unsupported date_diff unit for dialect: month"
+    );
+    assert_matches!(
+        months.sql(&Dialect::mssql()),
+        Err(SqlGenError::DialectError(_))
+    );
+    let millis = parse_sql_query("select date_diff('millisecond', a, b) from t").unwrap();
+    assert_matches!(
+        millis.sql(&Dialect::mysql()),
+        Err(SqlGenError::DialectError(_))
+    );
+}
+
+#[test]
+fn test_statement_batch() {
+    let query = parse_sql_query("select a, b from t where a > 0").unwrap();
+    let statements = vec![
+        Statement::from(CreateTableAs {
+            or_replace: false,
+            temporary: true,
+            name: ObjectName(vec![Ident::new("tmp")]),
+            query: Box::new(query),
+        }),
+        Statement::from(parse_sql_query("select count(*) from tmp").unwrap()),
+    ];
+    assert_eq!(
+        statements[1]
+            .to_statement_sql(&Dialect::postgres())
+            .unwrap(),
+        r#"SELECT count(*) FROM "tmp";"#
+    );
+    assert_eq!(
+        Statement::batch_sql(&statements, &Dialect::postgres()).unwrap(),
+        "CREATE TEMPORARY TABLE \"tmp\" AS SELECT \"a\", \"b\" FROM \"t\" WHERE \"a\" > 0;\nSELECT count(*) FROM \"tmp\";"
+    );
+    assert_eq!(
+        Statement::batch_sql(&statements, &Dialect::mysql()).unwrap(),
+        "CREATE TEMPORARY TABLE `tmp` AS SELECT `a`, `b` FROM `t` WHERE `a` > 0;\nSELECT count(*) FROM `tmp`;"
+    );
+}
+
+#[test]
+fn test_string_agg_spellings() {
+    let res = parse_sql_query("select string_agg(name, ', ') from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT string_agg("name", ', ') FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT group_concat("name", ', ') FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT GROUP_CONCAT(`name` SEPARATOR ', ') FROM `t`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT listagg("name", ', ') FROM "t""#
+    );
+
+    let res =
+        parse_sql_query("select string_agg(distinct name, ', ' order by name) from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT string_agg(DISTINCT "name", ', ' ORDER BY "name") FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::redshift()).unwrap(),
+        r#"SELECT listagg(DISTINCT "name", ', ') WITHIN GROUP (ORDER BY "name") FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT GROUP_CONCAT(DISTINCT `name` ORDER BY `name` SEPARATOR ', ') FROM `t`"
+    );
+    assert_matches!(
+        res.sql(&Dialect::sqlite()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_render_script() {
+    let statements = vec![
+        Statement::from(CreateView {
+            or_replace: false,
+            materialized: false,
+            name: ObjectName(vec![Ident::new("v")]),
+            columns: vec![],
+            query: Box::new(parse_sql_query("select a from t").unwrap()),
+        }),
+        Statement::from(parse_sql_query("select a from v").unwrap()),
+    ];
+    assert_eq!(
+        render_script(&statements, &Dialect::postgres(), false).unwrap(),
+        "CREATE VIEW \"v\" AS SELECT \"a\" FROM \"t\";\nSELECT \"a\" FROM \"v\";"
+    );
+    assert_eq!(
+        render_script(&statements, &Dialect::postgres(), true).unwrap(),
+        "BEGIN;\nCREATE VIEW \"v\" AS SELECT \"a\" FROM \"t\";\nSELECT \"a\" FROM \"v\";\nCOMMIT;"
+    );
+    assert_eq!(
+        render_script(&statements, &Dialect::mysql(), true).unwrap(),
+        "START TRANSACTION;\nCREATE VIEW `v` AS SELECT `a` FROM `t`;\nSELECT `a` FROM `v`;\nCOMMIT;"
+    );
+    assert_eq!(
+        render_script(&statements, &Dialect::mssql(), true).unwrap(),
+        "BEGIN TRANSACTION;\nCREATE VIEW [v] AS SELECT [a] FROM [t];\nSELECT [a] FROM [v];\nCOMMIT TRANSACTION;"
+    );
+    assert_matches!(
+        render_script(&statements, &Dialect::spark(), true),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_limit_by() {
+    let res = parse_sql_query(
+        "select user_id, ts from events order by ts desc limit 1 by user_id limit 10",
+    )
+    .unwrap();
+    assert_eq!(
+        res.limit_by,
+        Some(LimitBy {
+            limit: Expr::Value(number("1")),
+            by: vec![Expr::Identifier(Ident::new("user_id"))],
+        })
+    );
+    assert_eq!(res.limit, Some(Expr::Value(number("10"))));
+    assert_eq!(
+        res.sql(&Dialect::clickhouse()).unwrap(),
+        r#"SELECT "user_id", "ts" FROM "events" ORDER BY "ts" DESC LIMIT 1 BY "user_id" LIMIT 10"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        "sqlgen error: LIMIT BY is not supported by dialect postgres"
+    );
+
+    let res = parse_sql_query("select a, b, c from t limit 2 by a, b").unwrap();
+    assert_eq!(res.limit, None);
+    assert_eq!(
+        res.sql(&Dialect::clickhouse()).unwrap(),
+        r#"SELECT "a", "b", "c" FROM "t" LIMIT 2 BY "a", "b""#
+    );
+}
+
+#[test]
+fn test_max_render_depth() {
+    let nested = |depth: usize| {
+        let mut query = parse_sql_query("select 1").unwrap();
+        for _ in 0..depth {
+            query = Query {
+                with: None,
+                body: Box::new(SetExpr::Query(Box::new(query))),
+                order_by: vec![],
+                limit_by: None,
+                limit: None,
+                offset: None,
+                fetch: None,
+                lock: None,
+                settings: vec![],
+            };
+        }
+        query
+    };
+    // Take the tree apart iteratively, since dropping it recursively could
+    // overflow the stack too
+    let dismantle = |query: Query| {
+        let mut body = query.body;
+        while let SetExpr::Query(query) = *body {
+            body = query.body;
+        }
+    };
+
+    let query = nested(10_000);
+    assert_eq!(
+        query.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        format!(
+            "sqlgen error: Rendering exceeded the maximum depth of {}",
+            DEFAULT_MAX_RENDER_DEPTH
+        )
+    );
+    let mut dialect = Dialect::postgres();
+    dialect.max_render_depth = Some(10);
+    assert_matches!(query.sql(&dialect), Err(SqlGenError::DepthExceeded(10)));
+    dismantle(query);
+
+    assert_eq!(
+        nested(3).sql(&Dialect::postgres()).unwrap(),
+        "(((SELECT 1)))"
+    );
+    // Each level is a query and a set expression, around the query, its set
+    // expression and the selected expression
+    let query = nested((DEFAULT_MAX_RENDER_DEPTH - 3) / 2);
+    assert!(query.sql(&Dialect::postgres()).is_ok());
+    dismantle(query);
+
+    let mut chain = Expr::Identifier(Ident::new("a"));
+    for _ in 0..100_000 {
+        chain = Expr::BinaryOp {
+            left: Box::new(chain),
+            op: BinaryOperator::Plus,
+            right: Box::new(Expr::Value(number("1"))),
+        };
+    }
+    assert_matches!(
+        chain.sql(&Dialect::postgres()),
+        Err(SqlGenError::DepthExceeded(DEFAULT_MAX_RENDER_DEPTH))
+    );
+    while let Expr::BinaryOp { left, .. } = chain {
+        chain = *left;
+    }
+
+    let mut nested_expr = Expr::Identifier(Ident::new("a"));
+    for _ in 0..100_000 {
+        nested_expr = Expr::Nested(Box::new(nested_expr));
+    }
+    assert_matches!(
+        nested_expr.sql(&Dialect::postgres()),
+        Err(SqlGenError::DepthExceeded(DEFAULT_MAX_RENDER_DEPTH))
+    );
+    while let Expr::Nested(inner) = nested_expr {
+        nested_expr = *inner;
+    }
+}
+
+#[test]
+fn test_query_settings() {
+    let mut query = parse_sql_query("select a, count(*) from t group by a").unwrap();
+    query.settings = vec![
+        Setting {
+            key: "max_threads".to_string(),
+            value: number("8"),
+        },
+        Setting {
+            key: "join_algorithm".to_string(),
+            value: Value::SingleQuotedString("hash".to_string()),
+        },
+    ];
+    assert_eq!(
+        query.sql(&Dialect::clickhouse()).unwrap(),
+        r#"SELECT "a", count(*) FROM "t" GROUP BY "a" SETTINGS max_threads = 8, join_algorithm = 'hash'"#
+    );
+    assert_matches!(
+        query.sql(&Dialect::postgres()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+
+    query.settings[0].key = "max threads".to_string();
+    assert_matches!(
+        query.sql(&Dialect::clickhouse()),
+        Err(SqlGenError::InvalidIdentifier(_))
+    );
+}
+
+#[test]
+fn test_error_context() {
+    let mut res = parse_sql_query("values (1)").unwrap();
+    if let SetExpr::Values(values) = res.body.as_mut() {
+        values.0.clear();
+    }
+    assert_matches!(res.sql(&Dialect::postgres()), Err(SqlGenError::EmptyValues));
+
+    let mut res = parse_sql_query("select a from t").unwrap();
+    if let SetExpr::Select(select) = res.body.as_mut() {
+        select.projection = vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident::new("")))];
+    }
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        r#"sqlgen error: Invalid identifier """#
+    );
+}
+
+#[test]
+fn test_strip_redundant_inner_order_by() {
+    let strip = |sql: &str| {
+        parse_sql_query(sql)
+            .unwrap()
+            .strip_redundant_inner_order_by()
+            .sql(&Dialect::postgres())
+            .unwrap()
+    };
+    assert_eq!(
+        strip("select a from (select a from t order by a) as s order by a desc"),
+        r#"SELECT "a" FROM (SELECT "a" FROM "t") AS "s" ORDER BY "a" DESC"#
+    );
+    assert_eq!(
+        strip("select a from (select a from t order by a limit 10) as s"),
+        r#"SELECT "a" FROM (SELECT "a" FROM "t" ORDER BY "a" LIMIT 10) AS "s""#
+    );
+    assert_eq!(
+        strip("(select a from t order by a) union all (select a from u order by a limit 5) order by a"),
+        r#"(SELECT "a" FROM "t") UNION ALL (SELECT "a" FROM "u" ORDER BY "a" LIMIT 5) ORDER BY "a""#
+    );
+    assert_eq!(
+        strip("select * from (select * from (select a from t order by a) as x) as y"),
+        r#"SELECT * FROM (SELECT * FROM (SELECT "a" FROM "t") AS "x") AS "y""#
+    );
+}
+
+#[test]
+fn test_identifier_special_characters() {
+    let res = parse_sql_query(r#"select "my col" from "it's""#).unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "my col" FROM "it's""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `my col` FROM `it's`"
+    );
+    let res = parse_sql_query(r#"select "a""b", "c]" from t"#).unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a""b", "c]" FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        r#"SELECT [a"b], [c]]] FROM [t]"#
+    );
+
+    let unquoted = Dialect {
+        quote_style: None,
+        ..Dialect::postgres()
+    };
+    assert_eq!(
+        parse_sql_query("select a from t")
+            .unwrap()
+            .sql(&unquoted)
+            .unwrap(),
+        "SELECT a FROM t"
+    );
+    let res = parse_sql_query(r#"select "my col" from t"#).unwrap();
+    assert_matches!(
+        res.sql(&unquoted),
+        Err(SqlGenError::InvalidIdentifier(ident)) if ident == "my col"
+    );
+    let res = parse_sql_query(r#"select "select" from t"#).unwrap();
+    assert_matches!(
+        res.sql(&unquoted),
+        Err(SqlGenError::InvalidIdentifier(ident)) if ident == "select"
+    );
+    // Function names that are keywords are still plain calls
+    assert_eq!(
+        parse_sql_query("select left(a, 1) from t")
+            .unwrap()
+            .sql(&unquoted)
+            .unwrap(),
+        "SELECT left(a, 1) FROM t"
+    );
+    for name in ["a;drop", "x--", "1a", "café"] {
+        let ident = Expr::Identifier(Ident::with_quote('"', name));
+        assert_eq!(
+            ident.sql(&unquoted).unwrap_err(),
+            SqlGenError::InvalidIdentifier(name.to_string())
+        );
+    }
+    assert_eq!(
+        Expr::Identifier(Ident::new("_a$1")).sql(&unquoted).unwrap(),
+        "_a$1"
+    );
+}
+
+#[test]
+fn test_null_comparison() {
+    let lint = |sql: &str| parse_sql_query(sql).unwrap().lint().unwrap();
+    let comparison = |expr: &str, test: &str| Lint {
+        kind: LintKind::NullComparison,
+        severity: Severity::Warning,
+        message: format!(
+            "Comparison {} is never true, use {} to test for NULL",
+            expr, test
+        ),
+    };
+    assert_eq!(
+        lint("select a from t where a = null or null <> b"),
+        vec![
+            comparison(r#""a" = NULL"#, "IS NULL"),
+            comparison(r#"NULL <> "b""#, "IS NOT NULL")
+        ]
+    );
+    assert_eq!(lint("select a from t where a is null and b > 1"), vec![]);
+
+    let res = parse_sql_query("select a from t where a + 1 = null or null <> b").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" WHERE "a" + 1 = NULL OR NULL <> "b""#
+    );
+    let dialect = Dialect {
+        rewrite_null_comparisons: true,
+        ..Dialect::postgres()
+    };
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "a" FROM "t" WHERE "a" + 1 IS NULL OR "b" IS NOT NULL"#
+    );
+
+    // Comparing NULL with NULL is never true, unlike NULL IS NULL
+    let res = parse_sql_query("select a from t where null = null or null <> null").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT "a" FROM "t" WHERE NULL = NULL OR NULL <> NULL"#
+    );
+}
+
+#[test]
+fn test_force_quoted_ident() {
+    let mut res = parse_sql_query("select a from t").unwrap();
+    if let SetExpr::Select(select) = res.body.as_mut() {
+        select.projection = vec![SelectItem::UnnamedExpr(Expr::Identifier(
+            Ident::force_quoted("MixedCase"),
+        ))];
+    }
+    let unquoted = Dialect {
+        quote_style: None,
+        ..Dialect::snowflake()
+    };
+    assert_eq!(res.sql(&unquoted).unwrap(), r#"SELECT "MixedCase" FROM t"#);
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `MixedCase` FROM `t`"
+    );
+
+    if let SetExpr::Select(select) = res.body.as_mut() {
+        select.projection = vec![SelectItem::UnnamedExpr(Expr::Identifier(Ident {
+            quote_style: Some('`'),
+            ..Ident::force_quoted("my col")
+        }))];
+    }
+    assert_eq!(res.sql(&unquoted).unwrap(), "SELECT `my col` FROM t");
+}
+
+#[test]
+fn test_rounding_result_type() {
+    let res = parse_sql_query("select floor(a), ceil(b / 2), c from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::sqlite()).unwrap(),
+        r#"SELECT round("a" - 0.5), round("b" / 2 + 0.5), "c" FROM "t""#
+    );
+    let dialect = Dialect {
+        rounding_result_type: Some(DataType::Double),
+        ..Dialect::sqlite()
+    };
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT CAST(round("a" - 0.5) AS REAL), CAST(round("b" / 2 + 0.5) AS REAL), "c" FROM "t""#
+    );
+}
+
+#[test]
+fn test_wildcard_ilike() {
+    let res = parse_sql_query("select * ilike '%sales%', t.* ilike 'id_' from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::snowflake()).unwrap(),
+        r#"SELECT * ILIKE '%sales%', "t".* ILIKE 'id_' FROM "t""#
+    );
+    assert_matches!(
+        res.sql(&Dialect::postgres()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+    let mut schema = Schema::new();
+    schema.insert("t".to_string(), vec![Ident::new("id")]);
+    assert_matches!(
+        res.clone().expand_wildcards(&schema),
+        Err(SqlGenError::RewriteError(_))
+    );
+}
+
+#[test]
+fn test_modulo_style() {
+    // The remainder has the sign of the dividend with either spelling
+    let res = parse_sql_query("select (-7) % 3, (a + 1) % b from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT (- 7) % 3, ("a" + 1) % "b" FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT MOD((- 7), 3), MOD((`a` + 1), `b`) FROM `t`"
+    );
+}
+
+#[test]
+fn test_wildcard_rename() {
+    let res =
+        parse_sql_query("select * ilike 'a%' rename (a1 as x, a2 as y), t.* rename b as z from t")
+            .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::snowflake()).unwrap(),
+        r#"SELECT * ILIKE 'a%' RENAME ("a1" AS "x", "a2" AS "y"), "t".* RENAME ("b" AS "z") FROM "t""#
+    );
+    let res = parse_sql_query("select * rename a as b from t").unwrap();
+    assert_matches!(
+        res.sql(&Dialect::bigquery()),
+        Err(SqlGenError::UnsupportedFeature { feature, .. }) if feature == "Wildcard RENAME"
+    );
+}
+
+#[test]
+fn test_parameterize() {
+    let res = parse_sql_query(
+        "with c as (select a from t where b = 'x') \
+         select a, count(*) from c where a > 1.5 and d is null and e = null and a in (select a from u where f <> true) \
+         group by 1 having count(*) > 2 order by 1 limit 10",
+    )
+    .unwrap();
+    let (query, values) = res.clone().parameterize(&Dialect::postgres());
+    assert_eq!(
+        query.sql(&Dialect::postgres()).unwrap(),
+        r#"WITH "c" AS (SELECT "a" FROM "t" WHERE "b" = $1) SELECT "a", count(*) FROM "c" WHERE "a" > $2 AND "d" IS NULL AND "e" = NULL AND "a" IN (SELECT "a" FROM "u" WHERE "f" <> $3) GROUP BY 1 HAVING count(*) > $4 ORDER BY 1 LIMIT 10"#
+    );
+    assert_eq!(
+        values,
+        vec![
+            Value::SingleQuotedString("x".to_string()),
+            number("1.5"),
+            Value::Boolean(true),
+            number("2"),
+        ]
+    );
+
+    let (query, values) = res.parameterize_with(&Dialect::postgres(), true);
+    assert!(query
+        .sql(&Dialect::postgres())
+        .unwrap()
+        .contains(r#""e" = $3"#));
+    assert_eq!(values[2], Value::Null);
+    assert_eq!(values.len(), 5);
+}
+
+#[test]
+fn test_parameterize_placeholder_style() {
+    let res =
+        parse_sql_query("select a, b from t where c = 'x' and d > 2 order by b limit 10 offset 20")
+            .unwrap();
+    let (query, values) = res.clone().parameterize(&Dialect::mysql());
+    assert_eq!(
+        query.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a`, `b` FROM `t` WHERE `c` = ? AND `d` > ? ORDER BY `b` LIMIT 10 OFFSET 20"
+    );
+    assert_eq!(
+        values,
+        vec![Value::SingleQuotedString("x".to_string()), number("2")]
+    );
+
+    let (query, values) = res.parameterize(&Dialect::mssql());
+    assert_eq!(
+        query.sql(&Dialect::mssql()).unwrap(),
+        "SELECT [a], [b] FROM [t] WHERE [c] = @p1 AND [d] > @p2 ORDER BY [b] OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY"
+    );
+    assert_eq!(values.len(), 2);
+
+    let res = parse_sql_query("select top 5 a from t where b = 'x'").unwrap();
+    let (query, _) = res.parameterize(&Dialect::mssql());
+    assert_eq!(
+        query.sql(&Dialect::mssql()).unwrap(),
+        "SELECT TOP (5) [a] FROM [t] WHERE [b] = @p1"
+    );
+
+    let res =
+        parse_sql_query("select date_trunc('month', a), date_part('year', a) from t where b = 'x'")
+            .unwrap();
+    let (query, values) = res.parameterize(&Dialect::postgres());
+    assert_eq!(
+        query.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT date_trunc('month', "a"), EXTRACT(YEAR FROM "a") FROM "t" WHERE "b" = $1"#
+    );
+    assert_eq!(values.len(), 1);
+}
+
+#[test]
+fn test_require_order_by_with_offset() {
+    let res = parse_sql_query("select a from t limit 10 offset 20").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM "t" LIMIT 10 OFFSET 20"#
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap_err().to_string(),
+        "sqlgen error: OFFSET and FETCH require an ORDER BY"
+    );
+    let strict = Dialect {
+        require_order_by_with_offset: true,
+        ..Dialect::postgres()
+    };
+    assert_matches!(res.sql(&strict), Err(SqlGenError::DialectError(_)));
+    // A LIMIT alone is fine unless it is rendered as FETCH, and SQL Server
+    // renders it as TOP where it can
+    let res = parse_sql_query("select a from t limit 10").unwrap();
+    assert_eq!(res.sql(&strict).unwrap(), r#"SELECT "a" FROM "t" LIMIT 10"#);
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT TOP (10) [a] FROM [t]"
+    );
+    let res = parse_sql_query("select a from t union select a from u limit 10").unwrap();
+    assert_matches!(
+        res.sql(&Dialect::mssql()),
+        Err(SqlGenError::DialectError(_))
+    );
+
+    let res = parse_sql_query("select a from t order by a limit 10 offset 20").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT [a] FROM [t] ORDER BY [a] OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY"
+    );
+}
+
+#[test]
+fn test_case_insensitive_identifiers() {
+    let res = parse_sql_query(
+        r#"select id, "Foo".id from "Foo" join u using (id) where exists (select 1 from v where v.x = "foo".a)"#,
+    )
+    .unwrap();
+    assert_eq!(res.correlated_subqueries().unwrap().len(), 1);
+    assert_eq!(
+        res.correlated_subqueries_for(&Dialect::mysql())
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(
+        res.correlated_subqueries_for(&Dialect::postgres())
+            .unwrap()
+            .len(),
+        0
+    );
+
+    let res = parse_sql_query(r#"select "foo".id from "Foo" join u using (id)"#).unwrap();
+    let qualified = vec![Lint {
+        kind: LintKind::QualifiedUsingColumn,
+        severity: Severity::Warning,
+        message: r#"Column "foo"."id" is merged by a USING join and shouldn't be qualified"#
+            .to_string(),
+    }];
+    assert_eq!(res.lint_for(&Dialect::mysql()).unwrap(), qualified);
+    assert_eq!(res.lint_for(&Dialect::postgres()).unwrap(), vec![]);
+
+    // Unquoted identifiers are folded, quoted ones compared exactly
+    let res = parse_sql_query(
+        r#"select 1 from Foo where exists (select 1 from v where v.x = "foo".a) and exists (select 1 from v where v.x = FOO.a)"#,
+    )
+    .unwrap();
+    let correlated = |dialect: &Dialect| res.correlated_subqueries_for(dialect).unwrap().len();
+    assert_eq!(correlated(&Dialect::postgres()), 2);
+    assert_eq!(correlated(&Dialect::datafusion()), 2);
+    assert_eq!(correlated(&Dialect::snowflake()), 1);
+    assert_eq!(correlated(&Dialect::oracle()), 1);
+    assert_eq!(correlated(&Dialect::clickhouse()), 0);
+    assert_eq!(correlated(&Dialect::mysql()), 2);
+
+    let res = parse_sql_query(r#"select "FOO".id from foo join u using (id)"#).unwrap();
+    assert_eq!(res.lint_for(&Dialect::snowflake()).unwrap().len(), 1);
+    assert_eq!(res.lint_for(&Dialect::postgres()).unwrap(), vec![]);
+
+    // ORDER BY of SELECT DISTINCT matches aliases and columns by the same rule
+    for sql in [
+        "select distinct a as Foo from t order by foo",
+        "select distinct T.a from t as T order by t.a",
+    ] {
+        let res = parse_sql_query(sql).unwrap();
+        assert_eq!(res.lint_for(&Dialect::postgres()).unwrap(), vec![]);
+        assert_eq!(res.lint_for(&Dialect::mysql()).unwrap(), vec![]);
+        assert_eq!(res.lint_for(&Dialect::clickhouse()).unwrap().len(), 1);
+    }
+    let res = parse_sql_query(r#"select distinct a as "Foo" from t order by foo"#).unwrap();
+    assert_eq!(res.lint_for(&Dialect::postgres()).unwrap().len(), 1);
+}
+
+#[test]
+fn test_require_derived_alias() {
+    let res = parse_sql_query("select a from (select a from t)").unwrap();
+    assert_matches!(
+        res.sql(&Dialect::postgres()),
+        Err(SqlGenError::UnsupportedFeature { feature, .. })
+            if feature == "Derived table without an alias"
+    );
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT `a` FROM (SELECT `a` FROM `t`)"
+    );
+
+    let res = parse_sql_query("select a from (select a from t) as s").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT "a" FROM (SELECT "a" FROM "t") AS "s""#
+    );
+}
+
+#[test]
+fn test_count_distinct_columns() {
+    let res = parse_sql_query("select count(distinct a, b), count(distinct c) from t").unwrap();
+    assert_eq!(
+        res.sql(&Dialect::mysql()).unwrap(),
+        "SELECT count(DISTINCT `a`, `b`), count(DISTINCT `c`) FROM `t`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap(),
+        r#"SELECT count(DISTINCT CASE WHEN "a" IS NULL OR "b" IS NULL THEN NULL ELSE ("a", "b") END), count(DISTINCT "c") FROM "t""#
+    );
+    assert_eq!(
+        res.sql(&Dialect::bigquery()).unwrap(),
+        "SELECT count(DISTINCT CASE WHEN `a` IS NULL OR `b` IS NULL THEN NULL ELSE CAST(`a` AS STRING) || '|' || CAST(`b` AS STRING) END), count(DISTINCT `c`) FROM `t`"
+    );
+    assert_eq!(
+        res.sql(&Dialect::mssql()).unwrap(),
+        "SELECT count(DISTINCT CASE WHEN [a] IS NULL OR [b] IS NULL THEN NULL ELSE concat(CAST([a] AS NVARCHAR(MAX)), '|', CAST([b] AS NVARCHAR(MAX))) END), count(DISTINCT [c]) FROM [t]"
+    );
+    assert_eq!(
+        res.sql(&Dialect::oracle()).unwrap(),
+        r#"SELECT count(DISTINCT CASE WHEN "a" IS NULL OR "b" IS NULL THEN NULL ELSE CAST("a" AS VARCHAR2(4000)) || '|' || CAST("b" AS VARCHAR2(4000)) END), count(DISTINCT "c") FROM "t""#
+    );
+}
+
+#[test]
+fn test_empty_projection() {
+    let mut res = parse_sql_query("select a from t").unwrap();
+    if let SetExpr::Select(select) = res.body.as_mut() {
+        select.projection.clear();
+    }
+    assert_eq!(
+        res.sql(&Dialect::postgres()).unwrap_err().to_string(),
+        "sqlgen error: SELECT must have at least one item in its projection"
+    );
+    assert_matches!(
+        res.sql(&Dialect::mysql()),
+        Err(SqlGenError::EmptyProjection)
+    );
+}
+
+#[test]
+fn test_no_redundant_whitespace() {
+    let res = parse_sql_query(
+        "select a, x from t join u on t.id = u.id left join v using (id) \
+         lateral view explode(arr) e as x where a > 1",
+    )
+    .unwrap();
+    let join = res.body.as_ref().clone();
+    let spark = Dialect {
+        unknown_function_policy: UnknownFunctionPolicy::PassThrough,
+        ..Dialect::spark()
+    };
+    for dialect in [spark.clone(), Dialect::generic()] {
+        let sql = res.sql(&dialect).unwrap();
+        assert!(!sql.contains("  "), "{}", sql);
+        assert_eq!(sql.trim(), sql);
+    }
+    if let SetExpr::Select(select) = join {
+        let join = &select.from[0].joins[0];
+        assert_eq!(join.sql(&spark).unwrap(), "JOIN `u` ON `t`.`id` = `u`.`id`");
+        assert_eq!(
+            select.lateral_views[0].sql(&spark).unwrap(),
+            "LATERAL VIEW explode(`arr`) `e` AS `x`"
+        );
+    }
+}
+
+#[test]
+fn test_supports_function() {
+    let sqlite = Dialect::sqlite();
+    assert!(sqlite.supports_function("abs"));
+    assert!(sqlite.supports_function("ABS"));
+    // Rendered by a transform
+    assert!(sqlite.supports_function("floor"));
+    assert!(!sqlite.supports_function("regexp_replace"));
+    assert!(!sqlite.supports_function("no_such_function"));
+    assert!(Dialect::sqlite()
+        .with_function("my_udf")
+        .supports_function("my_udf"));
+
+    let functions: Vec<&str> = sqlite.functions().collect();
+    assert!(functions.contains(&"abs"));
+    assert!(functions.contains(&"floor"));
+    assert!(!functions.contains(&"regexp_replace"));
+    let mut sorted = functions.clone();
+    sorted.sort_unstable();
+    assert_eq!(functions, sorted);
+}
+
+#[test]
+fn test_dialect_capabilities() {
+    use sqlgen::dialect::Capabilities;
+    let postgres = Dialect::postgres().capabilities();
+    assert_eq!(
+        postgres,
+        Capabilities {
+            aggregate_filter: true,
+            aggregate_order_by: true,
+            nulls_ordering: true,
+            ilike: true,
+            like_any: false,
+            regex_match: true,
+            boolean_coercion: false,
+            try_cast: false,
+            arrays: true,
+            array_index: true,
+            structs: false,
+            correlated_select_subqueries: true,
+            between_symmetric: true,
+            window_frame_exclude: true,
+            groups_frames: true,
+            table_sample: true,
+            table_sample_repeatable: true,
+            limit_expression: true,
+            limit_by: false,
+            query_settings: false,
+            optimizer_hints: false,
+            natural_join: true,
+            wildcard_ilike: false,
+            wildcard_rename: false,
+            upsert: true,
+            create_or_replace_table: false,
+            materialized_views: true,
+            transactions: true,
+            unicode_strings: true,
+            qualify: false,
+            table_alias_as: true,
+            create_or_replace_view: true,
+            create_or_replace_materialized_view: false,
+        }
+    );
+    assert_eq!(
+        Dialect::mysql().capabilities(),
+        Capabilities {
+            aggregate_filter: false,
+            aggregate_order_by: false,
+            nulls_ordering: false,
+            ilike: false,
+            boolean_coercion: true,
+            arrays: false,
+            array_index: false,
+            between_symmetric: false,
+            window_frame_exclude: false,
+            groups_frames: false,
+            table_sample: false,
+            table_sample_repeatable: false,
+            limit_expression: false,
+            optimizer_hints: true,
+            materialized_views: false,
+            unicode_strings: false,
+            ..postgres
+        }
+    );
+    assert!(!Dialect::mssql().capabilities().regex_match);
+    assert!(!Dialect::sqlite().capabilities().regex_match);
+}
+
+#[test]
+fn test_shared_dialect_not_cloned() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Weak;
+
+    // Counts the references to itself when called, which a clone of the
+    // dialect's transforms would add to
+    #[derive(Debug)]
+    struct CountingTransform {
+        this: Weak<CountingTransform>,
+        max_count: AtomicUsize,
+    }
+    impl FunctionTransform for CountingTransform {
+        fn transform(
+            &self,
+            _name: &str,
+            args: &[FunctionArg],
+            dialect: &Dialect,
+        ) -> Result<String, SqlGenError> {
+            self.max_count
+                .fetch_max(self.this.strong_count(), Ordering::SeqCst);
+            Ok(format!("-({})", args[0].sql(dialect)?))
+        }
+    }
+
+    let transform = Arc::new_cyclic(|this| CountingTransform {
+        this: this.clone(),
+        max_count: AtomicUsize::new(0),
+    });
+    let dialect: SharedDialect = Arc::new(Dialect {
+        rounding_result_type: Some(DataType::Double),
+        dummy_from_table: Some(ObjectName(vec![Ident::new("dual")])),
+        ..Dialect::postgres().with_function_transform("negate", transform.clone())
+    });
+    let this = transform.this.clone();
+    drop(transform);
+
+    let res = parse_sql_query(
+        "select negate(floor(a)), b collate de_DE, c from (select negate(1) as c) as t",
+    )
+    .unwrap();
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                assert_eq!(
+                    res.sql(&dialect).unwrap(),
+                    r#"SELECT -(CAST(floor("a") AS DOUBLE PRECISION)), "b" COLLATE "de_DE", "c" FROM (SELECT -(1) AS "c" FROM "dual") AS "t""#
+                );
+            });
+        }
+    });
+    let transform = this.upgrade().unwrap();
+    assert_eq!(transform.max_count.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_function_set_normalized() {
+    let mut functions: FunctionSet = ["Abs", "UPPER"].into_iter().collect();
+    assert!(!functions.insert("abs"));
+    assert!(functions.insert("My_Udf"));
+    let mut names: Vec<&str> = functions.iter().collect();
+    names.sort_unstable();
+    assert_eq!(names, vec!["abs", "my_udf", "upper"]);
+    assert!(functions.contains("UPPER") && functions.contains("upper"));
+    assert!(functions.remove("MY_UDF"));
+    assert_eq!(functions.len(), 2);
+
+    let mut dialect = Dialect::postgres().with_function("Add_One");
+    dialect.functions.insert("Add_Two");
+    assert!(dialect.supports_function("add_one"));
+    assert!(dialect.supports_function("ADD_TWO"));
+    let names: Vec<&str> = dialect.functions().collect();
+    assert!(names.contains(&"add_one") && names.contains(&"add_two"));
+    let res = parse_sql_query("select ADD_ONE(a), add_two(b) from t").unwrap();
+    assert_eq!(
+        res.sql(&dialect).unwrap(),
+        r#"SELECT ADD_ONE("a"), add_two("b") FROM "t""#
+    );
+}
+
+#[test]
+fn test_escaped_string_control_characters() {
+    let value = Value::EscapedStringLiteral("a\u{1}b\u{c}c\u{8}d\u{1b}\n".to_string());
+    let sql = value.sql(&Dialect::postgres()).unwrap();
+    assert_eq!(sql, r"E'a\u0001b\fc\bd\u001B\n'");
+    assert!(!sql.contains(|c: char| c.is_control()));
+
+    // The escapes are read back into the same string
+    let select = verified_only_select(&format!("select {}", sql));
+    assert_eq!(
+        expr_from_projection(only(&select.projection)),
+        &Expr::Value(value)
+    );
+    assert!(parse_sql_query(r"select E'\u00'").is_err());
+
+    assert_eq!(
+        Value::EscapedStringLiteral("a\0b".to_string())
+            .sql(&Dialect::postgres())
+            .unwrap_err()
+            .to_string(),
+        "sqlgen error: String literals can't contain NUL characters"
+    );
+}
+
+#[test]
+fn test_unicode_string_literal() {
+    let value = Value::UnicodeStringLiteral("café 'au' \\ lait 🎉".to_string());
+    assert_eq!(
+        value.sql(&Dialect::postgres()).unwrap(),
+        r"U&'caf\00E9 ''au'' \\ lait \+01F389'"
+    );
+    assert_eq!(
+        value.sql(&Dialect::trino()).unwrap(),
+        r"U&'caf\00E9 ''au'' \\ lait \+01F389'"
+    );
+    assert_matches!(
+        value.sql(&Dialect::mysql()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+}
+
+#[test]
+fn test_druid_time_series_query() {
+    let sql = "SELECT date_trunc('hour', __time) AS t, approx_distinct(user_id) AS users \
+               FROM events \
+               WHERE __time >= TIMESTAMP '2022-01-01 00:00:00' \
+               GROUP BY date_trunc('hour', __time) \
+               ORDER BY t";
+    let query = parse_sql_query(sql).unwrap();
+    assert_eq!(
+        query.sql(&Dialect::druid()).unwrap(),
+        r#"SELECT TIME_FLOOR("__time", 'PT1H') AS "t", APPROX_COUNT_DISTINCT("user_id") AS "users" FROM "events" WHERE "__time" >= TIMESTAMP '2022-01-01 00:00:00' GROUP BY TIME_FLOOR("__time", 'PT1H') ORDER BY "t""#
+    );
+}
+
+#[test]
+fn test_druid_date_trunc_units() {
+    for (unit, period) in [("day", "P1D"), ("MONTH", "P1M"), ("quarter", "P3M")] {
+        let query = parse_sql_query(&format!("SELECT date_trunc('{}', ts) FROM t", unit)).unwrap();
+        assert_eq!(
+            query.sql(&Dialect::druid()).unwrap(),
+            format!(r#"SELECT TIME_FLOOR("ts", '{}') FROM "t""#, period)
+        );
+    }
+}
+
+#[test]
+fn test_druid_date_trunc_time_zone() {
+    let query = parse_sql_query("SELECT date_trunc('day', ts, 'America/New_York') FROM t").unwrap();
+    assert_eq!(
+        query.sql(&Dialect::druid()).unwrap(),
+        r#"SELECT TIME_FLOOR("ts", 'P1D', NULL, 'America/New_York') FROM "t""#
+    );
+}
+
+#[test]
+fn test_druid_date_trunc_unsupported_unit() {
+    let query = parse_sql_query("SELECT date_trunc('fortnight', ts) FROM t").unwrap();
+    assert_eq!(
+        query.sql(&Dialect::druid()).unwrap_err(),
+        SqlGenError::DialectError("Unsupported date_trunc unit for dialect: fortnight".to_string())
+    );
+}
+
+#[test]
+fn test_druid_unsupported_function() {
+    let query = parse_sql_query("SELECT md5(name) FROM t").unwrap();
+    assert!(query.sql(&Dialect::druid()).is_err());
+}
+
+#[test]
+fn test_qualify() {
+    let res = parse_sql_query(
+        "select a, row_number() over (partition by b order by c) as r from t qualify r = 1",
+    )
+    .unwrap();
+    assert_eq!(
+        res.sql(&Dialect::snowflake()).unwrap(),
+        r#"SELECT "a", row_number() OVER (PARTITION BY "b" ORDER BY "c") AS "r" FROM "t" QUALIFY "r" = 1"#
+    );
+    assert_matches!(
+        res.sql(&Dialect::postgres()),
+        Err(SqlGenError::UnsupportedFeature { .. })
+    );
+
+    for clause in ["cluster by", "distribute by", "sort by"] {
+        assert_eq!(
+            parse_sql_query(&format!("select a from t {} a", clause)).unwrap_err(),
+            SqlGenError::ParserError(format!("{} is not supported", clause.to_uppercase()))
+        );
+    }
+}